@@ -1,4 +1,4 @@
-use crate::CollisionDetails;
+use crate::{CollisionDetails, Rounded, Sphere};
 
 
 #[derive(Debug, Clone, Copy)]
@@ -56,6 +56,121 @@ impl BoundingBox {
         self.center
     }
 
+    /// 박스를 감싸는 월드 공간의 AABB를 반환한다.  
+    /// AABB인 경우 자기 자신과 같다.  
+    pub fn aabb(&self) -> BoundingBox {
+        match self.rotation {
+            Some(rotation) => {
+                // 회전행렬 각 성분의 절댓값으로 extents를 투영하면 감싸는 AABB의 extents가 된다.
+                let abs_rotation = glam::Mat3::from_cols(
+                    rotation.x_axis.abs(),
+                    rotation.y_axis.abs(),
+                    rotation.z_axis.abs(),
+                );
+                BoundingBox::new(self.center, abs_rotation * self.extents)
+            }
+            None => BoundingBox::new(self.center, self.extents),
+        }
+    }
+
+    /// AABB 기준의 최소 좌표
+    pub fn min(&self) -> glam::Vec3 {
+        let aabb = self.aabb();
+        aabb.center - aabb.extents
+    }
+
+    /// AABB 기준의 최대 좌표
+    pub fn max(&self) -> glam::Vec3 {
+        let aabb = self.aabb();
+        aabb.center + aabb.extents
+    }
+
+    /// 최소, 최대 좌표로부터 AABB 생성
+    pub fn from_min_max(min: glam::Vec3, max: glam::Vec3) -> Self {
+        Self::new((min + max) * 0.5, (max - min) * 0.5)
+    }
+
+    /// 두 박스를 모두 감싸는 AABB를 반환한다.  
+    /// OBB는 감싸는 AABB로 변환한 뒤 합친다.  
+    pub fn union(&self, other: &BoundingBox) -> BoundingBox {
+        BoundingBox::from_min_max(self.min().min(other.min()), self.max().max(other.max()))
+    }
+
+    /// 두 박스가 겹치는 영역의 AABB를 반환한다.  
+    /// 겹치지 않으면 None, 접하는 경우에는 두께가 0인 박스를 반환한다.  
+    pub fn intersection(&self, other: &BoundingBox) -> Option<BoundingBox> {
+        let min = self.min().max(other.min());
+        let max = self.max().min(other.max());
+        if min.cmple(max).all() {
+            Some(BoundingBox::from_min_max(min, max))
+        } else {
+            None
+        }
+    }
+
+    /// other가 self 안에 완전히 포함되는지 확인한다.
+    pub fn contains(&self, other: &BoundingBox) -> bool {
+        if self.rotation.is_none() && other.rotation.is_none() {
+            return self.min().cmple(other.min()).all() && other.max().cmple(self.max()).all();
+        }
+        other.get_vertices().iter().all(|v| self.contains_point(v))
+    }
+
+    /// 점이 박스 안(경계 포함)에 있는지 확인한다.
+    pub fn contains_point(&self, point: &glam::Vec3A) -> bool {
+        let local = self.world_to_local(point);
+        local.abs().cmple(glam::Vec3A::from(self.extents)).all()
+    }
+
+    /// 월드 공간의 점을 박스의 로컬 공간(center가 원점, 축 정렬)으로 변환한다.
+    pub(crate) fn world_to_local(&self, point: &glam::Vec3A) -> glam::Vec3A {
+        let offset = point - glam::Vec3A::from(self.center);
+        match self.rotation {
+            // 회전행렬의 전치행렬은 역행렬과 같다.
+            Some(rotation) => glam::Vec3A::from(rotation.transpose() * glam::Vec3::from(offset)),
+            None => offset,
+        }
+    }
+
+    /// 박스의 로컬 공간의 점을 월드 공간으로 변환한다.
+    pub(crate) fn local_to_world(&self, local: &glam::Vec3A) -> glam::Vec3A {
+        let center = glam::Vec3A::from(self.center);
+        match self.rotation {
+            Some(rotation) => center + glam::Vec3A::from(rotation * glam::Vec3::from(*local)),
+            None => center + local,
+        }
+    }
+
+    /// 모든 방향으로 amount만큼 확장된 박스를 반환한다. (회전은 유지)  
+    /// 음수인 경우 축소되며, extents는 0보다 작아지지 않는다.  
+    pub fn expanded_by(&self, amount: f32) -> BoundingBox {
+        BoundingBox {
+            center: self.center,
+            extents: (self.extents + glam::Vec3::splat(amount)).max(glam::Vec3::ZERO),
+            rotation: self.rotation,
+        }
+    }
+
+    /// 박스의 겉넓이
+    pub fn surface_area(&self) -> f32 {
+        let size = self.extents * 2.0;
+        2.0 * (size.x * size.y + size.y * size.z + size.z * size.x)
+    }
+
+    /// 박스의 부피
+    pub fn volume(&self) -> f32 {
+        let size = self.extents * 2.0;
+        size.x * size.y * size.z
+    }
+
+    /// 박스를 감싸는 구를 반환한다.
+    pub fn bounding_sphere(&self) -> Sphere {
+        Sphere {
+            center: self.center,
+            radius: self.extents.length(),
+        }
+    }
+
     // AABB collision detection
     pub fn aabb_collision(&self, other: &BoundingBox) -> bool {
         let x_overlap = (self.center.x - other.center.x).abs() <= (self.extents.x + other.extents.x);
@@ -77,6 +192,8 @@ impl BoundingBox {
 
         let mut min_penetration = f32::MAX;
         let mut min_element = 0;
+        // 접하기만 해서 penetration이 0이면 부호로 방향을 나타낼 수 없으므로 따로 기억한다.
+        let mut self_on_left = false;
 
         for i in 0..3 {
             let penetration = if overlap_min[i] <= overlap_max[i] {
@@ -98,18 +215,19 @@ impl BoundingBox {
             if penetration.abs() < min_penetration.abs() {
                 min_penetration = penetration;
                 min_element = i;
+                self_on_left = self.center[i] < other.center[i];
             }
         }
 
         let mut collision_normal = match min_element {
             0 => glam::Vec3A::X,
             1 => glam::Vec3A::Y,
-            2 => glam::Vec3A::Z,
-            _ => glam::Vec3A::ZERO,
+            _ => glam::Vec3A::Z,
         };
 
-        if min_penetration == 0.0 {
-            collision_normal = glam::Vec3A::ZERO;
+        // 접하기만 한 경우 normal을 other에서 self를 향하게 하여, normal * penetration의 방향과 맞춘다.
+        if min_penetration == 0.0 && self_on_left {
+            collision_normal = -collision_normal;
         }
 
         Some(CollisionDetails {
@@ -120,90 +238,12 @@ impl BoundingBox {
 
     // SAT 를 이용한 OBB collision detection
     pub fn obb_collision(&self, other: &BoundingBox) -> bool {
-        let self_axes = self.get_axes();
-        let other_axes = other.get_axes();
-
-        // cross products > vector
-        let cross_products: [glam::Vec3A; 9] = [
-            self_axes[0].cross(other_axes[0]),
-            self_axes[0].cross(other_axes[1]),
-            self_axes[0].cross(other_axes[2]),
-            self_axes[1].cross(other_axes[0]),
-            self_axes[1].cross(other_axes[1]),
-            self_axes[1].cross(other_axes[2]),
-            self_axes[2].cross(other_axes[0]),
-            self_axes[2].cross(other_axes[1]),
-            self_axes[2].cross(other_axes[2]),
-        ];
-
-        // 모든 축 확인
-        let axes_to_test = self_axes.iter()
-            .chain(other_axes.iter())       // 양 OBB의 지역 축
-            .chain(cross_products.iter())   // Cross product 축
-            .filter(|&axis| !axis.is_nan() && *axis != glam::Vec3A::ZERO); // NaN, Zero 제외
-
-        let vbox1 = VertexBox::from(self);
-        let vbox2 = VertexBox::from(other);
-
-        for axis in axes_to_test {
-            if !vbox1.overlaps_on_axis(&vbox2, axis) {
-                return false; // if 분리된 축이 존재 = 충돌 없음
-            }
-        }
-
-        true // 분리된 축 없음 = 충돌
+        ObbFrame::from(self).collides(&ObbFrame::from(other))
     }
     
     // SAT 를 이용한 OBB collision detection + 충돌 상세 정보 반환
     pub fn obb_collision_details(&self, other: &BoundingBox) -> Option<CollisionDetails> {
-        let self_axes = self.get_axes();
-        let other_axes = other.get_axes();
-
-        // cross products > vector
-        let cross_products: [glam::Vec3A; 9] = [
-            self_axes[0].cross(other_axes[0]),
-            self_axes[0].cross(other_axes[1]),
-            self_axes[0].cross(other_axes[2]),
-            self_axes[1].cross(other_axes[0]),
-            self_axes[1].cross(other_axes[1]),
-            self_axes[1].cross(other_axes[2]),
-            self_axes[2].cross(other_axes[0]),
-            self_axes[2].cross(other_axes[1]),
-            self_axes[2].cross(other_axes[2]),
-        ];
-
-        // 모든 축 확인
-        let axes_to_test = self_axes.iter()
-            .chain(other_axes.iter())       // 양 OBB의 지역 축
-            .chain(cross_products.iter())   // Cross product 축
-            .filter(|&axis| !axis.is_nan() && *axis != glam::Vec3A::ZERO); // NaN, Zero 제외
-
-        let vbox1 = VertexBox::from(self);
-        let vbox2 = VertexBox::from(other);
-
-        let mut min_penetration = f32::MAX;
-        let mut collision_normal = glam::Vec3A::ZERO;
-
-        for axis in axes_to_test {
-            match vbox1.overlaps_length_on_axis(&vbox2, axis) {
-                Some(penetration) => {
-                    if penetration.abs() < min_penetration.abs() {
-                        min_penetration = penetration;
-                        collision_normal = axis.normalize();  // 최소 침투가 있는 축을 충돌 노말로 설정
-                    }
-                }
-                None => return None, // 분리된 축이 존재 => 충돌 없음
-            }
-        }
-
-        if min_penetration != f32::MAX {
-            Some(CollisionDetails {
-                normal: collision_normal,
-                penetration: min_penetration,
-            })
-        } else {
-            None // 침투가 없으면 충돌 없음
-        }
+        ObbFrame::from(self).collision_details(&ObbFrame::from(other))
     }
 
     // OBB의 지역 축 가져오기 (회전 행렬의 열)
@@ -216,6 +256,20 @@ impl BoundingBox {
         ]
     }
 
+    /// 모서리를 radius만큼 둥글게 만든 박스  
+    /// 바깥 크기는 그대로이고, extents에서 radius를 뺀 박스가 core가 된다. (margin-based GJK)  
+    pub fn rounded(&self, radius: f32) -> Rounded<VertexBox> {
+        let radius = radius.clamp(0.0, self.extents.min_element());
+        let core = BoundingBox {
+            extents: self.extents - glam::Vec3::splat(radius),
+            ..*self
+        };
+        Rounded {
+            shape: VertexBox::from(&core),
+            radius,
+        }
+    }
+
     // 월드 공간에서 OBB의 정점 가져오기
     pub fn get_vertices(&self) -> [glam::Vec3A; 8] {
         let center = glam::Vec3A::from(self.center);
@@ -240,73 +294,236 @@ impl BoundingBox {
 }
 
 
-pub struct VertexBox {
-    vertices: [glam::Vec3A; 8],
+/// 월드 공간의 정점, 축, 감싸는 AABB를 미리 계산해 둔 OBB  
+/// 박스가 움직이거나 회전할 때만 다시 만들면 되므로, 한 박스를 여러 물체와 검사할 때 그 비용을 한 번만 치른다.  
+#[derive(Debug, Clone, Copy)]
+pub struct PreparedBox {
+    bounding_box: BoundingBox,
+    vertex_box: VertexBox,
+    axes: [glam::Vec3A; 3],
+    aabb: BoundingBox,
 }
 
-impl From<&BoundingBox> for VertexBox {
-    fn from(boundingbox: &BoundingBox) -> Self {
+impl From<&BoundingBox> for PreparedBox {
+    fn from(bounding_box: &BoundingBox) -> Self {
         Self {
-            vertices: boundingbox.get_vertices(),
+            bounding_box: *bounding_box,
+            vertex_box: VertexBox::from(bounding_box),
+            axes: bounding_box.get_axes(),
+            aabb: bounding_box.aabb(),
         }
     }
 }
 
-impl VertexBox {
-    // OBB를 축에 투영하고 투영 간격(최소, 최대)을 반환하는 메서드
-    fn project_onto_axis(&self, axis: &glam::Vec3A) -> (f32, f32) {
-        //각 정점을 축에 투영하는 dot product 계산
-        let projections: [f32; 8] = [
-            axis.dot(self.vertices[0]),
-            axis.dot(self.vertices[1]),
-            axis.dot(self.vertices[2]),
-            axis.dot(self.vertices[3]),
-            axis.dot(self.vertices[4]),
-            axis.dot(self.vertices[5]),
-            axis.dot(self.vertices[6]),
-            axis.dot(self.vertices[7]),
-        ];
+impl PreparedBox {
+    /// 위치와 회전을 바꾸고 정점, 축, AABB를 다시 계산한다.
+    pub fn set_transform(&mut self, center: glam::Vec3, rotation: glam::Mat3) {
+        let mut bounding_box = self.bounding_box;
+        bounding_box.center = center;
+        bounding_box.set_rotation(rotation);
+        *self = Self::from(&bounding_box);
+    }
 
-        let min_proj = *projections.iter().min_by(|a, b| a.partial_cmp(b).unwrap()).unwrap();
-        let max_proj = *projections.iter().max_by(|a, b| a.partial_cmp(b).unwrap()).unwrap();
+    pub fn bounding_box(&self) -> &BoundingBox {
+        &self.bounding_box
+    }
 
-        (min_proj, max_proj)
+    pub fn vertex_box(&self) -> &VertexBox {
+        &self.vertex_box
     }
 
-    // 두 OBB가 주어진 축에서 겹치는지 확인
-    fn overlaps_on_axis(&self, other: &VertexBox, axis: &glam::Vec3A) -> bool {
-        let (min_a, max_a) = self.project_onto_axis(axis);
-        let (min_b, max_b) = other.project_onto_axis(axis);
+    /// 월드 공간의 정점
+    pub fn get_vertices(&self) -> &[glam::Vec3A; 8] {
+        self.vertex_box.get_vertices()
+    }
 
-        // 축에서 투영이 겹치는지 확인
-        max_a >= min_b && max_b >= min_a
+    /// 월드 공간의 지역 축 (회전 행렬의 열)
+    pub fn axes(&self) -> &[glam::Vec3A; 3] {
+        &self.axes
     }
 
-    // 두 OBB가 주어진 축에서 겹치는지 확인
-    fn overlaps_length_on_axis(&self, other: &VertexBox, axis: &glam::Vec3A) -> Option<f32> {
-        let (min_a, max_a) = self.project_onto_axis(axis);
-        let (min_b, max_b) = other.project_onto_axis(axis);
+    /// 박스를 감싸는 월드 공간의 AABB
+    pub fn aabb(&self) -> &BoundingBox {
+        &self.aabb
+    }
 
-        let overlap_min = min_a.max(min_b);
-        let overlap_max = max_a.min(max_b);
-        
-        if overlap_min <= overlap_max {
-            // 겹쳤을 경우, 겹침의 깊이를 반환
-            let mid_a = (max_a + min_a) * 0.5;
-            let mid_b = (max_b + min_b) * 0.5;
-            if mid_a < mid_b {
-                // self가 other보다 왼쪽에 있을 때
-                Some(overlap_min - overlap_max)
-            } else {
-                // self가 other보다 오른쪽에 있을 때
-                Some(overlap_max - overlap_min)
+    // SAT 를 이용한 OBB collision detection
+    pub fn obb_collision(&self, other: &PreparedBox) -> bool {
+        // 감싸는 AABB끼리 떨어져 있으면 OBB끼리도 떨어져 있다.
+        self.aabb.aabb_collision(&other.aabb)
+            && ObbFrame::from(self).collides(&ObbFrame::from(other))
+    }
+    
+    // SAT 를 이용한 OBB collision detection + 충돌 상세 정보 반환
+    pub fn obb_collision_details(&self, other: &PreparedBox) -> Option<CollisionDetails> {
+        if !self.aabb.aabb_collision(&other.aabb) {
+            return None;
+        }
+        ObbFrame::from(self).collision_details(&ObbFrame::from(other))
+    }
+}
+
+
+/// 두 축이 거의 평행하면 외적이 0에 가까워져 분리축을 잘못 찾을 수 있으므로, 회전 행렬 성분의 절댓값에 더하는 값
+const PARALLEL_EPSILON: f32 = 1.0e-6;
+
+/// 해석적 SAT에 필요한 OBB의 중심, 축, extents  
+/// 정점을 투영하지 않고, 두 OBB의 상대 회전 행렬 성분과 extents로 각 축에 대한 투영 반지름을 구한다. (Gottschalk)  
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct ObbFrame {
+    center: glam::Vec3A,
+    axes: [glam::Vec3A; 3],
+    extents: [f32; 3],
+}
+
+/// 분리축 하나에 대한 두 OBB의 투영
+#[derive(Debug, Clone, Copy)]
+struct AxisProjection {
+    /// 0 ~ 2: self의 축, 3 ~ 5: other의 축, 6 ~ 14: self의 축 x other의 축
+    axis: usize,
+    /// 두 중심 사이의 거리 (other - self)
+    distance: f32,
+    /// 두 OBB의 투영 반지름
+    self_radius: f32,
+    other_radius: f32,
+    /// 축의 길이 (외적 축은 1보다 작다.)
+    length: f32,
+}
+
+impl From<&BoundingBox> for ObbFrame {
+    fn from(bounding_box: &BoundingBox) -> Self {
+        Self {
+            center: glam::Vec3A::from(bounding_box.center),
+            axes: bounding_box.get_axes(),
+            extents: bounding_box.extents.to_array(),
+        }
+    }
+}
+
+impl From<&PreparedBox> for ObbFrame {
+    fn from(prepared_box: &PreparedBox) -> Self {
+        Self {
+            center: glam::Vec3A::from(prepared_box.bounding_box.center),
+            axes: prepared_box.axes,
+            extents: prepared_box.bounding_box.extents.to_array(),
+        }
+    }
+}
+
+impl ObbFrame {
+    /// 15개의 분리축 (self의 축 3개, other의 축 3개, 두 축의 외적 9개)에 대한 투영을 차례로 구한다.  
+    /// 필요한 축까지만 계산하도록 iterator로 반환한다.  
+    fn projections(&self, other: &ObbFrame) -> impl Iterator<Item = AxisProjection> {
+        // rotation[i][j] = self의 i축 · other의 j축 (other의 축을 self의 좌표계로 나타낸 회전 행렬)
+        let rotation: [[f32; 3]; 3] = std::array::from_fn(|i| std::array::from_fn(|j| self.axes[i].dot(other.axes[j])));
+        let abs_rotation = rotation.map(|row| row.map(|value| value.abs() + PARALLEL_EPSILON));
+        // self의 좌표계로 나타낸 두 중심 사이의 벡터
+        let offset = other.center - self.center;
+        let translation: [f32; 3] = std::array::from_fn(|i| self.axes[i].dot(offset));
+        let (a, b) = (self.extents, other.extents);
+
+        (0..15).map(move |axis| match axis {
+            // self의 축
+            0..3 => {
+                let i = axis;
+                AxisProjection {
+                    axis,
+                    distance: translation[i],
+                    self_radius: a[i],
+                    other_radius: b[0] * abs_rotation[i][0] + b[1] * abs_rotation[i][1] + b[2] * abs_rotation[i][2],
+                    length: 1.0,
+                }
+            }
+            // other의 축
+            3..6 => {
+                let j = axis - 3;
+                AxisProjection {
+                    axis,
+                    distance: translation[0] * rotation[0][j] + translation[1] * rotation[1][j] + translation[2] * rotation[2][j],
+                    self_radius: a[0] * abs_rotation[0][j] + a[1] * abs_rotation[1][j] + a[2] * abs_rotation[2][j],
+                    other_radius: b[j],
+                    length: 1.0,
+                }
+            }
+            // self의 i축 x other의 j축
+            _ => {
+                let (i, j) = ((axis - 6) / 3, (axis - 6) % 3);
+                let (i1, i2) = ((i + 1) % 3, (i + 2) % 3);
+                let (j1, j2) = ((j + 1) % 3, (j + 2) % 3);
+                AxisProjection {
+                    axis,
+                    distance: translation[i2] * rotation[i1][j] - translation[i1] * rotation[i2][j],
+                    self_radius: a[i1] * abs_rotation[i2][j] + a[i2] * abs_rotation[i1][j],
+                    other_radius: b[j1] * abs_rotation[i][j2] + b[j2] * abs_rotation[i][j1],
+                    // 두 축이 단위벡터이므로 |a x b| = sin = sqrt(1 - cos^2)
+                    length: (1.0 - rotation[i][j].powi(2)).max(0.0).sqrt(),
+                }
+            }
+        })
+    }
+
+    /// 분리축이 하나라도 있으면 충돌하지 않는다.
+    pub(crate) fn collides(&self, other: &ObbFrame) -> bool {
+        self.projections(other)
+            .all(|projection| projection.distance.abs() <= projection.self_radius + projection.other_radius)
+    }
+
+    /// 침투 깊이가 가장 작은 축을 충돌 노말로 한다.  
+    /// normal은 축 방향 그대로이고, other가 축의 양의 방향에 있으면 penetration이 음수이다.  
+    pub(crate) fn collision_details(&self, other: &ObbFrame) -> Option<CollisionDetails> {
+        let mut min_penetration = f32::MAX;
+        let mut collision_axis = None;
+        let mut other_on_positive_side = false;
+
+        for projection in self.projections(other) {
+            let AxisProjection { axis, distance, self_radius, other_radius, length } = projection;
+            if distance.abs() > self_radius + other_radius {
+                return None; // 분리된 축이 존재 => 충돌 없음
+            }
+            // 거의 평행한 축끼리의 외적 제외 (길이로 나누면 오차가 커진다.)
+            if length.powi(2) <= 1.0e-6 {
+                continue;
+            }
+            // 두 투영 구간이 겹치는 길이 (한 구간이 다른 구간을 포함하면 짧은 구간의 길이)
+            let overlap = self_radius.min(distance + other_radius) - (-self_radius).max(distance - other_radius);
+            let penetration = if distance > 0.0 { -overlap / length } else { overlap / length };
+            if penetration.abs() < min_penetration.abs() {
+                min_penetration = penetration;
+                collision_axis = Some(axis);
+                other_on_positive_side = distance > 0.0;
             }
-        } else {
-            // 겹치지 않는 경우
-            None
         }
+
+        let mut normal = match collision_axis? {
+            axis @ 0..3 => self.axes[axis],
+            axis @ 3..6 => other.axes[axis - 3],
+            axis => self.axes[(axis - 6) / 3].cross(other.axes[(axis - 6) % 3]).normalize(),
+        };
+        // 접하기만 해서 penetration이 0이면 부호가 사라지므로 normal을 other에서 self를 향하게 뒤집는다.
+        if min_penetration == 0.0 && other_on_positive_side {
+            normal = -normal;
+        }
+        Some(CollisionDetails {
+            normal,
+            penetration: min_penetration,
+        })
     }
+}
 
+#[derive(Debug, Clone, Copy)]
+pub struct VertexBox {
+    vertices: [glam::Vec3A; 8],
+}
+
+impl From<&BoundingBox> for VertexBox {
+    fn from(boundingbox: &BoundingBox) -> Self {
+        Self {
+            vertices: boundingbox.get_vertices(),
+        }
+    }
+}
+
+impl VertexBox {
     pub fn get_vertices(&self) -> &[glam::Vec3A; 8] {
         &self.vertices
     }
@@ -0,0 +1,414 @@
+use crate::{ray_cast::RayCast, BoundingBox, Ray};
+use super::{Bounded, Broadphase, ProxyId};
+
+
+#[derive(Debug, Clone)]
+struct TreeNode<T> {
+    /// 잎 노드인 경우 margin만큼 확장된 AABB(fat AABB)
+    aabb: BoundingBox,
+    parent: Option<usize>,
+    children: Option<[usize; 2]>,
+    /// 잎 노드는 0, 사용하지 않는 노드는 -1
+    height: i32,
+    data: Option<T>,
+}
+
+/// 움직이는 물체를 위한 동적 AABB 트리 (Bounding Volume Hierarchy)  
+/// 잎 노드는 실제 AABB보다 margin만큼 큰 fat AABB를 가지므로,  
+/// 물체가 조금 움직이는 경우에는 트리를 갱신하지 않는다.  
+/// 삽입 위치는 겉넓이 기반 비용(SAH)으로 고르고, 회전으로 균형을 유지한다.  
+#[derive(Debug, Clone)]
+pub struct DynamicTree<T> {
+    nodes: Vec<TreeNode<T>>,
+    root: Option<usize>,
+    free_list: Vec<usize>,
+    margin: f32,
+}
+
+impl<T> Default for DynamicTree<T> {
+    fn default() -> Self {
+        Self::new(0.1)
+    }
+}
+
+impl<T> DynamicTree<T> {
+    pub fn new(margin: f32) -> Self {
+        Self {
+            nodes: Vec::new(),
+            root: None,
+            free_list: Vec::new(),
+            margin: margin.max(0.0),
+        }
+    }
+
+    pub fn margin(&self) -> f32 {
+        self.margin
+    }
+
+    /// 삽입된 객체의 개수
+    pub fn len(&self) -> usize {
+        self.nodes.iter().filter(|node| node.data.is_some()).count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.root.is_none()
+    }
+
+    /// 트리의 높이 (잎 노드만 있는 경우 0)
+    pub fn height(&self) -> i32 {
+        self.root.map_or(0, |root| self.nodes[root].height)
+    }
+
+    pub fn get(&self, id: ProxyId) -> Option<&T> {
+        self.nodes.get(id.0)?.data.as_ref()
+    }
+
+    pub fn get_mut(&mut self, id: ProxyId) -> Option<&mut T> {
+        self.nodes.get_mut(id.0)?.data.as_mut()
+    }
+
+    /// 객체를 감싸는 fat AABB
+    pub fn fat_aabb(&self, id: ProxyId) -> Option<BoundingBox> {
+        let node = self.nodes.get(id.0)?;
+        node.data.as_ref().map(|_| node.aabb)
+    }
+
+    /// 삽입된 모든 객체
+    pub fn iter(&self) -> impl Iterator<Item = (ProxyId, &T)> {
+        self.nodes.iter()
+            .enumerate()
+            .filter_map(|(i, node)| node.data.as_ref().map(|data| (ProxyId(i), data)))
+    }
+
+    pub fn insert(&mut self, bounds: &impl Bounded, data: T) -> ProxyId {
+        let aabb = bounds.aabb().expanded_by(self.margin);
+        let leaf = self.allocate_node(aabb, Some(data));
+        self.insert_leaf(leaf);
+        ProxyId(leaf)
+    }
+
+    pub fn remove(&mut self, id: ProxyId) -> Option<T> {
+        self.nodes.get(id.0)?.data.as_ref()?;
+        self.remove_leaf(id.0);
+        let data = self.nodes[id.0].data.take();
+        self.free_node(id.0);
+        data
+    }
+
+    /// 객체가 fat AABB를 벗어난 경우에만 트리를 갱신한다.  
+    /// 트리가 갱신되었으면 true를 반환한다.  
+    pub fn update(&mut self, id: ProxyId, bounds: &impl Bounded) -> bool {
+        let aabb = bounds.aabb();
+        match self.nodes.get(id.0) {
+            Some(node) if node.data.is_some() => {
+                if node.aabb.contains(&aabb) {
+                    return false;
+                }
+            }
+            _ => return false,
+        }
+
+        self.remove_leaf(id.0);
+        self.nodes[id.0].aabb = aabb.expanded_by(self.margin);
+        self.insert_leaf(id.0);
+        true
+    }
+
+    /// bounds와 fat AABB가 겹치는 모든 객체를 구한다.
+    pub fn query(&self, bounds: &impl Bounded) -> Vec<ProxyId> {
+        let aabb = bounds.aabb();
+        let mut result = Vec::new();
+        self.query_aabb(&aabb, |id| result.push(id));
+        result
+    }
+
+    fn query_aabb(&self, aabb: &BoundingBox, mut callback: impl FnMut(ProxyId)) {
+        let mut stack = Vec::with_capacity(64);
+        stack.extend(self.root);
+
+        while let Some(index) = stack.pop() {
+            let node = &self.nodes[index];
+            if !node.aabb.aabb_collision(aabb) {
+                continue;
+            }
+            match node.children {
+                Some(children) => stack.extend(children),
+                None => callback(ProxyId(index)),
+            }
+        }
+    }
+
+    /// ray와 만나는 가장 가까운 객체를 구한다.  
+    /// hit_test는 fat AABB와 만난 객체에 대해 호출되며, 현재까지의 최단 거리 이내에서  
+    /// 실제 도형과 만나는 거리를 반환해야 한다.  
+    pub fn ray_cast(
+        &self,
+        ray: &Ray,
+        max_distance: f32,
+        mut hit_test: impl FnMut(ProxyId, &T, f32) -> Option<f32>,
+    ) -> Option<(ProxyId, f32)> {
+        let mut closest = None;
+        let mut max_distance = max_distance;
+        let mut stack = Vec::with_capacity(64);
+        stack.extend(self.root);
+
+        while let Some(index) = stack.pop() {
+            let node = &self.nodes[index];
+            if node.aabb.cast_ray(ray, max_distance).is_none() {
+                continue;
+            }
+            match (node.children, node.data.as_ref()) {
+                (Some(children), _) => stack.extend(children),
+                (None, Some(data)) => {
+                    if let Some(distance) = hit_test(ProxyId(index), data, max_distance)
+                        && distance <= max_distance
+                    {
+                        // 찾은 거리보다 먼 노드는 더 이상 검사하지 않는다.
+                        max_distance = distance;
+                        closest = Some((ProxyId(index), distance));
+                    }
+                }
+                (None, None) => {}
+            }
+        }
+
+        closest
+    }
+
+    /// fat AABB가 서로 겹치는 모든 객체 쌍을 구한다. (중복 없음)
+    pub fn query_pairs(&self) -> Vec<(ProxyId, ProxyId)> {
+        let mut pairs = Vec::new();
+        for (i, node) in self.nodes.iter().enumerate() {
+            if node.data.is_none() {
+                continue;
+            }
+            self.query_aabb(&node.aabb, |other| {
+                if i < other.0 {
+                    pairs.push((ProxyId(i), other));
+                }
+            });
+        }
+        pairs
+    }
+
+    fn allocate_node(&mut self, aabb: BoundingBox, data: Option<T>) -> usize {
+        let height = if data.is_some() { 0 } else { 1 };
+        let node = TreeNode {
+            aabb,
+            parent: None,
+            children: None,
+            height,
+            data,
+        };
+        match self.free_list.pop() {
+            Some(index) => {
+                self.nodes[index] = node;
+                index
+            }
+            None => {
+                self.nodes.push(node);
+                self.nodes.len() - 1
+            }
+        }
+    }
+
+    fn free_node(&mut self, index: usize) {
+        let node = &mut self.nodes[index];
+        node.parent = None;
+        node.children = None;
+        node.height = -1;
+        node.data = None;
+        self.free_list.push(index);
+    }
+
+    fn insert_leaf(&mut self, leaf: usize) {
+        let Some(root) = self.root else {
+            self.root = Some(leaf);
+            self.nodes[leaf].parent = None;
+            return;
+        };
+
+        // 1. 비용이 가장 적은 형제 노드를 찾는다.
+        let leaf_aabb = self.nodes[leaf].aabb;
+        let mut index = root;
+        while let Some([child1, child2]) = self.nodes[index].children {
+            let area = self.nodes[index].aabb.surface_area();
+            let combined_area = self.nodes[index].aabb.union(&leaf_aabb).surface_area();
+
+            // 현재 노드와 새 잎 노드를 묶는 새 부모를 만드는 비용
+            let cost = 2.0 * combined_area;
+            // 더 아래로 내려갈 때 조상 노드들이 커지는 비용
+            let inheritance_cost = 2.0 * (combined_area - area);
+
+            let child_cost = |child: usize| {
+                let node = &self.nodes[child];
+                let combined = node.aabb.union(&leaf_aabb).surface_area();
+                if node.children.is_some() {
+                    combined - node.aabb.surface_area() + inheritance_cost
+                } else {
+                    combined + inheritance_cost
+                }
+            };
+            let cost1 = child_cost(child1);
+            let cost2 = child_cost(child2);
+
+            if cost < cost1 && cost < cost2 {
+                break;
+            }
+            index = if cost1 < cost2 { child1 } else { child2 };
+        }
+        let sibling = index;
+
+        // 2. 형제 노드와 새 잎 노드를 묶는 부모 노드를 만든다.
+        let old_parent = self.nodes[sibling].parent;
+        let new_parent = self.allocate_node(leaf_aabb.union(&self.nodes[sibling].aabb), None);
+        self.nodes[new_parent].parent = old_parent;
+        self.nodes[new_parent].children = Some([sibling, leaf]);
+        self.nodes[new_parent].height = self.nodes[sibling].height + 1;
+        self.nodes[sibling].parent = Some(new_parent);
+        self.nodes[leaf].parent = Some(new_parent);
+
+        match old_parent {
+            Some(parent) => self.replace_child(parent, sibling, new_parent),
+            None => self.root = Some(new_parent),
+        }
+
+        // 3. 위로 올라가며 AABB와 높이를 갱신하고 균형을 맞춘다.
+        self.fix_upwards(Some(new_parent));
+    }
+
+    fn remove_leaf(&mut self, leaf: usize) {
+        if self.root == Some(leaf) {
+            self.root = None;
+            return;
+        }
+
+        let parent = self.nodes[leaf].parent.expect("leaf must have a parent");
+        let grand_parent = self.nodes[parent].parent;
+        let [child1, child2] = self.nodes[parent].children.expect("parent must have children");
+        let sibling = if child1 == leaf { child2 } else { child1 };
+
+        // 부모 노드를 없애고 형제 노드를 그 자리에 올린다.
+        self.nodes[sibling].parent = grand_parent;
+        match grand_parent {
+            Some(grand_parent) => {
+                self.replace_child(grand_parent, parent, sibling);
+                self.free_node(parent);
+                self.fix_upwards(Some(grand_parent));
+            }
+            None => {
+                self.root = Some(sibling);
+                self.free_node(parent);
+            }
+        }
+        self.nodes[leaf].parent = None;
+    }
+
+    fn replace_child(&mut self, parent: usize, old_child: usize, new_child: usize) {
+        if let Some(children) = self.nodes[parent].children.as_mut() {
+            if children[0] == old_child {
+                children[0] = new_child;
+            } else {
+                children[1] = new_child;
+            }
+        }
+    }
+
+    fn fix_upwards(&mut self, mut index: Option<usize>) {
+        while let Some(i) = index {
+            let i = self.balance(i);
+            self.refit(i);
+            index = self.nodes[i].parent;
+        }
+    }
+
+    /// 자식 노드로부터 AABB와 높이를 다시 계산한다.
+    fn refit(&mut self, index: usize) {
+        if let Some([child1, child2]) = self.nodes[index].children {
+            self.nodes[index].height = 1 + self.nodes[child1].height.max(self.nodes[child2].height);
+            self.nodes[index].aabb = self.nodes[child1].aabb.union(&self.nodes[child2].aabb);
+        }
+    }
+
+    /// a의 자식 노드들의 높이 차가 1보다 크면 회전하여 균형을 맞춘다.  
+    /// 회전 후 a의 자리에 오게 된 노드를 반환한다.  
+    fn balance(&mut self, a: usize) -> usize {
+        let Some([b, c]) = self.nodes[a].children else {
+            return a;
+        };
+        if self.nodes[a].height < 2 {
+            return a;
+        }
+
+        let balance = self.nodes[c].height - self.nodes[b].height;
+        if balance > 1 {
+            self.rotate_up(a, c, b);
+            c
+        } else if balance < -1 {
+            self.rotate_up(a, b, c);
+            b
+        } else {
+            a
+        }
+    }
+
+    /// 높은 자식 노드(up)를 a의 자리로 올린다. (other는 a의 다른 자식)
+    fn rotate_up(&mut self, a: usize, up: usize, other: usize) {
+        let [f, g] = self.nodes[up].children.expect("rotated node must have children");
+
+        // up을 a의 자리로 올린다.
+        let a_parent = self.nodes[a].parent;
+        self.nodes[up].parent = a_parent;
+        self.nodes[a].parent = Some(up);
+        match a_parent {
+            Some(parent) => self.replace_child(parent, a, up),
+            None => self.root = Some(up),
+        }
+
+        // up의 자식 중 높은 쪽은 up에 남기고, 낮은 쪽은 a로 내린다.
+        let (keep, give) = if self.nodes[f].height > self.nodes[g].height { (f, g) } else { (g, f) };
+        self.nodes[up].children = Some([a, keep]);
+        self.nodes[a].children = Some([other, give]);
+        self.nodes[give].parent = Some(a);
+
+        self.refit(a);
+        self.refit(up);
+    }
+}
+
+
+impl<T: Copy> Broadphase<T> for DynamicTree<T> {
+    fn insert_proxy(&mut self, aabb: &BoundingBox, data: T) -> ProxyId {
+        self.insert(aabb, data)
+    }
+
+    fn remove_proxy(&mut self, id: ProxyId) {
+        self.remove(id);
+    }
+
+    fn update_proxy(&mut self, id: ProxyId, aabb: &BoundingBox) {
+        self.update(id, aabb);
+    }
+
+    fn candidate_pairs(&mut self) -> Vec<(T, T)> {
+        self.query_pairs()
+            .into_iter()
+            .map(|(a, b)| (self.nodes[a.0].data.unwrap(), self.nodes[b.0].data.unwrap()))
+            .collect()
+    }
+
+    fn query_candidates(&self, aabb: &BoundingBox) -> Vec<T> {
+        let mut result = Vec::new();
+        self.query_aabb(aabb, |id| result.extend(self.nodes[id.0].data));
+        result
+    }
+
+    fn ray_candidates(&self, ray: &Ray, max_distance: f32) -> Vec<T> {
+        let mut result = Vec::new();
+        self.ray_cast(ray, max_distance, |_, data, _| {
+            result.push(*data);
+            None
+        });
+        result
+    }
+}
@@ -0,0 +1,110 @@
+mod dynamic_tree;
+mod octree;
+mod spatial_hash;
+mod static_bvh;
+mod sweep_and_prune;
+
+pub use dynamic_tree::*;
+pub use octree::*;
+pub use spatial_hash::*;
+pub use static_bvh::*;
+pub use sweep_and_prune::*;
+
+use crate::{ray_cast::RayCast, BoundingBox, Capsule, Ray, RayHit, Sphere, Triangle};
+
+
+/// broadphase에 삽입된 객체의 식별자
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ProxyId(pub(crate) usize);
+
+/// broadphase에서 사용할 수 있도록 AABB로 감쌀 수 있는 도형
+pub trait Bounded {
+    /// 도형을 감싸는 월드 공간의 AABB
+    fn aabb(&self) -> BoundingBox;
+}
+
+impl Bounded for BoundingBox {
+    fn aabb(&self) -> BoundingBox {
+        BoundingBox::aabb(self)
+    }
+}
+
+impl Bounded for Sphere {
+    fn aabb(&self) -> BoundingBox {
+        self.bounding_box()
+    }
+}
+
+impl Bounded for Capsule {
+    fn aabb(&self) -> BoundingBox {
+        self.bounding_box()
+    }
+}
+
+impl Bounded for Triangle {
+    fn aabb(&self) -> BoundingBox {
+        self.bounding_box()
+    }
+}
+
+
+/// 바꿔 끼울 수 있는 broadphase의 공통 인터페이스  
+/// data는 객체를 식별하기 위한 작은 값(핸들 등)이며, 결과는 ProxyId 대신 data로 돌려준다.  
+pub trait Broadphase<T: Copy> {
+    fn insert_proxy(&mut self, aabb: &BoundingBox, data: T) -> ProxyId;
+    fn remove_proxy(&mut self, id: ProxyId);
+    fn update_proxy(&mut self, id: ProxyId, aabb: &BoundingBox);
+    /// AABB가 겹칠 수 있는 모든 쌍 (중복 없음)
+    fn candidate_pairs(&mut self) -> Vec<(T, T)>;
+    /// AABB가 aabb와 겹칠 수 있는 모든 객체
+    fn query_candidates(&self, aabb: &BoundingBox) -> Vec<T>;
+    /// AABB가 max_distance 이내에서 ray와 만날 수 있는 모든 객체
+    fn ray_candidates(&self, ray: &Ray, max_distance: f32) -> Vec<T>;
+}
+
+/// 도형 자체를 저장하는 broadphase(SpatialHashGrid, LooseOctree)에 넣기 위한 AABB와 data의 묶음
+#[derive(Debug, Clone, Copy)]
+pub struct BroadphaseProxy<T> {
+    pub aabb: BoundingBox,
+    pub data: T,
+}
+
+impl<T> Bounded for BroadphaseProxy<T> {
+    fn aabb(&self) -> BoundingBox {
+        self.aabb
+    }
+}
+
+impl<T> RayCast for BroadphaseProxy<T> {
+    fn cast_ray(&self, ray: &Ray, max_distance: f32) -> Option<RayHit> {
+        self.aabb.cast_ray(ray, max_distance)
+    }
+}
+
+
+/// ray가 min ~ max의 AABB 안에 있는 구간 (0 ~ max_distance로 제한)  
+/// inv_direction은 ray.direction().recip()이다. 방향 성분이 0이면 역수가 무한대가 되어  
+/// 경계 위에서 0 * inf = NaN이 나오므로, 그 축은 slab 안에서 출발했는지만 확인한다.  
+pub(crate) fn ray_aabb_interval(
+    ray: &Ray,
+    inv_direction: &glam::Vec3A,
+    min: &glam::Vec3A,
+    max: &glam::Vec3A,
+    max_distance: f32,
+) -> Option<(f32, f32)> {
+    let mut entry = 0.0_f32;
+    let mut exit = max_distance;
+    for i in 0..3 {
+        if !inv_direction[i].is_finite() {
+            if ray.origin[i] < min[i] || ray.origin[i] > max[i] {
+                return None;
+            }
+            continue;
+        }
+        let t1 = (min[i] - ray.origin[i]) * inv_direction[i];
+        let t2 = (max[i] - ray.origin[i]) * inv_direction[i];
+        entry = entry.max(t1.min(t2));
+        exit = exit.min(t1.max(t2));
+    }
+    (entry <= exit).then_some((entry, exit))
+}
@@ -0,0 +1,457 @@
+use crate::{
+    ray_cast::RayCast,
+    static_collision::StaticCollision,
+    BoundingBox, ConvexHull, Frustum, Ray, RayHit, Sphere,
+};
+use super::{Bounded, Broadphase, BroadphaseProxy, ProxyId};
+
+
+/// LooseOctree의 설정
+#[derive(Debug, Clone, Copy)]
+pub struct OctreeSettings {
+    /// 루트 노드의 깊이는 0
+    pub max_depth: usize,
+    /// 노드의 느슨한 경계 = 노드 크기 x looseness (1 이상, 보통 2)
+    pub looseness: f32,
+    /// 노드에 담긴 객체가 이 수를 넘으면 자식 노드로 나눈다.
+    pub node_capacity: usize,
+}
+
+impl Default for OctreeSettings {
+    fn default() -> Self {
+        Self {
+            max_depth: 8,
+            looseness: 2.0,
+            node_capacity: 8,
+        }
+    }
+}
+
+/// 디버그용 통계
+#[derive(Debug, Clone, Default)]
+pub struct OctreeStats {
+    pub node_count: usize,
+    pub leaf_count: usize,
+    /// 깊이별 노드 수
+    pub depth_histogram: Vec<usize>,
+    /// 깊이별 객체 수
+    pub objects_per_depth: Vec<usize>,
+    /// 한 노드에 담긴 가장 많은 객체 수
+    pub max_objects_per_node: usize,
+    /// 객체가 있는 노드의 평균 객체 수
+    pub average_objects_per_node: f32,
+}
+
+#[derive(Debug, Clone)]
+struct OctreeNode {
+    center: glam::Vec3,
+    /// 느슨하지 않은 원래 크기의 절반
+    half_size: f32,
+    depth: usize,
+    parent: Option<usize>,
+    /// 8개의 자식 노드는 연속된 인덱스에 있으며, 그 첫 번째 인덱스
+    children: Option<usize>,
+    objects: Vec<usize>,
+}
+
+#[derive(Debug, Clone)]
+struct OctreeObject<S> {
+    shape: S,
+    aabb: BoundingBox,
+    node: usize,
+}
+
+/// 월드 경계가 정해진 느슨한 Octree  
+/// 노드의 경계를 looseness배로 넓혀, 객체는 중심이 속한 노드 하나에만 들어간다.  
+/// 넓고 드문드문한 맵에서 움직이는 물체와 고정된 물체를 함께 다루기 위함이다.  
+/// 월드 경계를 벗어난 객체는 루트 노드에 들어간다.  
+#[derive(Debug, Clone)]
+pub struct LooseOctree<S> {
+    settings: OctreeSettings,
+    nodes: Vec<OctreeNode>,
+    /// 재사용할 수 있는 8개 단위의 자식 노드 묶음
+    free_children: Vec<usize>,
+    objects: Vec<Option<OctreeObject<S>>>,
+    free_list: Vec<usize>,
+}
+
+impl<S: Bounded> LooseOctree<S> {
+    /// world를 감싸는 정육면체를 루트 노드로 한다.
+    pub fn new(world: &BoundingBox, settings: OctreeSettings) -> Self {
+        let settings = OctreeSettings {
+            looseness: settings.looseness.max(1.0),
+            node_capacity: settings.node_capacity.max(1),
+            ..settings
+        };
+        let world = world.aabb();
+        let root = OctreeNode {
+            center: world.center,
+            half_size: world.extents().max_element().max(f32::EPSILON),
+            depth: 0,
+            parent: None,
+            children: None,
+            objects: Vec::new(),
+        };
+        Self {
+            settings,
+            nodes: vec![root],
+            free_children: Vec::new(),
+            objects: Vec::new(),
+            free_list: Vec::new(),
+        }
+    }
+
+    pub fn settings(&self) -> OctreeSettings {
+        self.settings
+    }
+
+    /// 삽입된 객체의 개수
+    pub fn len(&self) -> usize {
+        self.objects.len() - self.free_list.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn get(&self, id: ProxyId) -> Option<&S> {
+        self.objects.get(id.0)?.as_ref().map(|object| &object.shape)
+    }
+
+    /// 삽입된 모든 객체
+    pub fn iter(&self) -> impl Iterator<Item = (ProxyId, &S)> {
+        self.objects.iter()
+            .enumerate()
+            .filter_map(|(i, object)| object.as_ref().map(|object| (ProxyId(i), &object.shape)))
+    }
+
+    pub fn insert(&mut self, shape: S) -> ProxyId {
+        let aabb = shape.aabb();
+        let index = match self.free_list.pop() {
+            Some(index) => index,
+            None => {
+                self.objects.push(None);
+                self.objects.len() - 1
+            }
+        };
+        self.objects[index] = Some(OctreeObject {
+            shape,
+            aabb,
+            node: 0,
+        });
+        self.insert_object(index, 0);
+        ProxyId(index)
+    }
+
+    pub fn remove(&mut self, id: ProxyId) -> Option<S> {
+        let object = self.objects.get_mut(id.0)?.take()?;
+        self.detach(id.0, object.node);
+        self.free_list.push(id.0);
+        Some(object.shape)
+    }
+
+    /// 객체의 모양을 바꾼다.  
+    /// 여전히 지금 노드의 느슨한 경계 안에 있고 더 깊이 내려갈 수 없으면 노드를 옮기지 않는다.  
+    pub fn update(&mut self, id: ProxyId, shape: S) {
+        let aabb = shape.aabb();
+        let Some(Some(object)) = self.objects.get_mut(id.0) else {
+            return;
+        };
+        object.shape = shape;
+        object.aabb = aabb;
+        let node = object.node;
+
+        let stays = self.loose_bounds(node).contains(&aabb)
+            && self.nodes[node].children.is_none_or(|_| self.child_for(node, &aabb).is_none());
+        if !stays {
+            self.detach(id.0, node);
+            self.insert_object(id.0, 0);
+        }
+    }
+
+    /// 노드의 느슨한 경계
+    fn loose_bounds(&self, node: usize) -> BoundingBox {
+        let node = &self.nodes[node];
+        BoundingBox::new(node.center, glam::Vec3::splat(node.half_size * self.settings.looseness))
+    }
+
+    /// aabb를 완전히 담을 수 있는 자식 노드 (중심이 속한 자식)
+    fn child_for(&self, node: usize, aabb: &BoundingBox) -> Option<usize> {
+        let first = self.nodes[node].children?;
+        let child = first + octant(self.nodes[node].center, aabb.center);
+        self.loose_bounds(child).contains(aabb).then_some(child)
+    }
+
+    fn insert_object(&mut self, index: usize, start: usize) {
+        let aabb = self.objects[index].as_ref().expect("inserting a removed object").aabb;
+        let mut node = start;
+        loop {
+            if self.nodes[node].children.is_none()
+                && self.nodes[node].objects.len() >= self.settings.node_capacity
+                && self.nodes[node].depth < self.settings.max_depth
+            {
+                self.split(node);
+            }
+            match self.child_for(node, &aabb) {
+                Some(child) => node = child,
+                None => break,
+            }
+        }
+
+        self.nodes[node].objects.push(index);
+        if let Some(object) = self.objects[index].as_mut() {
+            object.node = node;
+        }
+    }
+
+    /// 노드를 8개의 자식으로 나누고, 들어갈 수 있는 객체를 자식으로 내린다.
+    fn split(&mut self, node: usize) {
+        let (center, half_size, depth) = {
+            let node = &self.nodes[node];
+            (node.center, node.half_size * 0.5, node.depth + 1)
+        };
+        let children = (0..8).map(|i| OctreeNode {
+            center: center + octant_offset(i) * half_size,
+            half_size,
+            depth,
+            parent: Some(node),
+            children: None,
+            objects: Vec::new(),
+        });
+        let first = match self.free_children.pop() {
+            Some(first) => {
+                for (i, child) in children.enumerate() {
+                    self.nodes[first + i] = child;
+                }
+                first
+            }
+            None => {
+                let first = self.nodes.len();
+                self.nodes.extend(children);
+                first
+            }
+        };
+        self.nodes[node].children = Some(first);
+
+        let objects = std::mem::take(&mut self.nodes[node].objects);
+        for index in objects {
+            let aabb = self.objects[index].as_ref().expect("node refers to a removed object").aabb;
+            let target = self.child_for(node, &aabb).unwrap_or(node);
+            self.nodes[target].objects.push(index);
+            if let Some(object) = self.objects[index].as_mut() {
+                object.node = target;
+            }
+        }
+    }
+
+    /// 노드에서 객체를 빼고, 비어 있는 자식 노드들은 합친다.
+    fn detach(&mut self, index: usize, node: usize) {
+        let objects = &mut self.nodes[node].objects;
+        if let Some(pos) = objects.iter().position(|&i| i == index) {
+            objects.swap_remove(pos);
+        }
+
+        let mut current = Some(node);
+        while let Some(node) = current {
+            if !self.nodes[node].objects.is_empty() {
+                break;
+            }
+            if let Some(first) = self.nodes[node].children {
+                let empty = (first..first + 8).all(|child| {
+                    self.nodes[child].children.is_none() && self.nodes[child].objects.is_empty()
+                });
+                if !empty {
+                    break;
+                }
+                self.nodes[node].children = None;
+                self.free_children.push(first);
+            }
+            current = self.nodes[node].parent;
+        }
+    }
+
+    /// 느슨한 경계가 node_test를 통과하는 노드(와 루트 노드)만 방문하며, object_test를 통과한 객체를 모은다.
+    fn collect(
+        &self,
+        node_test: impl Fn(&BoundingBox) -> bool,
+        mut object_test: impl FnMut(&OctreeObject<S>) -> bool,
+    ) -> Vec<ProxyId> {
+        let mut result = Vec::new();
+        let mut stack = vec![0];
+        while let Some(node) = stack.pop() {
+            // 월드 경계 밖의 객체는 루트 노드에 들어가므로 루트 노드는 항상 확인한다.
+            if node != 0 && !node_test(&self.loose_bounds(node)) {
+                continue;
+            }
+            for &index in self.nodes[node].objects.iter() {
+                let object = self.objects[index].as_ref().expect("node refers to a removed object");
+                if object_test(object) {
+                    result.push(ProxyId(index));
+                }
+            }
+            if let Some(first) = self.nodes[node].children {
+                stack.extend(first..first + 8);
+            }
+        }
+        result
+    }
+
+    /// AABB가 bounds와 겹치는 모든 객체
+    pub fn query_aabb(&self, bounds: &BoundingBox) -> Vec<ProxyId> {
+        let aabb = bounds.aabb();
+        self.collect(
+            |node| node.aabb_collision(&aabb),
+            |object| object.aabb.aabb_collision(&aabb),
+        )
+    }
+
+    /// sphere와 실제로 충돌하는 모든 객체
+    pub fn query_sphere(&self, sphere: &Sphere) -> Vec<ProxyId>
+    where
+        S: ConvexHull,
+        Sphere: StaticCollision<S>,
+    {
+        let aabb = sphere.bounding_box();
+        self.collect(
+            |node| node.check_static_collision(sphere),
+            |object| object.aabb.aabb_collision(&aabb) && sphere.check_static_collision(&object.shape),
+        )
+    }
+
+    /// shape과 실제로 충돌하는 모든 객체  
+    /// narrowphase는 StaticCollision 구현을 사용한다.  
+    pub fn query<Q>(&self, shape: &Q) -> Vec<ProxyId>
+    where
+        S: ConvexHull,
+        Q: Bounded + StaticCollision<S>,
+    {
+        let aabb = shape.aabb();
+        self.collect(
+            |node| node.aabb_collision(&aabb),
+            |object| object.aabb.aabb_collision(&aabb) && shape.check_static_collision(&object.shape),
+        )
+    }
+
+    /// AABB가 절두체와 겹치는 모든 객체
+    pub fn query_frustum(&self, frustum: &Frustum) -> Vec<ProxyId> {
+        self.collect(
+            |node| frustum.intersects_box(node),
+            |object| frustum.intersects_box(&object.aabb),
+        )
+    }
+
+    /// ray와 만나는 가장 가까운 객체
+    pub fn ray_cast(&self, ray: &Ray, max_distance: f32) -> Option<(ProxyId, RayHit)>
+    where
+        S: RayCast,
+    {
+        let mut closest: Option<(ProxyId, RayHit)> = None;
+        let mut max_distance = max_distance;
+        let mut stack = vec![0];
+        while let Some(node) = stack.pop() {
+            if node != 0 && self.loose_bounds(node).cast_ray(ray, max_distance).is_none() {
+                continue;
+            }
+            for &index in self.nodes[node].objects.iter() {
+                let object = self.objects[index].as_ref().expect("node refers to a removed object");
+                if let Some(hit) = object.shape.cast_ray(ray, max_distance) {
+                    max_distance = hit.distance;
+                    closest = Some((ProxyId(index), hit));
+                }
+            }
+            if let Some(first) = self.nodes[node].children {
+                stack.extend(first..first + 8);
+            }
+        }
+        closest
+    }
+
+    pub fn stats(&self) -> OctreeStats {
+        let mut stats = OctreeStats::default();
+        let mut occupied_nodes = 0;
+        let mut stack = vec![0];
+        while let Some(node) = stack.pop() {
+            let node = &self.nodes[node];
+            if stats.depth_histogram.len() <= node.depth {
+                stats.depth_histogram.resize(node.depth + 1, 0);
+                stats.objects_per_depth.resize(node.depth + 1, 0);
+            }
+            stats.node_count += 1;
+            stats.depth_histogram[node.depth] += 1;
+            stats.objects_per_depth[node.depth] += node.objects.len();
+            stats.max_objects_per_node = stats.max_objects_per_node.max(node.objects.len());
+            if !node.objects.is_empty() {
+                occupied_nodes += 1;
+            }
+            match node.children {
+                Some(first) => stack.extend(first..first + 8),
+                None => stats.leaf_count += 1,
+            }
+        }
+        if occupied_nodes > 0 {
+            stats.average_objects_per_node = self.len() as f32 / occupied_nodes as f32;
+        }
+        stats
+    }
+}
+
+
+impl<T: Copy> Broadphase<T> for LooseOctree<BroadphaseProxy<T>> {
+    fn insert_proxy(&mut self, aabb: &BoundingBox, data: T) -> ProxyId {
+        self.insert(BroadphaseProxy { aabb: aabb.aabb(), data })
+    }
+
+    fn remove_proxy(&mut self, id: ProxyId) {
+        self.remove(id);
+    }
+
+    fn update_proxy(&mut self, id: ProxyId, aabb: &BoundingBox) {
+        if let Some(proxy) = self.get(id) {
+            let data = proxy.data;
+            self.update(id, BroadphaseProxy { aabb: aabb.aabb(), data });
+        }
+    }
+
+    /// 객체마다 자신의 AABB로 질의하여 쌍을 모은다.
+    fn candidate_pairs(&mut self) -> Vec<(T, T)> {
+        let mut pairs = Vec::new();
+        for (id, proxy) in self.iter() {
+            for other in self.query_aabb(&proxy.aabb) {
+                if id < other {
+                    pairs.push((proxy.data, self.get(other).unwrap().data));
+                }
+            }
+        }
+        pairs
+    }
+
+    fn query_candidates(&self, aabb: &BoundingBox) -> Vec<T> {
+        self.query_aabb(aabb)
+            .into_iter()
+            .map(|id| self.get(id).unwrap().data)
+            .collect()
+    }
+
+    fn ray_candidates(&self, ray: &Ray, max_distance: f32) -> Vec<T> {
+        self.collect(
+            |node| node.cast_ray(ray, max_distance).is_some(),
+            |object| object.aabb.cast_ray(ray, max_distance).is_some(),
+        )
+        .into_iter()
+        .map(|id| self.get(id).unwrap().data)
+        .collect()
+    }
+}
+
+
+/// center를 기준으로 point가 속한 팔분면의 번호 (x, y, z 순서의 비트)
+fn octant(center: glam::Vec3, point: glam::Vec3) -> usize {
+    (point.x >= center.x) as usize
+        | ((point.y >= center.y) as usize) << 1
+        | ((point.z >= center.z) as usize) << 2
+}
+
+fn octant_offset(octant: usize) -> glam::Vec3 {
+    let sign = |bit: usize| if octant & bit != 0 { 1.0 } else { -1.0 };
+    glam::Vec3::new(sign(1), sign(2), sign(4))
+}
@@ -0,0 +1,395 @@
+use std::collections::{HashMap, HashSet};
+use crate::{
+    ray_cast::RayCast,
+    static_collision::StaticCollision,
+    BoundingBox, ConvexHull, Ray, RayHit,
+};
+use super::{ray_aabb_interval, Bounded, Broadphase, BroadphaseProxy, ProxyId};
+
+
+#[derive(Debug, Clone)]
+struct GridObject<S> {
+    shape: S,
+    aabb: BoundingBox,
+    min_cell: glam::IVec3,
+    max_cell: glam::IVec3,
+}
+
+/// 균일한 크기의 셀로 공간을 나누는 해시 그리드 broadphase  
+/// 크기가 비슷한 물체가 많은 경우(파티클 등)에 적합하다.  
+/// 셀보다 큰 물체는 겹치는 모든 셀에 등록된다.  
+#[derive(Debug, Clone)]
+pub struct SpatialHashGrid<S> {
+    cell_size: f32,
+    cells: HashMap<glam::IVec3, Vec<usize>>,
+    objects: Vec<Option<GridObject<S>>>,
+    free_list: Vec<usize>,
+    /// 한 번이라도 물체가 등록된 셀의 범위 (ray 검사의 범위를 제한하기 위함)
+    occupied: Option<(glam::IVec3, glam::IVec3)>,
+}
+
+impl<S: Bounded> SpatialHashGrid<S> {
+    pub fn new(cell_size: f32) -> Self {
+        Self {
+            cell_size: cell_size.abs().max(f32::EPSILON),
+            cells: HashMap::new(),
+            objects: Vec::new(),
+            free_list: Vec::new(),
+            occupied: None,
+        }
+    }
+
+    pub fn cell_size(&self) -> f32 {
+        self.cell_size
+    }
+
+    /// 삽입된 객체의 개수
+    pub fn len(&self) -> usize {
+        self.objects.len() - self.free_list.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// 물체가 하나 이상 있는 셀의 개수
+    pub fn cell_count(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn get(&self, id: ProxyId) -> Option<&S> {
+        self.objects.get(id.0)?.as_ref().map(|object| &object.shape)
+    }
+
+    /// 삽입된 모든 객체
+    pub fn iter(&self) -> impl Iterator<Item = (ProxyId, &S)> {
+        self.objects.iter()
+            .enumerate()
+            .filter_map(|(i, object)| object.as_ref().map(|object| (ProxyId(i), &object.shape)))
+    }
+
+    /// 점이 속한 셀의 좌표
+    pub fn cell_of(&self, point: glam::Vec3) -> glam::IVec3 {
+        (point / self.cell_size).floor().as_ivec3()
+    }
+
+    pub fn insert(&mut self, shape: S) -> ProxyId {
+        let aabb = shape.aabb();
+        let (min_cell, max_cell) = (self.cell_of(aabb.min()), self.cell_of(aabb.max()));
+        let index = match self.free_list.pop() {
+            Some(index) => index,
+            None => {
+                self.objects.push(None);
+                self.objects.len() - 1
+            }
+        };
+
+        self.add_to_cells(index, min_cell, max_cell);
+        self.objects[index] = Some(GridObject {
+            shape,
+            aabb,
+            min_cell,
+            max_cell,
+        });
+        ProxyId(index)
+    }
+
+    pub fn remove(&mut self, id: ProxyId) -> Option<S> {
+        let object = self.objects.get_mut(id.0)?.take()?;
+        self.remove_from_cells(id.0, object.min_cell, object.max_cell);
+        self.free_list.push(id.0);
+        Some(object.shape)
+    }
+
+    /// 물체를 옮긴다. 걸쳐 있는 셀의 범위가 바뀐 경우에만 셀을 갱신한다.
+    pub fn update(&mut self, id: ProxyId, shape: S) {
+        let aabb = shape.aabb();
+        let (min_cell, max_cell) = (self.cell_of(aabb.min()), self.cell_of(aabb.max()));
+        let Some(Some(object)) = self.objects.get(id.0) else {
+            return;
+        };
+        let (old_min, old_max) = (object.min_cell, object.max_cell);
+
+        if (old_min, old_max) != (min_cell, max_cell) {
+            self.remove_from_cells(id.0, old_min, old_max);
+            self.add_to_cells(id.0, min_cell, max_cell);
+        }
+        self.objects[id.0] = Some(GridObject {
+            shape,
+            aabb,
+            min_cell,
+            max_cell,
+        });
+    }
+
+    /// AABB가 bounds와 겹치는 모든 객체를 구한다. (narrowphase 없음)  
+    /// 물체가 등록된 적 있는 셀의 범위로 잘라서 훑으므로, bounds가 아주 커도 셀 수만큼만 검사한다.  
+    pub fn query_aabb(&self, bounds: &BoundingBox) -> Vec<ProxyId> {
+        let mut result = Vec::new();
+        let Some((occupied_min, occupied_max)) = self.occupied else {
+            return result;
+        };
+        if self.cells.is_empty() {
+            return result;
+        }
+
+        let aabb = bounds.aabb();
+        let min_cell = self.cell_of(aabb.min()).max(occupied_min);
+        let max_cell = self.cell_of(aabb.max()).min(occupied_max);
+        if min_cell.cmpgt(max_cell).any() {
+            return result;
+        }
+
+        for cell in CellRange::new(min_cell, max_cell) {
+            let Some(indices) = self.cells.get(&cell) else {
+                continue;
+            };
+            for &index in indices {
+                let object = self.objects[index].as_ref().expect("cell refers to a removed object");
+                // 여러 셀에서 만나는 경우, 겹치는 범위의 첫 셀에서만 보고한다.
+                if cell != object.min_cell.max(min_cell) {
+                    continue;
+                }
+                if object.aabb.aabb_collision(&aabb) {
+                    result.push(ProxyId(index));
+                }
+            }
+        }
+        result
+    }
+
+    /// shape과 실제로 충돌하는 모든 객체를 구한다.  
+    /// narrowphase는 StaticCollision 구현을 사용한다.  
+    pub fn query<Q>(&self, shape: &Q) -> Vec<ProxyId>
+    where
+        S: ConvexHull,
+        Q: Bounded + StaticCollision<S>,
+    {
+        let mut result = self.query_aabb(&shape.aabb());
+        result.retain(|id| shape.check_static_collision(self.get(*id).expect("query returned a removed object")));
+        result
+    }
+
+    /// 같은 셀에 있고 AABB가 겹치는 모든 객체 쌍을 구한다. (중복 없음, 작은 id가 앞)
+    pub fn query_pairs(&self) -> Vec<(ProxyId, ProxyId)> {
+        let mut pairs = Vec::new();
+        for (cell, indices) in self.cells.iter() {
+            for (n, &a) in indices.iter().enumerate() {
+                let object_a = self.objects[a].as_ref().expect("cell refers to a removed object");
+                for &b in indices[n + 1..].iter() {
+                    let object_b = self.objects[b].as_ref().expect("cell refers to a removed object");
+                    // 두 물체가 함께 걸친 범위의 첫 셀에서만 보고한다.
+                    if *cell != object_a.min_cell.max(object_b.min_cell) {
+                        continue;
+                    }
+                    if object_a.aabb.aabb_collision(&object_b.aabb) {
+                        pairs.push((ProxyId(a.min(b)), ProxyId(a.max(b))));
+                    }
+                }
+            }
+        }
+        pairs
+    }
+
+    /// 실제로 충돌하는 모든 객체 쌍을 구한다.  
+    /// narrowphase는 StaticCollision 구현을 사용한다.  
+    pub fn colliding_pairs(&self) -> Vec<(ProxyId, ProxyId)>
+    where
+        S: ConvexHull + StaticCollision<S>,
+    {
+        let mut pairs = self.query_pairs();
+        pairs.retain(|(a, b)| {
+            let a = self.get(*a).expect("query returned a removed object");
+            let b = self.get(*b).expect("query returned a removed object");
+            a.check_static_collision(b)
+        });
+        pairs
+    }
+
+    /// ray가 지나는 셀을 3D DDA로 따라가며 가장 가까운 객체를 찾는다.
+    pub fn ray_cast(&self, ray: &Ray, max_distance: f32) -> Option<(ProxyId, RayHit)>
+    where
+        S: RayCast,
+    {
+        let (occupied_min, occupied_max) = self.occupied?;
+
+        // 1. ray를 물체가 있는 영역으로 자른다. (slab)
+        let region_min = glam::Vec3A::from(occupied_min.as_vec3() * self.cell_size);
+        let region_max = glam::Vec3A::from((occupied_max + glam::IVec3::ONE).as_vec3() * self.cell_size);
+        let inv_direction = ray.direction().recip();
+        let (entry, exit) = ray_aabb_interval(ray, &inv_direction, &region_min, &region_max, max_distance)?;
+
+        // 2. 시작 셀과 각 축의 다음 경계까지의 거리를 구한다.
+        let start = ray.point_at(entry);
+        let direction = ray.direction();
+        let mut cell = self.cell_of(glam::Vec3::from(start)).clamp(occupied_min, occupied_max);
+        let mut step = glam::IVec3::ZERO;
+        let mut t_max = glam::Vec3A::splat(f32::INFINITY);
+        let mut t_delta = glam::Vec3A::splat(f32::INFINITY);
+        for i in 0..3 {
+            if direction[i] > 0.0 {
+                step[i] = 1;
+                t_max[i] = entry + ((cell[i] + 1) as f32 * self.cell_size - start[i]) / direction[i];
+                t_delta[i] = self.cell_size / direction[i];
+            } else if direction[i] < 0.0 {
+                step[i] = -1;
+                t_max[i] = entry + (cell[i] as f32 * self.cell_size - start[i]) / direction[i];
+                t_delta[i] = -self.cell_size / direction[i];
+            }
+        }
+
+        // 3. 셀을 따라가며 검사한다.
+        let mut tested = HashSet::new();
+        let mut closest: Option<(ProxyId, RayHit)> = None;
+        loop {
+            if let Some(indices) = self.cells.get(&cell) {
+                for &index in indices {
+                    if !tested.insert(index) {
+                        continue;
+                    }
+                    let object = self.objects[index].as_ref().expect("cell refers to a removed object");
+                    let limit = closest.map_or(max_distance, |(_, hit)| hit.distance);
+                    if let Some(hit) = object.shape.cast_ray(ray, limit) {
+                        closest = Some((ProxyId(index), hit));
+                    }
+                }
+            }
+
+            // 현재 셀 안에서 만났다면 더 먼 셀은 볼 필요가 없다.
+            let cell_exit = t_max.min_element();
+            if closest.is_some_and(|(_, hit)| hit.distance <= cell_exit) || cell_exit > exit {
+                break;
+            }
+
+            let axis = if t_max.x <= t_max.y && t_max.x <= t_max.z {
+                0
+            } else if t_max.y <= t_max.z {
+                1
+            } else {
+                2
+            };
+            cell[axis] += step[axis];
+            t_max[axis] += t_delta[axis];
+        }
+
+        closest
+    }
+
+    fn add_to_cells(&mut self, index: usize, min_cell: glam::IVec3, max_cell: glam::IVec3) {
+        for cell in CellRange::new(min_cell, max_cell) {
+            self.cells.entry(cell).or_default().push(index);
+        }
+        self.occupied = Some(match self.occupied {
+            Some((min, max)) => (min.min(min_cell), max.max(max_cell)),
+            None => (min_cell, max_cell),
+        });
+    }
+
+    fn remove_from_cells(&mut self, index: usize, min_cell: glam::IVec3, max_cell: glam::IVec3) {
+        for cell in CellRange::new(min_cell, max_cell) {
+            if let Some(indices) = self.cells.get_mut(&cell) {
+                if let Some(pos) = indices.iter().position(|&i| i == index) {
+                    indices.swap_remove(pos);
+                }
+                if indices.is_empty() {
+                    self.cells.remove(&cell);
+                }
+            }
+        }
+    }
+}
+
+
+impl<T: Copy> Broadphase<T> for SpatialHashGrid<BroadphaseProxy<T>> {
+    fn insert_proxy(&mut self, aabb: &BoundingBox, data: T) -> ProxyId {
+        self.insert(BroadphaseProxy { aabb: aabb.aabb(), data })
+    }
+
+    fn remove_proxy(&mut self, id: ProxyId) {
+        self.remove(id);
+    }
+
+    fn update_proxy(&mut self, id: ProxyId, aabb: &BoundingBox) {
+        if let Some(proxy) = self.get(id) {
+            let data = proxy.data;
+            self.update(id, BroadphaseProxy { aabb: aabb.aabb(), data });
+        }
+    }
+
+    fn candidate_pairs(&mut self) -> Vec<(T, T)> {
+        self.query_pairs()
+            .into_iter()
+            .map(|(a, b)| (self.get(a).unwrap().data, self.get(b).unwrap().data))
+            .collect()
+    }
+
+    fn query_candidates(&self, aabb: &BoundingBox) -> Vec<T> {
+        self.query_aabb(aabb)
+            .into_iter()
+            .map(|id| self.get(id).unwrap().data)
+            .collect()
+    }
+
+    fn ray_candidates(&self, ray: &Ray, max_distance: f32) -> Vec<T> {
+        let Some((occupied_min, occupied_max)) = self.occupied else {
+            return Vec::new();
+        };
+
+        // 물체가 있는 영역으로 자른 선분의 AABB 안에서 ray와 만나는 객체만 고른다.
+        let region_min = glam::Vec3A::from(occupied_min.as_vec3() * self.cell_size);
+        let region_max = glam::Vec3A::from((occupied_max + glam::IVec3::ONE).as_vec3() * self.cell_size);
+        let inv_direction = ray.direction().recip();
+        let Some((entry, exit)) = ray_aabb_interval(ray, &inv_direction, &region_min, &region_max, max_distance) else {
+            return Vec::new();
+        };
+        let (start, end) = (ray.point_at(entry), ray.point_at(exit));
+        let segment = BoundingBox::from_min_max(start.min(end).into(), start.max(end).into());
+
+        self.query_aabb(&segment)
+            .into_iter()
+            .map(|id| self.get(id).unwrap())
+            .filter(|proxy| proxy.aabb.cast_ray(ray, max_distance).is_some())
+            .map(|proxy| proxy.data)
+            .collect()
+    }
+}
+
+
+/// min부터 max까지(포함)의 모든 셀 좌표
+struct CellRange {
+    min: glam::IVec3,
+    max: glam::IVec3,
+    current: glam::IVec3,
+}
+
+impl CellRange {
+    fn new(min: glam::IVec3, max: glam::IVec3) -> Self {
+        Self {
+            min,
+            max,
+            current: min,
+        }
+    }
+}
+
+impl Iterator for CellRange {
+    type Item = glam::IVec3;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.current.z > self.max.z || self.min.cmpgt(self.max).any() {
+            return None;
+        }
+        let cell = self.current;
+
+        self.current.x += 1;
+        if self.current.x > self.max.x {
+            self.current.x = self.min.x;
+            self.current.y += 1;
+            if self.current.y > self.max.y {
+                self.current.y = self.min.y;
+                self.current.z += 1;
+            }
+        }
+        Some(cell)
+    }
+}
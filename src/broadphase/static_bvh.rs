@@ -0,0 +1,387 @@
+use std::collections::BinaryHeap;
+use crate::{
+    point_query::PointQuery,
+    ray_cast::RayCast,
+    static_collision::StaticCollision,
+    BoundingBox, ConvexHull, Ray, RayHit,
+};
+use super::{ray_aabb_interval, Bounded};
+
+
+/// SAH 계산에 사용할 구간의 개수
+const BIN_COUNT: usize = 12;
+/// 객체가 이 개수 이하이면 나누지 않고 잎 노드로 둔다.
+const LEAF_SIZE: usize = 4;
+/// 잎 노드에 넣을 수 있는 최대 객체 수  
+/// LEAF_SIZE보다 많고 이 개수 이하이면, SAH 비용이 나누는 것보다 낮은 경우에만 잎 노드로 둔다.  
+const MAX_LEAF_SIZE: usize = 16;
+
+#[derive(Debug, Clone, Copy)]
+struct BvhNode {
+    min: glam::Vec3A,
+    max: glam::Vec3A,
+    /// 잎 노드면 order에서의 시작 위치, 내부 노드면 오른쪽 자식의 인덱스 (왼쪽 자식은 바로 다음 노드)
+    offset: usize,
+    /// 잎 노드의 객체 수 (내부 노드는 0)
+    count: usize,
+}
+
+impl BvhNode {
+    fn is_leaf(&self) -> bool {
+        self.count > 0
+    }
+
+    fn aabb(&self) -> BoundingBox {
+        BoundingBox::from_min_max(self.min.into(), self.max.into())
+    }
+}
+
+/// 움직이지 않는 물체를 위한 BVH  
+/// 한 번만 만들고(binned SAH), 노드는 깊이 우선 순서의 평평한 배열에 저장한다.  
+/// 객체의 인덱스는 생성할 때 넘긴 순서와 같다.  
+/// 삼각형 메시는 Triangle을 객체로 넣어 만든다.  
+#[derive(Debug, Clone)]
+pub struct StaticBvh<T> {
+    nodes: Vec<BvhNode>,
+    items: Vec<T>,
+    /// 잎 노드가 가리키는 객체의 인덱스
+    order: Vec<usize>,
+}
+
+impl<T: Bounded> FromIterator<T> for StaticBvh<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self::new(iter.into_iter().collect())
+    }
+}
+
+impl<T: Bounded> StaticBvh<T> {
+    pub fn new(items: Vec<T>) -> Self {
+        let bounds = items.iter()
+            .map(|item| {
+                let aabb = item.aabb();
+                (glam::Vec3A::from(aabb.min()), glam::Vec3A::from(aabb.max()))
+            })
+            .collect::<Vec<_>>();
+        let mut order = (0..items.len()).collect::<Vec<_>>();
+        let mut nodes = Vec::with_capacity(items.len().max(1) * 2);
+
+        if !items.is_empty() {
+            build(&mut nodes, &bounds, &mut order, 0);
+        }
+
+        Self {
+            nodes,
+            items,
+            order,
+        }
+    }
+}
+
+impl<T> StaticBvh<T> {
+    pub fn items(&self) -> &[T] {
+        &self.items
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// 전체를 감싸는 AABB
+    pub fn bounds(&self) -> Option<BoundingBox> {
+        self.nodes.first().map(BvhNode::aabb)
+    }
+
+    /// 트리의 깊이 (루트만 있으면 1)
+    pub fn depth(&self) -> usize {
+        let mut max_depth = 0;
+        let mut stack = Vec::new();
+        if !self.nodes.is_empty() {
+            stack.push((0, 1));
+        }
+        while let Some((index, depth)) = stack.pop() {
+            max_depth = max_depth.max(depth);
+            let node = &self.nodes[index];
+            if !node.is_leaf() {
+                stack.push((index + 1, depth + 1));
+                stack.push((node.offset, depth + 1));
+            }
+        }
+        max_depth
+    }
+
+    /// AABB가 bounds와 겹치는 모든 객체의 인덱스 (narrowphase 없음)
+    pub fn query_aabb(&self, bounds: &BoundingBox) -> Vec<usize>
+    where
+        T: Bounded,
+    {
+        let aabb = bounds.aabb();
+        let (min, max) = (glam::Vec3A::from(aabb.min()), glam::Vec3A::from(aabb.max()));
+        let mut result = Vec::new();
+        let mut stack = Vec::with_capacity(64);
+        if !self.nodes.is_empty() {
+            stack.push(0);
+        }
+
+        while let Some(index) = stack.pop() {
+            let node = &self.nodes[index];
+            if node.min.cmpgt(max).any() || min.cmpgt(node.max).any() {
+                continue;
+            }
+            if node.is_leaf() {
+                // 잎 노드의 AABB와 겹쳐도 각 객체와는 겹치지 않을 수 있다.
+                let items = &self.order[node.offset..node.offset + node.count];
+                result.extend(items.iter().filter(|&&i| self.items[i].aabb().aabb_collision(&aabb)));
+            } else {
+                stack.push(node.offset);
+                stack.push(index + 1);
+            }
+        }
+        result
+    }
+
+    /// shape과 실제로 충돌하는 모든 객체의 인덱스  
+    /// narrowphase는 StaticCollision 구현을 사용한다.  
+    pub fn query<Q>(&self, shape: &Q) -> Vec<usize>
+    where
+        T: Bounded + ConvexHull,
+        Q: Bounded + StaticCollision<T>,
+    {
+        let mut result = self.query_aabb(&shape.aabb());
+        result.retain(|&i| shape.check_static_collision(&self.items[i]));
+        result
+    }
+
+    /// ray와 만나는 가장 가까운 객체를 구한다.  
+    /// 가까운 자식 노드부터 방문하여 먼 노드는 일찍 걸러낸다.  
+    pub fn ray_cast(&self, ray: &Ray, max_distance: f32) -> Option<(usize, RayHit)>
+    where
+        T: RayCast,
+    {
+        let root = self.nodes.first()?;
+        let inv_direction = ray.direction().recip();
+        let mut closest: Option<(usize, RayHit)> = None;
+        let mut max_distance = max_distance;
+        let mut stack = Vec::with_capacity(64);
+        if let Some(entry) = slab(root, ray, &inv_direction, max_distance) {
+            stack.push((0, entry));
+        }
+
+        while let Some((index, entry)) = stack.pop() {
+            if entry > max_distance {
+                continue;
+            }
+            let node = &self.nodes[index];
+            if node.is_leaf() {
+                for &i in &self.order[node.offset..node.offset + node.count] {
+                    if let Some(hit) = self.items[i].cast_ray(ray, max_distance) {
+                        max_distance = hit.distance;
+                        closest = Some((i, hit));
+                    }
+                }
+                continue;
+            }
+
+            let left = (index + 1, slab(&self.nodes[index + 1], ray, &inv_direction, max_distance));
+            let right = (node.offset, slab(&self.nodes[node.offset], ray, &inv_direction, max_distance));
+            // 가까운 노드를 나중에 넣어 먼저 꺼낸다.
+            let (near, far) = match (left.1, right.1) {
+                (Some(l), Some(r)) if r < l => (right, left),
+                _ => (left, right),
+            };
+            if let (index, Some(entry)) = far {
+                stack.push((index, entry));
+            }
+            if let (index, Some(entry)) = near {
+                stack.push((index, entry));
+            }
+        }
+
+        closest
+    }
+
+    /// point와 가장 가까운 객체와 그 표면 위의 점, 거리를 구한다.  
+    /// point가 객체 안에 있으면 거리는 0이다.  
+    pub fn closest_point(&self, point: &glam::Vec3A) -> Option<(usize, glam::Vec3A, f32)>
+    where
+        T: PointQuery,
+    {
+        let root = self.nodes.first()?;
+        let mut best: Option<(usize, glam::Vec3A, f32)> = None;
+        // 노드까지의 거리가 가까운 순서로 방문한다.
+        let mut heap = BinaryHeap::new();
+        heap.push(NodeDistance { distance: node_distance(root, point), index: 0 });
+
+        while let Some(NodeDistance { distance, index }) = heap.pop() {
+            if best.is_some_and(|(_, _, best_distance)| distance >= best_distance) {
+                break;
+            }
+            let node = &self.nodes[index];
+            if node.is_leaf() {
+                for &i in &self.order[node.offset..node.offset + node.count] {
+                    let projection = self.items[i].project_point(point);
+                    let distance = if projection.is_inside { 0.0 } else { (projection.point - point).length() };
+                    if best.is_none_or(|(_, _, best_distance)| distance < best_distance) {
+                        best = Some((i, projection.point, distance));
+                    }
+                }
+            } else {
+                for child in [index + 1, node.offset] {
+                    heap.push(NodeDistance { distance: node_distance(&self.nodes[child], point), index: child });
+                }
+            }
+        }
+
+        best
+    }
+}
+
+
+/// order[start..]의 객체들로 노드를 만든다. (깊이 우선)
+fn build(nodes: &mut Vec<BvhNode>, bounds: &[(glam::Vec3A, glam::Vec3A)], order: &mut [usize], start: usize) -> usize {
+    let (min, max) = order.iter()
+        .fold((glam::Vec3A::MAX, glam::Vec3A::MIN), |(min, max), &i| (min.min(bounds[i].0), max.max(bounds[i].1)));
+    let index = nodes.len();
+    nodes.push(BvhNode {
+        min,
+        max,
+        offset: start,
+        count: order.len(),
+    });
+
+    if order.len() <= LEAF_SIZE {
+        return index;
+    }
+
+    let Some(split) = find_split(bounds, order, min, max) else {
+        return index;
+    };
+
+    nodes[index].count = 0;
+    let (left, right) = order.split_at_mut(split);
+    build(nodes, bounds, left, start);
+    let right_index = build(nodes, bounds, right, start + split);
+    nodes[index].offset = right_index;
+    index
+}
+
+/// binned SAH로 분할 위치를 찾고 order를 나눈다.  
+/// 나누는 것보다 잎 노드로 두는 것이 나으면 None을 반환한다.  
+fn find_split(bounds: &[(glam::Vec3A, glam::Vec3A)], order: &mut [usize], min: glam::Vec3A, max: glam::Vec3A) -> Option<usize> {
+    let centroid = |i: usize| (bounds[i].0 + bounds[i].1) * 0.5;
+    let (centroid_min, centroid_max) = order.iter()
+        .fold((glam::Vec3A::MAX, glam::Vec3A::MIN), |(lo, hi), &i| (lo.min(centroid(i)), hi.max(centroid(i))));
+    let extent = centroid_max - centroid_min;
+    let axis = if extent.x >= extent.y && extent.x >= extent.z {
+        0
+    } else if extent.y >= extent.z {
+        1
+    } else {
+        2
+    };
+    if extent[axis] <= f32::EPSILON {
+        // 모든 중심이 한 점에 모여 있으면 나눌 수 없다.
+        return (order.len() > MAX_LEAF_SIZE).then_some(order.len() / 2);
+    }
+
+    // 1. 중심의 위치에 따라 구간에 나눠 담는다.
+    let bin_of = |i: usize| {
+        let t = (centroid(i)[axis] - centroid_min[axis]) / extent[axis];
+        ((t * BIN_COUNT as f32) as usize).min(BIN_COUNT - 1)
+    };
+    let mut bins = [(0_usize, glam::Vec3A::MAX, glam::Vec3A::MIN); BIN_COUNT];
+    for &i in order.iter() {
+        let bin = &mut bins[bin_of(i)];
+        bin.0 += 1;
+        bin.1 = bin.1.min(bounds[i].0);
+        bin.2 = bin.2.max(bounds[i].1);
+    }
+
+    // 2. 구간 사이의 경계마다 비용(겉넓이 x 개수)을 계산한다.
+    let area = |min: glam::Vec3A, max: glam::Vec3A| {
+        let size = (max - min).max(glam::Vec3A::ZERO);
+        size.x * size.y + size.y * size.z + size.z * size.x
+    };
+    let mut left_cost = [0.0; BIN_COUNT - 1];
+    let (mut count, mut lo, mut hi) = (0, glam::Vec3A::MAX, glam::Vec3A::MIN);
+    for b in 0..BIN_COUNT - 1 {
+        count += bins[b].0;
+        lo = lo.min(bins[b].1);
+        hi = hi.max(bins[b].2);
+        left_cost[b] = count as f32 * area(lo, hi);
+    }
+    let (mut best_split, mut best_cost) = (0, f32::MAX);
+    let (mut count, mut lo, mut hi) = (0, glam::Vec3A::MAX, glam::Vec3A::MIN);
+    for b in (1..BIN_COUNT).rev() {
+        count += bins[b].0;
+        lo = lo.min(bins[b].1);
+        hi = hi.max(bins[b].2);
+        let cost = left_cost[b - 1] + count as f32 * area(lo, hi);
+        if cost < best_cost {
+            best_cost = cost;
+            best_split = b;
+        }
+    }
+
+    // 3. 나누는 비용이 잎 노드의 비용보다 크면 나누지 않는다. (MAX_LEAF_SIZE보다 큰 잎 노드는 예외)
+    let leaf_cost = order.len() as f32 * area(min, max);
+    if best_cost >= leaf_cost && order.len() <= MAX_LEAF_SIZE {
+        return None;
+    }
+
+    // 4. 구간 경계를 기준으로 order를 나눈다.
+    let mut split = 0;
+    for n in 0..order.len() {
+        if bin_of(order[n]) < best_split {
+            order.swap(n, split);
+            split += 1;
+        }
+    }
+    if split == 0 || split == order.len() {
+        split = order.len() / 2;
+    }
+    Some(split)
+}
+
+/// ray가 노드의 AABB에 들어가는 거리 (만나지 않으면 None)
+fn slab(node: &BvhNode, ray: &Ray, inv_direction: &glam::Vec3A, max_distance: f32) -> Option<f32> {
+    ray_aabb_interval(ray, inv_direction, &node.min, &node.max, max_distance).map(|(entry, _)| entry)
+}
+
+fn node_distance(node: &BvhNode, point: &glam::Vec3A) -> f32 {
+    let outside = (node.min - point).max(point - node.max).max(glam::Vec3A::ZERO);
+    outside.length()
+}
+
+/// closest_point에서 노드를 가까운 순서로 꺼내기 위한 Min Heap 원소
+struct NodeDistance {
+    distance: f32,
+    index: usize,
+}
+
+impl PartialEq for NodeDistance {
+    fn eq(&self, other: &Self) -> bool {
+        self.distance == other.distance
+    }
+}
+
+impl Eq for NodeDistance {}
+
+impl PartialOrd for NodeDistance {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for NodeDistance {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        other.distance.total_cmp(&self.distance)
+    }
+}
@@ -0,0 +1,362 @@
+use std::collections::{HashMap, HashSet};
+use crate::{ray_cast::RayCast, BoundingBox, Ray};
+use super::{Bounded, Broadphase, ProxyId};
+
+
+/// 정렬할 축
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SweepMode {
+    /// 한 축(0: x, 1: y, 2: z)만 정렬하고, 나머지 축은 sweep 중에 검사한다.
+    SingleAxis(usize),
+    /// 세 축을 모두 정렬하고, 정렬 중의 교환으로 겹침 쌍을 갱신한다.
+    MultiAxis,
+}
+
+/// 겹침 쌍의 변화 (항상 작은 id가 앞)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PairEvent {
+    Added(ProxyId, ProxyId),
+    Removed(ProxyId, ProxyId),
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Endpoint {
+    value: f32,
+    proxy: usize,
+    is_min: bool,
+}
+
+impl Endpoint {
+    /// 같은 값이면 최소점이 앞에 온다. (접하는 경우도 겹침으로 취급)
+    fn is_before(&self, other: &Endpoint) -> bool {
+        self.value < other.value || (self.value == other.value && self.is_min && !other.is_min)
+    }
+}
+
+#[derive(Debug, Clone)]
+struct SapProxy<T> {
+    aabb: BoundingBox,
+    min: glam::Vec3,
+    max: glam::Vec3,
+    data: Option<T>,
+}
+
+/// Sweep and Prune broadphase  
+/// 축마다 AABB의 끝점들을 정렬된 상태로 유지하고, 매 프레임 삽입 정렬로 갱신한다.  
+/// 물체들이 조금씩만 움직이는 경우 정렬 비용이 거의 선형이다.  
+#[derive(Debug, Clone)]
+pub struct SweepAndPrune<T> {
+    mode: SweepMode,
+    proxies: Vec<SapProxy<T>>,
+    free_list: Vec<usize>,
+    /// 제거된 객체의 쌍이 보고되기 전까지는 자리를 재사용하지 않는다.
+    pending_free: Vec<usize>,
+    /// 마지막 update_pairs 이후 삽입된 객체의 수
+    inserted_count: usize,
+    endpoints: [Vec<Endpoint>; 3],
+    pairs: HashSet<(usize, usize)>,
+    /// 마지막 update_pairs 이후 바뀐 쌍과, 바뀌기 전의 겹침 여부
+    changed_pairs: HashMap<(usize, usize), bool>,
+}
+
+impl<T> Default for SweepAndPrune<T> {
+    fn default() -> Self {
+        Self::new(SweepMode::MultiAxis)
+    }
+}
+
+impl<T> SweepAndPrune<T> {
+    pub fn new(mode: SweepMode) -> Self {
+        let mode = match mode {
+            SweepMode::SingleAxis(axis) => SweepMode::SingleAxis(axis.min(2)),
+            SweepMode::MultiAxis => SweepMode::MultiAxis,
+        };
+        Self {
+            mode,
+            proxies: Vec::new(),
+            free_list: Vec::new(),
+            pending_free: Vec::new(),
+            inserted_count: 0,
+            endpoints: [Vec::new(), Vec::new(), Vec::new()],
+            pairs: HashSet::new(),
+            changed_pairs: HashMap::new(),
+        }
+    }
+
+    pub fn mode(&self) -> SweepMode {
+        self.mode
+    }
+
+    /// 삽입된 객체의 개수
+    pub fn len(&self) -> usize {
+        self.proxies.len() - self.free_list.len() - self.pending_free.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn get(&self, id: ProxyId) -> Option<&T> {
+        self.proxies.get(id.0)?.data.as_ref()
+    }
+
+    pub fn get_mut(&mut self, id: ProxyId) -> Option<&mut T> {
+        self.proxies.get_mut(id.0)?.data.as_mut()
+    }
+
+    pub fn aabb(&self, id: ProxyId) -> Option<BoundingBox> {
+        let proxy = self.proxies.get(id.0)?;
+        proxy.data.as_ref().map(|_| proxy.aabb)
+    }
+
+    /// 삽입된 모든 객체
+    pub fn iter(&self) -> impl Iterator<Item = (ProxyId, &T)> {
+        self.proxies.iter()
+            .enumerate()
+            .filter_map(|(i, proxy)| proxy.data.as_ref().map(|data| (ProxyId(i), data)))
+    }
+
+    /// 객체를 삽입한다. 겹침 쌍은 다음 update_pairs에서 갱신된다.
+    pub fn insert(&mut self, bounds: &impl Bounded, data: T) -> ProxyId {
+        let aabb = bounds.aabb();
+        let proxy = SapProxy {
+            aabb,
+            min: aabb.min(),
+            max: aabb.max(),
+            data: Some(data),
+        };
+        let index = match self.free_list.pop() {
+            Some(index) => {
+                self.proxies[index] = proxy;
+                index
+            }
+            None => {
+                self.proxies.push(proxy);
+                self.proxies.len() - 1
+            }
+        };
+
+        // 끝점은 맨 뒤에 넣고, 다음 정렬 때 제자리를 찾아간다.
+        for axis in self.sorted_axes() {
+            self.endpoints[axis].push(Endpoint { value: f32::MAX, proxy: index, is_min: true });
+            self.endpoints[axis].push(Endpoint { value: f32::MAX, proxy: index, is_min: false });
+        }
+        self.inserted_count += 1;
+        ProxyId(index)
+    }
+
+    /// 객체를 제거하고, 그 객체가 속한 쌍은 다음 update_pairs에서 Removed로 보고된다.
+    pub fn remove(&mut self, id: ProxyId) -> Option<T> {
+        let data = self.proxies.get_mut(id.0)?.data.take()?;
+        for endpoints in self.endpoints.iter_mut() {
+            endpoints.retain(|e| e.proxy != id.0);
+        }
+
+        let removed = self.pairs.iter()
+            .filter(|(a, b)| *a == id.0 || *b == id.0)
+            .copied()
+            .collect::<Vec<_>>();
+        for pair in removed {
+            self.pairs.remove(&pair);
+            self.changed_pairs.entry(pair).or_insert(true);
+        }
+
+        self.pending_free.push(id.0);
+        Some(data)
+    }
+
+    /// 객체의 AABB를 바꾼다. 겹침 쌍은 다음 update_pairs에서 갱신된다.
+    pub fn update(&mut self, id: ProxyId, bounds: &impl Bounded) {
+        if let Some(proxy) = self.proxies.get_mut(id.0)
+            && proxy.data.is_some()
+        {
+            let aabb = bounds.aabb();
+            proxy.aabb = aabb;
+            proxy.min = aabb.min();
+            proxy.max = aabb.max();
+        }
+    }
+
+    /// 현재 겹치는 모든 쌍 (작은 id가 앞)
+    pub fn pairs(&self) -> impl Iterator<Item = (ProxyId, ProxyId)> + '_ {
+        self.pairs.iter().map(|&(a, b)| (ProxyId(a), ProxyId(b)))
+    }
+
+    /// 현재 겹치는 쌍의 개수
+    pub fn pair_count(&self) -> usize {
+        self.pairs.len()
+    }
+
+    /// 끝점 목록을 다시 정렬하여 겹침 쌍을 갱신하고, 마지막 호출 이후의 변화를 반환한다.
+    pub fn update_pairs(&mut self) -> Vec<PairEvent> {
+        let mut events = Vec::new();
+        self.update_pairs_with(|event| events.push(event));
+        events.sort_by_key(|event| match *event {
+            PairEvent::Added(a, b) | PairEvent::Removed(a, b) => (a, b),
+        });
+        events
+    }
+
+    /// update_pairs와 같지만, 변화를 모아 정렬하지 않고 정해지지 않은 순서로 하나씩 넘긴다.  
+    /// 처음 갱신할 때처럼 변화가 많은 경우에도 변화 목록을 따로 만들지 않는다.  
+    pub fn update_pairs_with(&mut self, mut on_event: impl FnMut(PairEvent)) {
+        // 새로 삽입된 객체가 절반 이상이면 삽입 정렬 대신 전체를 다시 정렬한다.
+        let rebuild = self.inserted_count * 2 > self.len();
+        self.inserted_count = 0;
+
+        for axis in self.sorted_axes() {
+            let mut endpoints = std::mem::take(&mut self.endpoints[axis]);
+            for endpoint in endpoints.iter_mut() {
+                let proxy = &self.proxies[endpoint.proxy];
+                endpoint.value = if endpoint.is_min { proxy.min[axis] } else { proxy.max[axis] };
+            }
+            if rebuild {
+                endpoints.sort_unstable_by(|a, b| {
+                    a.value.total_cmp(&b.value).then(b.is_min.cmp(&a.is_min))
+                });
+            } else {
+                let track_pairs = self.mode == SweepMode::MultiAxis;
+                self.insertion_sort(&mut endpoints, track_pairs);
+            }
+            self.endpoints[axis] = endpoints;
+        }
+
+        match self.mode {
+            SweepMode::SingleAxis(axis) => self.sweep(axis),
+            SweepMode::MultiAxis if rebuild => self.sweep(0),
+            SweepMode::MultiAxis => {}
+        }
+
+        for (pair, was_overlapping) in self.changed_pairs.drain() {
+            let (a, b) = (ProxyId(pair.0), ProxyId(pair.1));
+            match (was_overlapping, self.pairs.contains(&pair)) {
+                (false, true) => on_event(PairEvent::Added(a, b)),
+                (true, false) => on_event(PairEvent::Removed(a, b)),
+                _ => {}  // 한 프레임 안에서 생겼다 사라진 쌍
+            }
+        }
+
+        self.free_list.append(&mut self.pending_free);
+    }
+
+    fn sorted_axes(&self) -> std::ops::Range<usize> {
+        match self.mode {
+            SweepMode::SingleAxis(axis) => axis..axis + 1,
+            SweepMode::MultiAxis => 0..3,
+        }
+    }
+
+    /// 끝점과 같은 값으로 비교해야 정렬 결과와 어긋나지 않는다.
+    fn overlaps(&self, a: usize, b: usize) -> bool {
+        let (a, b) = (&self.proxies[a], &self.proxies[b]);
+        a.min.cmple(b.max).all() && b.min.cmple(a.max).all()
+    }
+
+    fn add_pair(&mut self, a: usize, b: usize) {
+        let pair = (a.min(b), a.max(b));
+        if self.pairs.insert(pair) {
+            self.changed_pairs.entry(pair).or_insert(false);
+        }
+    }
+
+    fn remove_pair(&mut self, a: usize, b: usize) {
+        let pair = (a.min(b), a.max(b));
+        if self.pairs.remove(&pair) {
+            self.changed_pairs.entry(pair).or_insert(true);
+        }
+    }
+
+    /// 거의 정렬된 끝점 목록을 삽입 정렬한다.  
+    /// track_pairs인 경우, 최소점과 최대점이 교차할 때 겹침 쌍을 추가/제거한다.  
+    fn insertion_sort(&mut self, endpoints: &mut [Endpoint], track_pairs: bool) {
+        for i in 1..endpoints.len() {
+            let key = endpoints[i];
+            let mut j = i;
+            while j > 0 && key.is_before(&endpoints[j - 1]) {
+                let left = endpoints[j - 1];
+                if track_pairs && key.proxy != left.proxy {
+                    if key.is_min && !left.is_min {
+                        // 최소점이 다른 객체의 최대점을 왼쪽으로 넘음 = 이 축에서 겹치기 시작
+                        if self.overlaps(key.proxy, left.proxy) {
+                            self.add_pair(key.proxy, left.proxy);
+                        }
+                    } else if !key.is_min && left.is_min {
+                        // 최대점이 다른 객체의 최소점을 왼쪽으로 넘음 = 이 축에서 떨어짐
+                        self.remove_pair(key.proxy, left.proxy);
+                    }
+                }
+                endpoints[j] = left;
+                j -= 1;
+            }
+            endpoints[j] = key;
+        }
+    }
+
+    /// 정렬된 한 축을 따라 훑으며 겹치는 쌍을 모두 다시 구한다.
+    fn sweep(&mut self, axis: usize) {
+        let endpoints = std::mem::take(&mut self.endpoints[axis]);
+        let mut active: Vec<usize> = Vec::new();
+        let mut current = HashSet::with_capacity(self.pairs.len());
+
+        for endpoint in endpoints.iter() {
+            if endpoint.is_min {
+                for &other in active.iter() {
+                    if self.overlaps(endpoint.proxy, other) {
+                        let pair = (endpoint.proxy.min(other), endpoint.proxy.max(other));
+                        current.insert(pair);
+                    }
+                }
+                active.push(endpoint.proxy);
+            } else if let Some(pos) = active.iter().position(|&p| p == endpoint.proxy) {
+                active.swap_remove(pos);
+            }
+        }
+        self.endpoints[axis] = endpoints;
+
+        for &pair in self.pairs.difference(&current) {
+            self.changed_pairs.entry(pair).or_insert(true);
+        }
+        for &pair in current.difference(&self.pairs) {
+            self.changed_pairs.entry(pair).or_insert(false);
+        }
+        self.pairs = current;
+    }
+}
+
+
+/// 공간 질의를 위한 구조가 없으므로 query_candidates와 ray_candidates는 모든 객체를 검사한다.
+impl<T: Copy> Broadphase<T> for SweepAndPrune<T> {
+    fn insert_proxy(&mut self, aabb: &BoundingBox, data: T) -> ProxyId {
+        self.insert(aabb, data)
+    }
+
+    fn remove_proxy(&mut self, id: ProxyId) {
+        self.remove(id);
+    }
+
+    fn update_proxy(&mut self, id: ProxyId, aabb: &BoundingBox) {
+        self.update(id, aabb);
+    }
+
+    fn candidate_pairs(&mut self) -> Vec<(T, T)> {
+        self.update_pairs();
+        self.pairs()
+            .filter_map(|(a, b)| Some((*self.get(a)?, *self.get(b)?)))
+            .collect()
+    }
+
+    fn query_candidates(&self, aabb: &BoundingBox) -> Vec<T> {
+        let (min, max) = (aabb.min(), aabb.max());
+        self.proxies.iter()
+            .filter(|proxy| proxy.min.cmple(max).all() && min.cmple(proxy.max).all())
+            .filter_map(|proxy| proxy.data)
+            .collect()
+    }
+
+    fn ray_candidates(&self, ray: &Ray, max_distance: f32) -> Vec<T> {
+        self.proxies.iter()
+            .filter(|proxy| proxy.aabb.cast_ray(ray, max_distance).is_some())
+            .filter_map(|proxy| proxy.data)
+            .collect()
+    }
+}
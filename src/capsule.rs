@@ -0,0 +1,71 @@
+use crate::{BoundingBox, Sphere};
+
+
+/// 선분(center ± half_segment)으로부터 radius 이내의 점들로 이루어진 도형
+#[derive(Debug, Clone, Copy)]
+pub struct Capsule {
+    pub center: glam::Vec3,
+    /// center에서 선분의 한쪽 끝까지의 벡터
+    pub half_segment: glam::Vec3,
+    pub radius: f32,
+}
+
+impl Capsule {
+    pub fn new(center: glam::Vec3, half_segment: glam::Vec3, radius: f32) -> Self {
+        Self {
+            center,
+            half_segment,
+            radius: radius.abs(),
+        }
+    }
+
+    /// y축 방향으로 세운 캡슐  
+    /// height는 양 끝의 반구를 포함한 전체 높이이다.  
+    pub fn upright(center: glam::Vec3, height: f32, radius: f32) -> Self {
+        let half_length = (height * 0.5 - radius).max(0.0);
+        Self::new(center, glam::Vec3::new(0.0, half_length, 0.0), radius)
+    }
+
+    /// 선분의 두 끝점
+    pub fn endpoints(&self) -> (glam::Vec3A, glam::Vec3A) {
+        let center = glam::Vec3A::from(self.center);
+        let half_segment = glam::Vec3A::from(self.half_segment);
+        (center - half_segment, center + half_segment)
+    }
+
+    /// 선분 위에서 point와 가장 가까운 점
+    pub fn closest_point_on_segment(&self, point: &glam::Vec3A) -> glam::Vec3A {
+        let center = glam::Vec3A::from(self.center);
+        let half_segment = glam::Vec3A::from(self.half_segment);
+        let length_sq = half_segment.length_squared();
+        if length_sq <= f32::EPSILON {
+            return center;
+        }
+        let t = (half_segment.dot(point - center) / length_sq).clamp(-1.0, 1.0);
+        center + half_segment * t
+    }
+
+    /// 선분 위에서 point와 가장 가까운 점을 중심으로 하는 구
+    pub fn sphere_at(&self, point: &glam::Vec3A) -> Sphere {
+        Sphere {
+            center: glam::Vec3::from(self.closest_point_on_segment(point)),
+            radius: self.radius,
+        }
+    }
+
+    pub fn check_point_collision(&self, point: &glam::Vec3A) -> bool {
+        (point - self.closest_point_on_segment(point)).length_squared() <= self.radius.powi(2)
+    }
+
+    /// 캡슐을 감싸는 AABB를 반환한다.
+    pub fn bounding_box(&self) -> BoundingBox {
+        BoundingBox::new(self.center, self.half_segment.abs() + glam::Vec3::splat(self.radius))
+    }
+
+    pub fn inflated(&self, amount: f32) -> Capsule {
+        Capsule {
+            radius: self.radius + amount,
+            ..*self
+        }
+    }
+}
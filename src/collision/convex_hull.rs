@@ -1,8 +1,170 @@
 use std::collections::BinaryHeap;
-use crate::{BoundingBox, VertexBox, Sphere};
+use crate::{BoundingBox, Capsule, PreparedBox, VertexBox, Sphere, Triangle};
 use super::CollisionDetails;
 
 
+/// GJK, EPA의 반복 횟수와 허용 오차  
+/// 허용 오차는 도형의 크기에 대한 비율이므로, 밀리미터 단위의 물체와 킬로미터 단위의 물체에 같은 설정을 쓸 수 있다.  
+#[derive(Debug, Clone, Copy)]
+pub struct GjkConfig {
+    /// GJK에서 simplex를 갱신하는 최대 횟수
+    pub max_iterations: usize,
+    /// EPA에서 polytope를 확장하는 최대 횟수
+    pub max_epa_iterations: usize,
+    /// EPA에서 새 support point가 최근접면보다 (이 값 * 처음 simplex의 크기) 미만으로 멀면 수렴한 것으로 본다.
+    pub relative_tolerance: f32,
+    /// 두 변이 이루는 각의 sin 값이 이 값 이하이면 두 변이 평행한 것으로 보고, 그 면의 법선벡터를 구하지 않는다.  
+    /// GJK에서 원점이 simplex 위에 있는지도 (simplex의 크기에 대한) 이 비율로 판단한다.  
+    pub degenerate_tolerance: f32,
+    /// 두 도형이 이 거리 이내로 떨어져 있어도 충돌한 것으로 본다. (절대 거리)  
+    /// EPA의 penetration은 -contact_margin까지 음수가 될 수 있다.  
+    pub contact_margin: f32,
+}
+
+impl Default for GjkConfig {
+    fn default() -> Self {
+        Self {
+            max_iterations: 64,
+            max_epa_iterations: 128,
+            relative_tolerance: 0.0001,
+            degenerate_tolerance: 0.000001,
+            contact_margin: 0.0,
+        }
+    }
+}
+
+/// 이전 GJK의 탐색 방향  
+/// 도형은 프레임마다 조금씩만 움직이므로, 쌍마다 보관해 두었다가 다음 GJK를 이 방향에서 시작하면 반복 횟수가 줄어든다.  
+#[derive(Debug, Clone, Copy, Default)]
+pub struct GjkCache {
+    /// 떨어져 있었다면 분리 방향, 충돌했다면 마지막으로 support point를 구한 방향
+    pub direction: Option<glam::Vec3A>,
+}
+
+/// GJK, EPA가 끝난 이유
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Termination {
+    /// 원점을 분리하는 평면을 찾았다.
+    Separated,
+    /// 원점이 사면체가 아닌 simplex(점, 선분, 삼각형) 위에 있다. (EPA는 simplex를 사면체로 넓혀서 시작한다.)
+    Touching,
+    /// GJK의 사면체가 원점을 포함한다.
+    Intersecting,
+    /// EPA의 최근접면이 허용 오차 안으로 수렴했다.
+    Converged,
+    /// EPA에서 새 면을 만들 수 없어, 그때까지의 최근접면으로 끝났다.
+    Stalled,
+    /// simplex가 납작해져 원점이 안쪽에 있는지 판별하지 못하고 끝났다.
+    DegenerateSimplex,
+    /// 최대 반복 횟수 안에 수렴하지 못하고 끝났다.
+    IterationLimit,
+    /// support point가 유한하지 않거나 오차로 polytope의 면이 뒤집혀 끝났다.
+    NumericalFailure,
+}
+
+/// GJK, EPA가 한 일의 양  
+/// SAT와의 성능 차이를 분석할 때 사용한다.  
+#[derive(Debug, Clone, Copy, Default)]
+pub struct GjkDiagnostics {
+    /// GJK에서 처음 support point를 구한 뒤 simplex를 갱신한 횟수
+    pub gjk_iterations: usize,
+    /// EPA에서 polytope를 확장한 횟수
+    pub epa_iterations: usize,
+    /// support point를 구한 횟수 (GJK와 EPA의 합)
+    pub support_calls: usize,
+    /// EPA의 마지막 반복에서 polytope의 점의 개수
+    pub polytope_vertices: usize,
+    /// EPA의 마지막 반복에서 polytope의 면의 개수
+    pub polytope_faces: usize,
+    /// 아직 실행하지 않았다면 None
+    pub termination: Option<Termination>,
+}
+
+/// GJK, EPA가 답을 구하지 못한 이유  
+/// 실패하기 직전까지 구한 가장 좋은 추정값을 담는다. (추정값이 없으면 None)  
+#[derive(Debug, Clone)]
+pub enum GjkError<T> {
+    /// simplex가 납작해져 원점이 안쪽에 있는지 판별할 수 없다.
+    DegenerateSimplex(Option<Box<T>>),
+    /// 최대 반복 횟수 안에 수렴하지 않았다.
+    IterationLimit(Option<Box<T>>),
+    /// support point가 유한하지 않거나(NaN, 무한대) 오차로 polytope의 면이 뒤집혔다.
+    NumericalFailure(Option<Box<T>>),
+}
+
+impl<T> GjkError<T> {
+    pub fn estimate(&self) -> Option<&T> {
+        match self {
+            Self::DegenerateSimplex(estimate)
+            | Self::IterationLimit(estimate)
+            | Self::NumericalFailure(estimate) => estimate.as_deref(),
+        }
+    }
+
+    pub fn into_estimate(self) -> Option<T> {
+        match self {
+            Self::DegenerateSimplex(estimate)
+            | Self::IterationLimit(estimate)
+            | Self::NumericalFailure(estimate) => estimate.map(|estimate| *estimate),
+        }
+    }
+
+    pub fn termination(&self) -> Termination {
+        match self {
+            Self::DegenerateSimplex(_) => Termination::DegenerateSimplex,
+            Self::IterationLimit(_) => Termination::IterationLimit,
+            Self::NumericalFailure(_) => Termination::NumericalFailure,
+        }
+    }
+
+    /// 종류는 그대로 두고 추정값만 바꾼다.
+    fn map_estimate<U>(self, f: impl FnOnce(T) -> Option<U>) -> GjkError<U> {
+        let termination = self.termination();
+        let estimate = self.into_estimate().and_then(f).map(Box::new);
+        match termination {
+            Termination::DegenerateSimplex => GjkError::DegenerateSimplex(estimate),
+            Termination::IterationLimit => GjkError::IterationLimit(estimate),
+            _ => GjkError::NumericalFailure(estimate),
+        }
+    }
+
+    /// 종류는 그대로 두고 추정값만 버린다.
+    fn without_estimate<U>(self) -> GjkError<U> {
+        self.map_estimate(|_| None)
+    }
+}
+
+impl<T> std::fmt::Display for GjkError<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::DegenerateSimplex(_) => write!(f, "degenerate simplex"),
+            Self::IterationLimit(_) => write!(f, "iteration limit reached"),
+            Self::NumericalFailure(_) => write!(f, "numerical failure"),
+        }
+    }
+}
+
+impl<T: std::fmt::Debug> std::error::Error for GjkError<T> {}
+
+/// Ok(None)은 두 도형이 떨어져 있음을 뜻한다.
+pub type GjkResult = Result<Option<Simplex>, GjkError<Simplex>>;
+/// Ok(None)은 두 도형이 떨어져 있음을 뜻한다.
+pub type EpaResult = Result<Option<CollisionDetails>, GjkError<CollisionDetails>>;
+/// Ok(None)은 두 도형이 겹치거나 접해 있음을 뜻한다.
+pub type DistanceResult = Result<Option<ClosestPoints>, GjkError<ClosestPoints>>;
+
+/// 떨어져 있는 두 도형 사이의 최단 거리와 그 양 끝점
+#[derive(Debug, Clone, Copy)]
+pub struct ClosestPoints {
+    pub distance: f32,
+    /// self(contact_margin만큼 부풀린 도형) 위의 점
+    pub point_a: glam::Vec3A,
+    /// other 위의 점
+    pub point_b: glam::Vec3A,
+    /// point_b에서 point_a를 향하는 단위벡터
+    pub normal: glam::Vec3A,
+}
+
 pub trait ConvexHull {
     /// 도형에 속하는 점 중 direction 방향으로 가장 먼 점을 반환한다.  
     fn get_furthest_point(&self, direction: &glam::Vec3A) -> glam::Vec3A;
@@ -12,207 +174,182 @@ pub trait ConvexHull {
         self.get_furthest_point(direction) - other.get_furthest_point(&-direction)
     }
 
+    /// 도형을 core 도형과 그것을 감싸는 두께로 나눌 때의 두께 (margin-based GJK)  
+    /// 도형은 core를 margin만큼 부풀린 것이다. (구는 중심점 + 반지름)  
+    fn margin(&self) -> f32 {
+        0.0
+    }
+
+    /// margin을 뺀 core 도형에 속하는 점 중 direction 방향으로 가장 먼 점을 반환한다.  
+    /// margin이 0이 아니면 반드시 다시 구현해야 한다.  
+    fn get_core_point(&self, direction: &glam::Vec3A) -> glam::Vec3A {
+        self.get_furthest_point(direction)
+    }
+
+    /// 기본 설정으로 gjk_with_config를 호출한다.
+    fn gjk(&self, other: &impl ConvexHull) -> GjkResult {
+        self.gjk_with_config(other, &GjkConfig::default())
+    }
+
     /// 충돌거리가 0인경우(접하는 경우)에도 Some을 반환한다.  
-    fn gjk(&self, other: &impl ConvexHull) -> Option<Simplex> {
-        let mut simplex = Simplex {
-            vertices: [glam::Vec3A::ZERO; 4],
-            count: 0,
-        };
+    fn gjk_with_config(&self, other: &impl ConvexHull, config: &GjkConfig) -> GjkResult {
+        self.gjk_with_cache(other, config, &mut GjkCache::default())
+    }
 
-        // 1. 임의의 방향에 대한 support point를 구한다.
-        let mut direction = glam::Vec3A::X;
-        simplex.vertices[0] = self.get_support(other, &direction);
-        
-        // 2. -support방향에 대한 support point를 구한다.
-        direction = match simplex.vertices[0].try_normalize() {
-            Some(dir) => -dir,
-            // support point가 원점인 경우 = 두 도형이 접함
-            None => {
-                simplex.count = 1;
-                return Some(simplex);
-            }
-        };
-        simplex.vertices[1] = self.get_support(other, &direction);
-        if simplex.vertices[1].dot(direction) < 0.0 {
-            return None;
-        }
-
-        // 3. 두 support point를 잇는 직선에서 원점을 향하는 방향에 대한 support point를 구한다.
-        let cross = simplex.vertices[0].cross(simplex.vertices[1]);
-        let v = simplex.vertices[1] - simplex.vertices[0];
-        if cross == glam::Vec3A::ZERO {     // 원점과 두 support point가 한 직선 상에 있는 경우
-            direction = glam::Vec3A::Y.cross(v);
-            if direction == glam::Vec3A::ZERO {
-                direction = glam::Vec3A::Z.cross(v);
-            }
-            direction = direction.normalize();
-            simplex.vertices[2] = self.get_support(other, &direction);
-            if simplex.vertices[2] == simplex.vertices[0] || simplex.vertices[2] == simplex.vertices[1] {
-                direction = -direction;
-                simplex.vertices[2] = self.get_support(other, &direction);
-            }
-        }
-        else {
-            direction = cross.cross(v);
-            direction = direction.normalize();
-            simplex.vertices[2] = self.get_support(other, &direction);
-        }
-        if simplex.vertices[2].dot(direction) < 0.0 {
-            return None;
-        }
+    /// cache.direction에서 탐색을 시작하고, 끝나면 cache를 갱신한다. (에러인 경우에는 그대로 둔다.)  
+    /// 이전에 떨어져 있었고 지금도 떨어져 있다면 대부분 support point 하나로 끝난다.  
+    fn gjk_with_cache(&self, other: &impl ConvexHull, config: &GjkConfig, cache: &mut GjkCache) -> GjkResult {
+        self.gjk_with_diagnostics(other, config, cache, &mut GjkDiagnostics::default())
+    }
 
-        // 4. 세 support point가 만드는 평면에서 원점을 향하는 방향에 대한 support point를 구한다.
-        let normal = (simplex.vertices[1] - simplex.vertices[0]).cross(simplex.vertices[2] - simplex.vertices[0]);
-        if normal.dot(simplex.vertices[0]) < 0.0 {
-            direction = normal;
-            // CCW로 정렬
-            (simplex.vertices[1], simplex.vertices[2]) = (simplex.vertices[2], simplex.vertices[1]);
-        } else {
-            direction = -normal;
-        }
-        direction = direction.normalize();
-        simplex.vertices[3] = self.get_support(other, &direction);
-        if simplex.vertices[3].dot(direction) < 0.0 {
-            return None;
-        }
-
-        // 5. Simplex가 원점을 포함할 때까지 반복한다.
-        while let Some(face) = simplex.get_nearest_if_not_contains_origin() {
-            simplex.vertices = [
-                // CCW로 정렬
-                simplex.vertices[face.vertices[0]], 
-                simplex.vertices[face.vertices[2]], 
-                simplex.vertices[face.vertices[1]], 
-                self.get_support(other, &face.normal)
-            ];
-            if simplex.vertices[3].dot(face.normal) < 0.0 {
-                return None;
-            }
+    /// gjk_with_cache와 같고, 반복 횟수 등을 diagnostics에 덮어쓴다.
+    fn gjk_with_diagnostics(
+        &self,
+        other: &impl ConvexHull,
+        config: &GjkConfig,
+        cache: &mut GjkCache,
+        diagnostics: &mut GjkDiagnostics,
+    ) -> GjkResult {
+        *diagnostics = GjkDiagnostics::default();
+        let result = gjk_search(self, other, config, cache, diagnostics, Some(0.0));
+        diagnostics.termination = Some(match &result {
+            Ok(GjkOutcome::Separated(_)) => Termination::Separated,
+            Ok(GjkOutcome::Intersecting(simplex)) if simplex.count < 4 => Termination::Touching,
+            Ok(GjkOutcome::Intersecting(_)) => Termination::Intersecting,
+            Err(error) => error.termination(),
+        });
+        result.map(|outcome| match outcome {
+            GjkOutcome::Separated(_) => None,
+            GjkOutcome::Intersecting(simplex) => Some(simplex),
+        })
+    }
+
+    /// 떨어져 있는 두 도형 사이의 최단 거리와 양 끝점을 구한다.  
+    /// 두 도형이 겹치거나 접하면 Ok(None)을 반환한다. (침투 깊이는 gjk_epa로 구한다.)  
+    fn gjk_distance(&self, other: &impl ConvexHull, config: &GjkConfig) -> DistanceResult {
+        let result = gjk_search(
+            self, other, config,
+            &mut GjkCache::default(),
+            &mut GjkDiagnostics::default(),
+            None,
+        );
+        match result {
+            Ok(GjkOutcome::Separated(simplex)) => Ok(Some(simplex.closest_points())),
+            Ok(GjkOutcome::Intersecting(_)) => Ok(None),
+            Err(error) => Err(error.map_estimate(|simplex| Some(simplex.closest_points()))),
         }
+    }
 
-        simplex.count = 4;
-        Some(simplex)
+    /// 기본 설정으로 gjk_epa_with_config를 호출한다.
+    fn gjk_epa(&self, other: &impl ConvexHull) -> EpaResult {
+        self.gjk_epa_with_config(other, &GjkConfig::default())
     }
 
-    fn gjk_epa(&self, other: &impl ConvexHull) -> Option<CollisionDetails> {
-        let simplex = self.gjk(other)?;
-        if simplex.count <= 1 {
-            return Some(CollisionDetails {
-                normal: glam::Vec3A::ZERO,
-                penetration: 0.0,
-            });
+    /// GJK가 실패하면 EPA를 시작할 수 없으므로 추정값 없이 같은 종류의 에러를 반환한다.
+    fn gjk_epa_with_config(&self, other: &impl ConvexHull, config: &GjkConfig) -> EpaResult {
+        self.gjk_epa_with_diagnostics(other, config, &mut GjkDiagnostics::default())
+    }
+
+    /// gjk_epa_with_config와 같고, GJK와 EPA의 반복 횟수 등을 diagnostics에 덮어쓴다.
+    fn gjk_epa_with_diagnostics(
+        &self,
+        other: &impl ConvexHull,
+        config: &GjkConfig,
+        diagnostics: &mut GjkDiagnostics,
+    ) -> EpaResult {
+        *diagnostics = GjkDiagnostics::default();
+        let result = epa_search(self, other, config, diagnostics);
+        if let Err(error) = &result {
+            diagnostics.termination = Some(error.termination());
         }
+        result
+    }
 
-        // 1. simplex의 모든 면에 대해 원점과의 거리를 구한다.
-        let mut polytope = Vec::from(simplex.vertices);
-        let indices = [
-            // CCW
-            [0, 1, 2],
-            [3, 1, 0],
-            [3, 2, 1],
-            [3, 0, 2],
-        ];
-        // 원점은 무조건 simplex 안에 있다.
-        let faces = Face::vec(&polytope, &indices);
-        let mut faces = faces.into_iter()
-            .filter_map(|f| f)
-            .collect::<BinaryHeap<_>>();
-
-        loop {
-            // 2. 최근접면의 법선벡터 방향으로 polytope를 확장한다.
-            // 2-1. 최근접면을 찾고 법선벡터 방향으로 support point를 구한다.
-            let nearest_face = match faces.peek() {
-                Some(face) => face,
-                None => {
-                    println!("polytope: {:?}", polytope);
-                    println!("faces: {:?}", faces);
-                    panic!("No nearest face");
-                }
-            };
-            let collision_info = CollisionDetails {
-                normal: -nearest_face.normal,
-                penetration: nearest_face.distance,
-            };
-            let support = self.get_support(other, &nearest_face.normal);
-            let distance = nearest_face.normal.dot(support);
-            // 그런 simplex가 없다면 리턴
-            if (distance - nearest_face.distance).abs() < 0.0001 {
-                return Some(collision_info);
-            }
-            polytope.push(support);
-            let idx = polytope.len() - 1;
-            
-            // 2-2. O to support 벡터와 방향이 같은 모든 면을 제거한다.
-            let same_direction_faces;
-            (same_direction_faces, faces) = faces.iter()
-                .partition(|f| {
-                    let d = support - polytope[f.vertices[0]];
-                    f.normal.dot(d) > 0.0
-                });
-
-            // 2-3. 새로운 면을 만든다.
-            let edges = same_direction_faces.iter()
-                .map(|f| [
-                    [f.vertices[0], f.vertices[1]],
-                    [f.vertices[1], f.vertices[2]],
-                    [f.vertices[2], f.vertices[0]],
-                ])
-                .flatten();
-            let mut unique_edges = Vec::new();
-            // O(n^2)
-            for edge in edges {
-                let rev_edge = [edge[1], edge[0]];
-                let pos = unique_edges.iter().position(|e| *e == rev_edge);
-                if let Some(i) = pos {
-                    unique_edges.remove(i);
+    /// core끼리의 GJK 거리에서 두 margin을 빼서 충돌 정보를 구한다. (margin-based GJK)  
+    /// core끼리 떨어져 있는 얕은 침투는 EPA 없이 core의 최근접점으로 법선벡터와 penetration을 구하고,  
+    /// core끼리 겹치는 깊은 침투만 margin을 포함한 도형의 EPA로 구한다.  
+    fn gjk_epa_with_margin(&self, other: &impl ConvexHull, config: &GjkConfig) -> EpaResult {
+        let margin = self.margin() + other.margin();
+        let to_details = |closest: ClosestPoints| CollisionDetails {
+            normal: closest.normal,
+            penetration: margin - closest.distance,
+        };
+        // contact_margin은 core가 아니라 margin에 더한다.
+        let core_config = GjkConfig {
+            contact_margin: 0.0,
+            ..*config
+        };
+        // 두 margin의 합보다 멀리 떨어져 있음을 확인하면 최근접점까지 수렴하지 않고 끝낸다.
+        let limit = margin + config.contact_margin;
+        let result = gjk_search(
+            &Core(self), &Core(other), &core_config,
+            &mut GjkCache::default(),
+            &mut GjkDiagnostics::default(),
+            Some(limit),
+        );
+        match result {
+            Ok(GjkOutcome::Separated(simplex)) => {
+                let closest = simplex.closest_points();
+                if closest.distance > limit {
+                    Ok(None)
+                } else if closest.normal != glam::Vec3A::ZERO {
+                    Ok(Some(to_details(closest)))
                 } else {
-                    unique_edges.push(edge);
+                    self.gjk_epa_with_config(other, config)
                 }
             }
-            let new_face_indices = unique_edges.iter()
-                .map(|edge| [edge[0], edge[1], idx])    // CCW
-                .collect::<Vec<_>>();
-            let new_faces = Face::vec(&polytope, &new_face_indices)
-                .into_iter()
-                .filter_map(|f| f)
-                .collect::<Vec<_>>();
-            if new_faces.is_empty() {
-                return Some(collision_info);
-            }
-
-            // 3. 새로 만들어진 면들을 Heap에 추가한다.
-            for face in new_faces {
-                faces.push(face);
-            }
+            Ok(GjkOutcome::Intersecting(_)) => self.gjk_epa_with_config(other, config),
+            Err(error) => Err(error.map_estimate(|simplex| Some(to_details(simplex.closest_points())))),
         }
     }
 }
 
 impl ConvexHull for BoundingBox {
     fn get_furthest_point(&self, direction: &glam::Vec3A) -> glam::Vec3A {
-        self.get_vertices().iter()
-            .max_by(|&a, &b| direction.dot(*a).partial_cmp(&direction.dot(*b)).unwrap())
-            .copied()
-            .unwrap()
+        furthest_vertex(&self.get_vertices(), direction)
     }
 
-    fn gjk(&self, other: &impl ConvexHull) -> Option<Simplex> {
+    fn gjk_with_diagnostics(
+        &self,
+        other: &impl ConvexHull,
+        config: &GjkConfig,
+        cache: &mut GjkCache,
+        diagnostics: &mut GjkDiagnostics,
+    ) -> GjkResult {
         let vertex_box = VertexBox::from(self);
-        vertex_box.gjk(other)
+        vertex_box.gjk_with_diagnostics(other, config, cache, diagnostics)
     }
 
-    fn gjk_epa(&self, other: &impl ConvexHull) -> Option<CollisionDetails> {
+    fn gjk_epa_with_diagnostics(
+        &self,
+        other: &impl ConvexHull,
+        config: &GjkConfig,
+        diagnostics: &mut GjkDiagnostics,
+    ) -> EpaResult {
         let vertex_box = VertexBox::from(self);
-        vertex_box.gjk_epa(other)
+        vertex_box.gjk_epa_with_diagnostics(other, config, diagnostics)
+    }
+
+    fn gjk_distance(&self, other: &impl ConvexHull, config: &GjkConfig) -> DistanceResult {
+        let vertex_box = VertexBox::from(self);
+        vertex_box.gjk_distance(other, config)
+    }
+
+    fn gjk_epa_with_margin(&self, other: &impl ConvexHull, config: &GjkConfig) -> EpaResult {
+        let vertex_box = VertexBox::from(self);
+        vertex_box.gjk_epa_with_margin(other, config)
     }
 }
 
 impl ConvexHull for VertexBox {
     fn get_furthest_point(&self, direction: &glam::Vec3A) -> glam::Vec3A {
-        self.get_vertices().iter()
-            .max_by(|&a, &b| direction.dot(*a).partial_cmp(&direction.dot(*b)).unwrap())
-            .copied()
-            .unwrap()
+        furthest_vertex(self.get_vertices(), direction)
+    }
+}
+
+impl ConvexHull for PreparedBox {
+    fn get_furthest_point(&self, direction: &glam::Vec3A) -> glam::Vec3A {
+        self.vertex_box().get_furthest_point(direction)
     }
 }
 
@@ -220,107 +357,739 @@ impl ConvexHull for Sphere {
     fn get_furthest_point(&self, direction: &glam::Vec3A) -> glam::Vec3A {
         glam::Vec3A::from(self.center) + direction * self.radius
     }
+
+    /// 구는 중심점을 반지름만큼 부풀린 것이다.
+    fn margin(&self) -> f32 {
+        self.radius
+    }
+
+    fn get_core_point(&self, _direction: &glam::Vec3A) -> glam::Vec3A {
+        glam::Vec3A::from(self.center)
+    }
+}
+
+impl ConvexHull for Capsule {
+    fn get_furthest_point(&self, direction: &glam::Vec3A) -> glam::Vec3A {
+        self.get_core_point(direction) + direction.normalize_or_zero() * self.radius
+    }
+
+    /// 캡슐은 선분을 반지름만큼 부풀린 것이다.
+    fn margin(&self) -> f32 {
+        self.radius
+    }
+
+    fn get_core_point(&self, direction: &glam::Vec3A) -> glam::Vec3A {
+        let (start, end) = self.endpoints();
+        if direction.dot(end - start) >= 0.0 { end } else { start }
+    }
+}
+
+impl ConvexHull for Triangle {
+    fn get_furthest_point(&self, direction: &glam::Vec3A) -> glam::Vec3A {
+        let [a, b, c] = self.vertices;
+        let furthest = if direction.dot(b) > direction.dot(a) { b } else { a };
+        if direction.dot(c) > direction.dot(furthest) { c } else { furthest }
+    }
+}
+
+/// 도형의 모서리를 radius만큼 둥글게 만든 도형  
+/// 도형을 radius만큼 부풀리므로, 크기를 유지하려면 radius만큼 줄인 도형을 넣는다. (BoundingBox::rounded)  
+#[derive(Debug, Clone, Copy)]
+pub struct Rounded<T> {
+    pub shape: T,
+    pub radius: f32,
+}
+
+impl<T: ConvexHull> ConvexHull for Rounded<T> {
+    fn get_furthest_point(&self, direction: &glam::Vec3A) -> glam::Vec3A {
+        self.get_core_point(direction) + direction.normalize_or_zero() * self.margin()
+    }
+
+    fn margin(&self) -> f32 {
+        self.shape.margin() + self.radius
+    }
+
+    fn get_core_point(&self, direction: &glam::Vec3A) -> glam::Vec3A {
+        self.shape.get_core_point(direction)
+    }
+}
+
+/// margin을 뺀 core 도형
+struct Core<'a, T: ?Sized>(&'a T);
+
+impl<T: ConvexHull + ?Sized> ConvexHull for Core<'_, T> {
+    fn get_furthest_point(&self, direction: &glam::Vec3A) -> glam::Vec3A {
+        self.0.get_core_point(direction)
+    }
+}
+
+/// vertices 중 direction 방향으로 가장 먼 점  
+/// 투영이 NaN인 점이 있으면 그 점을 그대로 반환한다. (GJK가 panic 대신 NumericalFailure로 끝난다.)  
+fn furthest_vertex(vertices: &[glam::Vec3A; 8], direction: &glam::Vec3A) -> glam::Vec3A {
+    let mut furthest = vertices[0];
+    let mut max_projection = direction.dot(furthest);
+    for vertex in &vertices[1..] {
+        let projection = direction.dot(*vertex);
+        if projection.is_nan() {
+            return *vertex;
+        }
+        if projection > max_projection {
+            furthest = *vertex;
+            max_projection = projection;
+        }
+    }
+    furthest
+}
+
+/// self - other의 Minkowski 차
+pub(crate) struct MinkowskiDifference<'a, A, B> {
+    pub a: &'a A,
+    pub b: &'a B,
+}
+
+impl<A: ConvexHull, B: ConvexHull> ConvexHull for MinkowskiDifference<'_, A, B> {
+    fn get_furthest_point(&self, direction: &glam::Vec3A) -> glam::Vec3A {
+        self.a.get_support(self.b, direction)
+    }
+}
+
+/// gjk_search의 결과
+enum GjkOutcome {
+    Separated(Simplex),
+    /// 원점이 simplex 위에 있다. (사면체가 아니면 두 도형이 접한 경우)
+    Intersecting(Simplex),
+}
+
+/// signed volume으로 simplex를 줄여 가며 원점과 가장 가까운 점을 찾는 GJK  
+/// separate_beyond가 Some이면 두 도형이 그 거리보다 멀리 떨어져 있음을 확인하는 즉시 끝내고,  
+/// None이면 최근접점으로 수렴할 때까지 반복한다. (거리 질의)  
+fn gjk_search<A: ConvexHull + ?Sized>(
+    a: &A,
+    b: &impl ConvexHull,
+    config: &GjkConfig,
+    cache: &mut GjkCache,
+    diagnostics: &mut GjkDiagnostics,
+    separate_beyond: Option<f32>,
+) -> Result<GjkOutcome, GjkError<Simplex>> {
+    // direction은 항상 단위벡터이다. contact_margin은 a를 부풀린 것으로 본다.
+    let support = |direction: &glam::Vec3A, diagnostics: &mut GjkDiagnostics| {
+        diagnostics.support_calls += 1;
+        let point_a = a.get_furthest_point(direction) + *direction * config.contact_margin;
+        let point_b = b.get_furthest_point(&-*direction);
+        (point_a, point_b)
+    };
+    let mut simplex = Simplex::default();
+
+    // 1. 임의의 방향(이전의 탐색 방향)에 대한 support point에서 시작한다.
+    let mut direction = cache.direction
+        .and_then(|direction| direction.try_normalize())
+        .unwrap_or(glam::Vec3A::X);
+    let (point_a, point_b) = support(&direction, diagnostics);
+    simplex.push(point_a, point_b);
+    simplex.check_finite()?;
+    let mut closest = simplex.vertices[0];
+    // support point의 direction 성분의 음수는 원점과 Minkowski 차 사이 거리의 하한이다.
+    let separated = |w: glam::Vec3A, direction: glam::Vec3A| {
+        separate_beyond.is_some_and(|limit| w.dot(direction) < -limit)
+    };
+    if separated(closest, direction) {
+        cache.direction = Some(direction);
+        return Ok(GjkOutcome::Separated(simplex));
+    }
+
+    loop {
+        // 2. 원점이 simplex 위에 있으면 충돌
+        let scale_sq = simplex.vertices().iter()
+            .map(|vertex| vertex.length_squared())
+            .fold(0.0, f32::max);
+        if closest.length_squared() <= config.degenerate_tolerance.powi(2) * scale_sq {
+            // 원점이 선분이나 삼각형 위에 있으면, 수직인 방향으로 점을 더해 사면체로 만든다.
+            // 어느 쪽으로도 넓어지지 않으면 원점이 도형의 경계 위에 있는 것이다. (접한 경우)
+            let perpendicular = match simplex.count {
+                2 => (simplex.vertices[1] - simplex.vertices[0]).try_normalize()
+                    .map(|edge| edge.any_orthonormal_vector()),
+                3 => (simplex.vertices[1] - simplex.vertices[0])
+                    .cross(simplex.vertices[2] - simplex.vertices[0])
+                    .try_normalize(),
+                _ => None,
+            };
+            let expansion = perpendicular.and_then(|perpendicular| {
+                [perpendicular, -perpendicular].into_iter().find_map(|candidate| {
+                    let (point_a, point_b) = support(&candidate, diagnostics);
+                    let w = point_a - point_b;
+                    (w.dot(candidate) > config.degenerate_tolerance * scale_sq.sqrt())
+                        .then_some((candidate, point_a, point_b))
+                })
+            });
+            let Some((candidate, point_a, point_b)) = expansion else {
+                cache.direction = Some(direction);
+                return Ok(GjkOutcome::Intersecting(simplex));
+            };
+            direction = candidate;
+            simplex.push(point_a, point_b);
+            simplex.check_finite()?;
+            continue;
+        }
+        if diagnostics.gjk_iterations >= config.max_iterations {
+            return Err(GjkError::IterationLimit(Some(Box::new(simplex))));
+        }
+        diagnostics.gjk_iterations += 1;
+
+        // 3. 원점을 향하는 방향의 support point를 구한다.
+        direction = -closest.normalize();
+        let (point_a, point_b) = support(&direction, diagnostics);
+        let w = point_a - point_b;
+        if !w.is_finite() {
+            return Err(GjkError::NumericalFailure(Some(Box::new(simplex))));
+        }
+        // support point가 원점을 넘지 못하면 이 방향의 평면이 원점을 분리한다.
+        if separated(w, direction) {
+            cache.direction = Some(direction);
+            return Ok(GjkOutcome::Separated(simplex));
+        }
+        // 더 이상 원점에 가까워지지 않으면 closest가 최근접점이다.
+        let distance_sq = closest.length_squared();
+        if distance_sq - closest.dot(w) <= config.relative_tolerance * distance_sq
+            || simplex.vertices().contains(&w)
+        {
+            cache.direction = Some(direction);
+            return Ok(GjkOutcome::Separated(simplex));
+        }
+
+        // 4. 새 점을 더하고, 원점과 가장 가까운 점을 만드는 데 쓰이지 않는 점을 뺀다.
+        simplex.push(point_a, point_b);
+        closest = simplex.reduce();
+        if simplex.count == 4 {
+            // 원점이 사면체 안에 있음
+            cache.direction = Some(direction);
+            return Ok(GjkOutcome::Intersecting(simplex));
+        }
+        // 오차 때문에 원점에 가까워지지 않고 같은 점들을 맴도는 경우
+        if closest.length_squared() >= distance_sq {
+            cache.direction = Some(direction);
+            return Ok(GjkOutcome::Separated(simplex));
+        }
+    }
 }
 
+/// EPA의 본체 (ConvexHull::gjk_epa_with_diagnostics)  
+/// GJK가 떨어져 있다고 판단했다면 GJK가 기록한 termination을 그대로 둔다.  
+fn epa_search<A: ConvexHull + ?Sized>(
+    a: &A,
+    b: &impl ConvexHull,
+    config: &GjkConfig,
+    diagnostics: &mut GjkDiagnostics,
+) -> EpaResult {
+    // GJK와 같이 contact_margin만큼 부풀린 도형으로 확장한다.
+    let support = |direction: &glam::Vec3A, diagnostics: &mut GjkDiagnostics| {
+        diagnostics.support_calls += 1;
+        a.get_support(b, direction) + *direction * config.contact_margin
+    };
+    let Some(simplex) = a.gjk_with_diagnostics(b, config, &mut GjkCache::default(), diagnostics)
+        .map_err(GjkError::without_estimate)?
+    else {
+        return Ok(None);
+    };
+
+    // 1. simplex로 polytope를 만든다. (원점은 무조건 simplex 안이나 경계 위에 있다.)
+    // 두 도형이 접해서 GJK가 사면체를 만들지 못했다면, 주축 방향의 support point를 더해 사면체로 만든다.
+    let Some(vertices) = blow_up_simplex(&simplex, config.degenerate_tolerance, |direction| {
+        support(direction, diagnostics)
+    }) else {
+        return Err(GjkError::DegenerateSimplex(None));
+    };
+    let Some(mut polytope) = Polytope::from_tetrahedron(vertices, config.degenerate_tolerance) else {
+        return Err(GjkError::DegenerateSimplex(None));
+    };
+    // 처음 simplex의 크기에 비례하는 허용 오차
+    let scale = vertices.iter().map(|vertex| vertex.length()).fold(0.0, f32::max);
+    let tolerance = config.relative_tolerance * scale;
 
+    let mut best = None;
+    while diagnostics.epa_iterations < config.max_epa_iterations {
+        diagnostics.epa_iterations += 1;
+        diagnostics.polytope_vertices = polytope.vertices.len();
+        diagnostics.polytope_faces = polytope.live_faces;
 
+        // 2. 최근접면의 법선벡터 방향으로 polytope를 확장한다.
+        // 2-1. 최근접면을 찾고 법선벡터 방향으로 support point를 구한다.
+        // polytope는 항상 닫혀 있으므로, 최근접면이 없다면 모든 면이 납작한 것이다.
+        let Some(nearest) = polytope.nearest() else {
+            return Err(GjkError::NumericalFailure(best.map(Box::new)));
+        };
+        let nearest_face = polytope.faces[nearest];
+        // 원점은 polytope 안에 있으므로 거리가 음수인 면은 뒤집힌 면이다.
+        if nearest_face.distance < -tolerance {
+            return Err(GjkError::NumericalFailure(best.map(Box::new)));
+        }
+        let collision_info = CollisionDetails {
+            normal: -nearest_face.normal,
+            penetration: nearest_face.distance - config.contact_margin,
+        };
+        best = Some(collision_info);
+        let support = support(&nearest_face.normal, diagnostics);
+        if !support.is_finite() {
+            return Err(GjkError::NumericalFailure(best.map(Box::new)));
+        }
+        let distance = nearest_face.normal.dot(support);
+        // 그런 simplex가 없다면 리턴
+        if (distance - nearest_face.distance).abs() < tolerance {
+            diagnostics.termination = Some(Termination::Converged);
+            return Ok(Some(collision_info));
+        }
+        // 오차 때문에 support point가 최근접면 안쪽에 있으면 확장할 수 없다.
+        if distance < nearest_face.distance {
+            diagnostics.termination = Some(Termination::Stalled);
+            return Ok(Some(collision_info));
+        }
+
+        // 2-2. support point에서 보이는 면을 모두 제거하고, 지평선의 변과 support point로 새 면을 만든다.
+        polytope.expand(nearest, support);
+    }
+
+    Err(GjkError::IterationLimit(best.map(Box::new)))
+}
+
+
+
+/// GJK가 사면체가 아닌 simplex(점, 선분, 삼각형)로 끝났을 때, support point를 더해 사면체로 만든다.  
+/// 지금의 점들이 이루는 점, 직선, 평면에 수직인 방향을 주축으로부터 만들고, 그 방향으로 tolerance보다 멀리 나가는 support point를 더한다.  
+/// 원점은 simplex 위에 있으므로 만들어진 사면체의 경계 위에 있다.  
+/// 어느 방향으로도 넓어지지 않으면(Minkowski 차가 납작하면) None을 반환한다.  
+fn blow_up_simplex(
+    simplex: &Simplex,
+    degenerate_tolerance: f32,
+    mut support: impl FnMut(&glam::Vec3A) -> glam::Vec3A,
+) -> Option<[glam::Vec3A; 4]> {
+    const AXES: [glam::Vec3A; 3] = [glam::Vec3A::X, glam::Vec3A::Y, glam::Vec3A::Z];
+
+    let mut vertices = simplex.vertices;
+    for count in simplex.count..4 {
+        let directions = match count {
+            // 점: 주축 방향
+            1 => AXES.to_vec(),
+            // 선분: 선분과 주축에 수직인 방향
+            2 => {
+                let edge = vertices[1] - vertices[0];
+                AXES.iter().filter_map(|axis| edge.cross(*axis).try_normalize()).collect()
+            }
+            // 삼각형: 삼각형의 법선벡터 방향
+            _ => (vertices[1] - vertices[0])
+                .cross(vertices[2] - vertices[0])
+                .try_normalize()
+                .into_iter()
+                .collect(),
+        };
+        // direction이 지금의 점들에 수직이므로, direction 방향으로 나아간 거리가 새 점과 점들 사이의 거리이다.
+        vertices[count] = directions.iter()
+            .flat_map(|direction| [*direction, -*direction])
+            .find_map(|direction| {
+                let w = support(&direction);
+                let tolerance = degenerate_tolerance * w.length().max(vertices[0].length());
+                ((w - vertices[0]).dot(direction) > tolerance).then_some(w)
+            })?;
+    }
+    Some(vertices)
+}
+
+/// EPA polytope의 면  
+/// 점은 바깥에서 볼 때 CCW 순서이고, i번째 변은 vertices[i]에서 vertices[(i + 1) % 3]으로 향한다.  
 #[derive(Debug, Clone, Copy)]
 struct Face {
     /// 평면을 이루는 세 점의 인덱스
     vertices: [usize; 3],
-    /// 평면의 법선벡터
+    /// i번째 변을 공유하는 면과, 그 면에서 그 변의 번호
+    adjacent: [(usize, usize); 3],
+    /// 평면의 법선벡터 (납작한 면은 ZERO)
     normal: glam::Vec3A,
-    /// 원점으로부터의 거리
+    /// 원점으로부터의 거리 (납작한 면은 무한대)
     distance: f32,
+    removed: bool,
 }
 
-impl Face {
-    fn vec(vertices: &[glam::Vec3A], indices: &[[usize; 3]]) -> Vec<Option<Self>> {
-        let mut faces = Vec::with_capacity(indices.len());
-        for idx in indices {
-            let v = [
-                vertices[idx[1]] - vertices[idx[0]],
-                vertices[idx[2]] - vertices[idx[0]],
-            ];
-            match v[0].cross(v[1]).try_normalize() {
-                Some(normal) => {
-                    let distance = normal.dot(vertices[idx[0]]);
-                    faces.push(Some(Face {
-                        vertices: *idx,
-                        normal,
-                        distance,
-                    }));
-                },
-                None => {
-                    faces.push(None);
-                }
-            };
+/// 면의 인접 정보를 가진 EPA의 polytope  
+/// 제거된 면은 heap에서 바로 빼지 않고, 꺼낼 때 건너뛴다.  
+struct Polytope {
+    vertices: Vec<glam::Vec3A>,
+    faces: Vec<Face>,
+    heap: BinaryHeap<HeapEntry>,
+    live_faces: usize,
+    degenerate_tolerance: f32,
+}
+
+impl Polytope {
+    /// 원점을 포함하는 사면체로 polytope를 만든다.  
+    /// 법선벡터를 구할 수 있는 면이 하나도 없으면 None을 반환한다.  
+    fn from_tetrahedron(mut vertices: [glam::Vec3A; 4], degenerate_tolerance: f32) -> Option<Self> {
+        // 바닥면(0, 1, 2)의 법선벡터가 나머지 한 점의 반대쪽을 향하도록 정렬한다.
+        let base_normal = (vertices[1] - vertices[0]).cross(vertices[2] - vertices[0]);
+        if base_normal.dot(vertices[3] - vertices[0]) > 0.0 {
+            vertices.swap(1, 2);
+        }
+
+        let mut polytope = Self {
+            vertices: Vec::from(vertices),
+            faces: Vec::new(),
+            heap: BinaryHeap::new(),
+            live_faces: 0,
+            degenerate_tolerance,
+        };
+        // CCW
+        let indices = [
+            [0, 1, 2],
+            [3, 1, 0],
+            [3, 2, 1],
+            [3, 0, 2],
+        ];
+        for face in indices {
+            polytope.add_face(face, [(0, 0); 3]);
+        }
+        // 같은 변을 반대 방향으로 가진 면끼리 잇는다.
+        for face in 0..4 {
+            for edge in 0..3 {
+                let [a, b] = polytope.edge(face, edge);
+                polytope.faces[face].adjacent[edge] = (0..4)
+                    .flat_map(|other| (0..3).map(move |other_edge| (other, other_edge)))
+                    .find(|&(other, other_edge)| polytope.edge(other, other_edge) == [b, a])?;
+            }
+        }
+
+        (!polytope.heap.is_empty()).then_some(polytope)
+    }
+
+    fn edge(&self, face: usize, edge: usize) -> [usize; 2] {
+        let vertices = self.faces[face].vertices;
+        [vertices[edge], vertices[(edge + 1) % 3]]
+    }
+
+    fn add_face(&mut self, vertices: [usize; 3], adjacent: [(usize, usize); 3]) -> usize {
+        let origin = self.vertices[vertices[0]];
+        let v = [
+            self.vertices[vertices[1]] - origin,
+            self.vertices[vertices[2]] - origin,
+        ];
+        let cross = v[0].cross(v[1]);
+        let normal = if is_parallel(cross, v[0], v[1], self.degenerate_tolerance) {
+            None
+        } else {
+            cross.try_normalize()
+        };
+
+        let index = self.faces.len();
+        let (normal, distance) = match normal {
+            Some(normal) => {
+                let distance = normal.dot(origin);
+                self.heap.push(HeapEntry { distance, face: index });
+                (normal, distance)
+            }
+            // 납작한 면은 최근접면이 되지 않고, 어느 점에서도 보이지 않는다.
+            None => (glam::Vec3A::ZERO, f32::INFINITY),
+        };
+        self.faces.push(Face {
+            vertices,
+            adjacent,
+            normal,
+            distance,
+            removed: false,
+        });
+        self.live_faces += 1;
+        index
+    }
+
+    fn remove_face(&mut self, face: usize) {
+        self.faces[face].removed = true;
+        self.live_faces -= 1;
+    }
+
+    /// 원점에서 가장 가까운 면
+    fn nearest(&mut self) -> Option<usize> {
+        while let Some(entry) = self.heap.peek() {
+            if !self.faces[entry.face].removed {
+                return Some(entry.face);
+            }
+            self.heap.pop();
+        }
+        None
+    }
+
+    fn is_visible(&self, face: usize, point: &glam::Vec3A) -> bool {
+        let face = &self.faces[face];
+        face.normal.dot(point - self.vertices[face.vertices[0]]) > 0.0
+    }
+
+    /// point에서 보이는 면을 모두 제거하고, 지평선의 변과 point로 새 면을 만든다.  
+    /// visible_face는 point에서 보이는 면이어야 한다.  
+    fn expand(&mut self, visible_face: usize, point: glam::Vec3A) {
+        self.vertices.push(point);
+        let point_index = self.vertices.len() - 1;
+
+        // 1. visible_face에서 시작해 인접한 면을 DFS로 따라가며, 보이는 면을 제거하고 지평선의 변을 모은다.
+        // 재귀 호출과 같은 순서로 방문하므로, 지평선의 변은 한 바퀴를 이어서 돈다.
+        self.remove_face(visible_face);
+        let mut horizon = Vec::new();
+        let mut stack = vec![(visible_face, 2), (visible_face, 1), (visible_face, 0)];
+        while let Some((face, edge)) = stack.pop() {
+            let (neighbor, neighbor_edge) = self.faces[face].adjacent[edge];
+            if self.faces[neighbor].removed {
+                continue;
+            }
+            if self.is_visible(neighbor, &point) {
+                self.remove_face(neighbor);
+                stack.push((neighbor, (neighbor_edge + 2) % 3));
+                stack.push((neighbor, (neighbor_edge + 1) % 3));
+            } else {
+                horizon.push((self.edge(face, edge), neighbor, neighbor_edge));
+            }
+        }
+
+        // 2. 지평선의 변마다 새 면을 만들고, 지평선 너머의 면과 이웃한 새 면끼리 잇는다.
+        let first = self.faces.len();
+        let count = horizon.len();
+        for (k, &([a, b], neighbor, neighbor_edge)) in horizon.iter().enumerate() {
+            let next = first + (k + 1) % count;
+            let previous = first + (k + count - 1) % count;
+            let face = self.add_face([a, b, point_index], [(neighbor, neighbor_edge), (next, 2), (previous, 1)]);
+            self.faces[neighbor].adjacent[neighbor_edge] = (face, 0);
         }
-        faces
     }
 }
 
-/////////////////////// for BinaryHeap ///////////////////////
-// Min Heap으로 사용하기 위해 -distance로 비교한다.
+/// 거리가 가까운 면이 먼저 나오는 BinaryHeap의 원소
+#[derive(Debug, Clone, Copy)]
+struct HeapEntry {
+    distance: f32,
+    face: usize,
+}
 
-impl PartialEq for Face {
+impl PartialEq for HeapEntry {
     fn eq(&self, other: &Self) -> bool {
-        self.distance == other.distance
+        self.cmp(other) == std::cmp::Ordering::Equal
     }
 }
 
-impl Eq for Face {}
+impl Eq for HeapEntry {}
 
-impl PartialOrd for Face {
+impl PartialOrd for HeapEntry {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        (-self.distance).partial_cmp(&-other.distance)
+        Some(self.cmp(other))
     }
 }
 
-impl Ord for Face {
+impl Ord for HeapEntry {
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        (-self.distance).partial_cmp(&-other.distance).unwrap()
+        // Min Heap으로 사용하기 위해 반대로 비교한다.
+        other.distance.total_cmp(&self.distance)
     }
 }
 
-//////////////////////////////////////////////////////////////
+/// cross = a x b일 때, a와 b가 이루는 각의 sin 값이 tolerance 이하인지 확인한다.  
+/// 길이에 대한 비율로 비교하므로 도형의 크기와 관계없이 같은 기준이 적용된다.  
+fn is_parallel(cross: glam::Vec3A, a: glam::Vec3A, b: glam::Vec3A, tolerance: f32) -> bool {
+    cross.length_squared() <= (tolerance * a.length() * b.length()).powi(2)
+}
 
 
-/// 바닥면을 이루는 세 점과 나머지 한 점 순으로 저장하고,  
-/// 바닥면의 법선벡터는 항상 원점 반대방향이어야 한다. (CCW)  
-#[derive(Debug)]
+/// GJK가 원점에 가장 가까운 점을 찾을 때 쓰는 점, 선분, 삼각형 또는 사면체  
+/// 각 점은 Minkowski 차의 점이며, 그 점을 만든 두 도형의 support point를 함께 저장한다.  
+#[derive(Debug, Clone, Default)]
 pub struct Simplex {
     vertices: [glam::Vec3A; 4],
+    /// vertices[i]를 만든 self 위의 점
+    points_a: [glam::Vec3A; 4],
+    /// vertices[i]를 만든 other 위의 점
+    points_b: [glam::Vec3A; 4],
     /// 유효한 점의 개수
     count: usize,
 }
 
 impl Simplex {
-    /// Simplex 안쪽에 원점이 있는지 확인하고,  
-    /// 그렇지 않다면 원점과 가장 가까운 면을 구한다.  
-    /// (바닥면은 검사하지 않는다.)  
-    fn get_nearest_if_not_contains_origin(&self) -> Option<Face> {
-        // CCW
-        let indices = [
-            [3, 1, 0],
-            [3, 2, 1],
-            [3, 0, 2],
-        ];
-        let faces = Face::vec(&self.vertices, &indices);
-
-        let mut min_distance = f32::MAX;
-        let mut nearest_face = None;
-
-        faces.into_iter()
-            .filter_map(|f| f)
-            .for_each(|face| {
-                let distance = -face.distance;
-                if 0.0 < distance && distance < min_distance {
-                    min_distance = distance;
-                    nearest_face = Some(face);
-                }
-            });
+    /// 유효한 점들
+    pub fn vertices(&self) -> &[glam::Vec3A] {
+        &self.vertices[..self.count]
+    }
+
+    /// 0: 점, 1: 선분, 2: 삼각형, 3: 사면체
+    pub fn dimension(&self) -> usize {
+        self.count.saturating_sub(1)
+    }
+
+    /// simplex 위에서 원점과 가장 가까운 점을 두 도형 위의 점으로 나타낸다.  
+    /// GJK가 떨어져 있다고 판단한 simplex라면 두 도형 사이의 최단 거리가 된다.  
+    pub fn closest_points(&self) -> ClosestPoints {
+        let lambda = closest_to_origin(self.vertices());
+        let mut point_a = glam::Vec3A::ZERO;
+        let mut point_b = glam::Vec3A::ZERO;
+        for (i, weight) in lambda[..self.count].iter().enumerate() {
+            point_a += self.points_a[i] * *weight;
+            point_b += self.points_b[i] * *weight;
+        }
+        let difference = point_a - point_b;
+        ClosestPoints {
+            distance: difference.length(),
+            point_a,
+            point_b,
+            normal: difference.normalize_or_zero(),
+        }
+    }
+
+    fn push(&mut self, point_a: glam::Vec3A, point_b: glam::Vec3A) {
+        self.vertices[self.count] = point_a - point_b;
+        self.points_a[self.count] = point_a;
+        self.points_b[self.count] = point_b;
+        self.count += 1;
+    }
+
+    /// 원점과 가장 가까운 점을 구하고, 그 점을 만드는 데 쓰이지 않는 점(가중치 0)을 뺀다.
+    fn reduce(&mut self) -> glam::Vec3A {
+        let lambda = closest_to_origin(self.vertices());
+        let mut closest = glam::Vec3A::ZERO;
+        let mut reduced = 0;
+        for (i, weight) in lambda[..self.count].iter().enumerate() {
+            if *weight > 0.0 {
+                closest += self.vertices[i] * *weight;
+                self.vertices[reduced] = self.vertices[i];
+                self.points_a[reduced] = self.points_a[i];
+                self.points_b[reduced] = self.points_b[i];
+                reduced += 1;
+            }
+        }
+        self.count = reduced;
+        closest
+    }
+
+    fn check_finite(&self) -> Result<(), GjkError<Simplex>> {
+        if self.vertices().iter().all(|vertex| vertex.is_finite()) {
+            Ok(())
+        } else {
+            Err(GjkError::NumericalFailure(Some(Box::new(self.clone()))))
+        }
+    }
+}
+
+/////////////////////// signed volume ///////////////////////
+// Montanari et al. 2017, "Improving the GJK algorithm for faster and more reliable distance queries between convex objects"
+
+fn same_sign(a: f32, b: f32) -> bool {
+    (a > 0.0) == (b > 0.0)
+}
+
+/// simplex(점, 선분, 삼각형, 사면체) 위에서 원점과 가장 가까운 점의 무게중심 좌표를 구한다.  
+/// 반환값의 i번째 원소는 vertices[i]의 가중치이며, 가중치가 0인 점은 simplex에서 제거해도 된다.  
+pub(crate) fn closest_to_origin(vertices: &[glam::Vec3A]) -> [f32; 4] {
+    match vertices.len() {
+        1 => [1.0, 0.0, 0.0, 0.0],
+        2 => {
+            let [a, b] = signed_volume_1d(vertices[0], vertices[1]);
+            [a, b, 0.0, 0.0]
+        }
+        3 => {
+            let [a, b, c] = signed_volume_2d(vertices[0], vertices[1], vertices[2]);
+            [a, b, c, 0.0]
+        }
+        4 => signed_volume_3d(vertices[0], vertices[1], vertices[2], vertices[3]),
+        _ => [0.0; 4],
+    }
+}
 
-        nearest_face
+fn signed_volume_1d(s0: glam::Vec3A, s1: glam::Vec3A) -> [f32; 2] {
+    let t = s1 - s0;
+    let length_sq = t.length_squared();
+    if length_sq <= f32::EPSILON * s0.length_squared().max(s1.length_squared()) {
+        // 두 점이 같은 경우
+        return if s0.length_squared() <= s1.length_squared() { [1.0, 0.0] } else { [0.0, 1.0] };
     }
-}
\ No newline at end of file
+
+    // 원점을 직선 위에 투영한 점의 매개변수
+    let u = (-s0.dot(t) / length_sq).clamp(0.0, 1.0);
+    [1.0 - u, u]
+}
+
+fn signed_volume_2d(s0: glam::Vec3A, s1: glam::Vec3A, s2: glam::Vec3A) -> [f32; 3] {
+    let normal = (s1 - s0).cross(s2 - s0);
+
+    // 넓이가 가장 크게 투영되는 평면을 고른다.
+    let abs = normal.abs();
+    let (i, j, k) = if abs.x >= abs.y && abs.x >= abs.z {
+        (0, 1, 2)
+    } else if abs.y >= abs.z {
+        (1, 2, 0)
+    } else {
+        (2, 0, 1)
+    };
+    let mu_max = normal[i];
+
+    if mu_max.abs() > f32::EPSILON * (s1 - s0).length_squared().max((s2 - s0).length_squared()) {
+        // 원점을 평면 위에 투영한 점
+        let p = normal * (s0.dot(normal) / normal.length_squared());
+        let area = |a: glam::Vec3A, b: glam::Vec3A, c: glam::Vec3A| {
+            (b[j] - a[j]) * (c[k] - a[k]) - (b[k] - a[k]) * (c[j] - a[j])
+        };
+        let c = [area(p, s1, s2), area(s0, p, s2), area(s0, s1, p)];
+
+        if c.iter().all(|&c| same_sign(mu_max, c)) {
+            // 투영한 점이 삼각형 안에 있음
+            return c.map(|c| c / mu_max);
+        }
+    }
+
+    // 삼각형 밖(또는 삼각형이 퇴화)인 경우, 가장 가까운 변을 찾는다.
+    let points = [s0, s1, s2];
+    let mut best = [0.0; 3];
+    let mut min_distance = f32::MAX;
+    for m in 0..3 {
+        let (a, b) = ((m + 1) % 3, (m + 2) % 3);
+        let [la, lb] = signed_volume_1d(points[a], points[b]);
+        let distance = (points[a] * la + points[b] * lb).length_squared();
+        if distance < min_distance {
+            min_distance = distance;
+            best = [0.0; 3];
+            best[a] = la;
+            best[b] = lb;
+        }
+    }
+    best
+}
+
+fn signed_volume_3d(s0: glam::Vec3A, s1: glam::Vec3A, s2: glam::Vec3A, s3: glam::Vec3A) -> [f32; 4] {
+    let volume = |a: glam::Vec3A, b: glam::Vec3A, c: glam::Vec3A, d: glam::Vec3A| {
+        (b - a).dot((c - a).cross(d - a))
+    };
+    let o = glam::Vec3A::ZERO;
+    let c = [
+        volume(o, s1, s2, s3),
+        volume(s0, o, s2, s3),
+        volume(s0, s1, o, s3),
+        volume(s0, s1, s2, o),
+    ];
+    let det = c[0] + c[1] + c[2] + c[3];
+
+    let scale = (s1 - s0).length().max((s2 - s0).length()).max((s3 - s0).length());
+    let degenerate = det.abs() <= f32::EPSILON * scale.powi(3);
+    if !degenerate && c.iter().all(|&c| same_sign(det, c)) {
+        // 원점이 사면체 안에 있음
+        return c.map(|c| c / det);
+    }
+
+    // 원점과 같은 쪽에 있지 않은 면들 중 가장 가까운 면을 찾는다.
+    let points = [s0, s1, s2, s3];
+    let mut best = [0.0; 4];
+    let mut min_distance = f32::MAX;
+    for (m, &c_m) in c.iter().enumerate() {
+        if !degenerate && same_sign(det, c_m) {
+            continue;
+        }
+        let face = [(m + 1) % 4, (m + 2) % 4, (m + 3) % 4];
+        let lambda = signed_volume_2d(points[face[0]], points[face[1]], points[face[2]]);
+        let distance = (points[face[0]] * lambda[0] + points[face[1]] * lambda[1] + points[face[2]] * lambda[2]).length_squared();
+        if distance < min_distance {
+            min_distance = distance;
+            best = [0.0; 4];
+            for (n, &f) in face.iter().enumerate() {
+                best[f] = lambda[n];
+            }
+        }
+    }
+    best
+}
+
+//////////////////////////////////////////////////////////////
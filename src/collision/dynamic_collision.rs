@@ -1,51 +1,223 @@
-use crate::{BoundingBox, Sphere};
-use super::{CollisionDetails, ConvexHull};
+use crate::{BoundingBox, Capsule, Sphere};
+use super::{
+    point_query::closest_point_outside,
+    static_collision::StaticCollision,
+    CollisionDetails, ConvexHull, GjkConfig, MinkowskiDifference,
+};
 
 
-/// 움직이는 물체(self)와 움직이지 않는 물체(other)의 충돌 검사
+/// sweep(conservative advancement)의 설정
+#[derive(Debug, Clone, Copy)]
+pub struct SweepConfig {
+    /// 전진을 반복하는 최대 횟수  
+    /// 이 안에 수렴하지 않으면 만났는지 알 수 없으므로 None을 반환한다.  
+    pub max_iterations: usize,
+    /// 남은 거리가 이 값 이하가 되면 만난 것으로 본다.
+    pub tolerance: f32,
+}
+
+impl Default for SweepConfig {
+    fn default() -> Self {
+        Self {
+            max_iterations: 32,
+            tolerance: 1.0e-4,
+        }
+    }
+}
+
+/// 움직이는 물체(self)와 움직이지 않는 물체(other)의 충돌 검사  
+/// velocity는 한 번의 검사 동안 self가 이동하는 변위이다.  
+///  
+/// 구현체는 time_of_impact만 구현하면 되고, 나머지는 그 결과로 계산된다.  
+/// 예전처럼 check_dynamic_collision과 check_dynamic_collision_details를 직접 구현하던 코드는  
+/// 반드시 time_of_impact를 구현하도록 바꿔야 한다. (time_of_impact에는 기본 구현이 없다.)  
 pub trait DynamicCollision<T: ConvexHull> {
-    fn check_dynamic_collision(&self, velocity: &glam::Vec3A, other: &T) -> bool;
-    fn check_dynamic_collision_details(&self, velocity: &glam::Vec3A, other: &T) -> Option<CollisionDetails>;
+    fn check_dynamic_collision(&self, velocity: &glam::Vec3A, other: &T) -> bool {
+        self.time_of_impact(velocity, other).is_some()
+    }
+
+    /// 처음 만나는 순간의 충돌 정보  
+    /// penetration은 velocity만큼 모두 이동했을 때 normal 방향으로 파고드는 깊이이다.  
+    fn check_dynamic_collision_details(&self, velocity: &glam::Vec3A, other: &T) -> Option<CollisionDetails> {
+        self.time_of_impact(velocity, other).map(|(_, details)| details)
+    }
+
+    /// 처음 만나는 시점(0 ~ 1)과 그때의 충돌 정보  
+    /// 처음부터 겹쳐 있으면 시점은 0이고, 충돌 정보는 정적 충돌 검사의 결과와 같다.  
+    fn time_of_impact(&self, velocity: &glam::Vec3A, other: &T) -> Option<(f32, CollisionDetails)>;
+
+    /// 반복 횟수와 허용 오차를 지정하는 time_of_impact  
+    /// 반복해서 근사하지 않는 구현(구와 구 등)은 config를 무시한다.  
+    fn time_of_impact_with_config(
+        &self,
+        velocity: &glam::Vec3A,
+        other: &T,
+        _config: &SweepConfig,
+    ) -> Option<(f32, CollisionDetails)> {
+        self.time_of_impact(velocity, other)
+    }
 }
 
 
 impl DynamicCollision<BoundingBox> for BoundingBox {
-    fn check_dynamic_collision(&self, velocity: &glam::Vec3A, other: &BoundingBox) -> bool {
-        todo!()
+    fn time_of_impact(&self, velocity: &glam::Vec3A, other: &BoundingBox) -> Option<(f32, CollisionDetails)> {
+        sweep(self, velocity, other, &SweepConfig::default())
     }
 
-    fn check_dynamic_collision_details(&self, velocity: &glam::Vec3A, other: &BoundingBox) -> Option<CollisionDetails> {
-        todo!()
+    fn time_of_impact_with_config(
+        &self,
+        velocity: &glam::Vec3A,
+        other: &BoundingBox,
+        config: &SweepConfig,
+    ) -> Option<(f32, CollisionDetails)> {
+        sweep(self, velocity, other, config)
     }
 }
 
 impl DynamicCollision<Sphere> for BoundingBox {
-    fn check_dynamic_collision(&self, velocity: &glam::Vec3A, other: &Sphere) -> bool {
-        todo!()
+    fn time_of_impact(&self, velocity: &glam::Vec3A, other: &Sphere) -> Option<(f32, CollisionDetails)> {
+        sweep(self, velocity, other, &SweepConfig::default())
     }
 
-    fn check_dynamic_collision_details(&self, velocity: &glam::Vec3A, other: &Sphere) -> Option<CollisionDetails> {
-        todo!()
+    fn time_of_impact_with_config(
+        &self,
+        velocity: &glam::Vec3A,
+        other: &Sphere,
+        config: &SweepConfig,
+    ) -> Option<(f32, CollisionDetails)> {
+        sweep(self, velocity, other, config)
     }
 }
 
 
 impl DynamicCollision<BoundingBox> for Sphere {
-    fn check_dynamic_collision(&self, velocity: &glam::Vec3A, other: &BoundingBox) -> bool {
-        todo!()
+    fn time_of_impact(&self, velocity: &glam::Vec3A, other: &BoundingBox) -> Option<(f32, CollisionDetails)> {
+        sweep(self, velocity, other, &SweepConfig::default())
+    }
+
+    fn time_of_impact_with_config(
+        &self,
+        velocity: &glam::Vec3A,
+        other: &BoundingBox,
+        config: &SweepConfig,
+    ) -> Option<(f32, CollisionDetails)> {
+        sweep(self, velocity, other, config)
+    }
+}
+
+impl DynamicCollision<BoundingBox> for Capsule {
+    fn time_of_impact(&self, velocity: &glam::Vec3A, other: &BoundingBox) -> Option<(f32, CollisionDetails)> {
+        sweep(self, velocity, other, &SweepConfig::default())
     }
 
-    fn check_dynamic_collision_details(&self, velocity: &glam::Vec3A, other: &BoundingBox) -> Option<CollisionDetails> {
-        todo!()
+    fn time_of_impact_with_config(
+        &self,
+        velocity: &glam::Vec3A,
+        other: &BoundingBox,
+        config: &SweepConfig,
+    ) -> Option<(f32, CollisionDetails)> {
+        sweep(self, velocity, other, config)
     }
 }
 
+impl DynamicCollision<Sphere> for Capsule {
+    fn time_of_impact(&self, velocity: &glam::Vec3A, other: &Sphere) -> Option<(f32, CollisionDetails)> {
+        sweep(self, velocity, other, &SweepConfig::default())
+    }
+
+    fn time_of_impact_with_config(
+        &self,
+        velocity: &glam::Vec3A,
+        other: &Sphere,
+        config: &SweepConfig,
+    ) -> Option<(f32, CollisionDetails)> {
+        sweep(self, velocity, other, config)
+    }
+}
+
+
 impl DynamicCollision<Sphere> for Sphere {
-    fn check_dynamic_collision(&self, velocity: &glam::Vec3A, other: &Sphere) -> bool {
-        todo!()
+    fn time_of_impact(&self, velocity: &glam::Vec3A, other: &Sphere) -> Option<(f32, CollisionDetails)> {
+        if let Some(details) = self.check_static_collision_details(other) {
+            return Some((0.0, details.with_positive_penetration()));
+        }
+
+        // |offset + velocity * t| = radius 의 가장 작은 해
+        let offset = glam::Vec3A::from(self.center - other.center);
+        let radius = self.radius + other.radius;
+        let a = velocity.length_squared();
+        let b = offset.dot(*velocity);
+        let c = offset.length_squared() - radius.powi(2);
+        if a <= f32::EPSILON || b >= 0.0 {
+            return None;    // 움직이지 않거나 멀어지는 중
+        }
+        let discriminant = b * b - a * c;
+        if discriminant < 0.0 {
+            return None;
+        }
+        let time = (-b - discriminant.sqrt()) / a;
+        if time > 1.0 {
+            return None;
+        }
+
+        let normal = (offset + velocity * time).normalize_or_zero();
+        Some((time, swept_details(velocity, time, normal)))
+    }
+}
+
+
+/// Conservative advancement로 a가 velocity만큼 움직이는 동안 b와 처음 만나는 시점을 구한다.  
+/// a를 t * velocity만큼 옮기는 것은 Minkowski 차 위의 점 -t * velocity가 원점으로 오는 것과 같으므로,  
+/// 그 점과 Minkowski 차 사이의 거리만큼씩 안전하게 전진한다.  
+fn sweep<A, B>(a: &A, velocity: &glam::Vec3A, b: &B, config: &SweepConfig) -> Option<(f32, CollisionDetails)>
+where
+    A: ConvexHull + StaticCollision<B>,
+    B: ConvexHull,
+{
+    let difference = MinkowskiDifference { a, b };
+    let mut time = 0.0;
+    let mut normal = glam::Vec3A::ZERO;
+
+    for _ in 0..config.max_iterations {
+        let point = -*velocity * time;
+        // closest -> point는 a에서 b를 향하는 방향
+        let separation = match closest_point_outside(&difference, &point, &GjkConfig::default()) {
+            Some(closest) => point - closest,
+            None if time == 0.0 => {
+                return a.check_static_collision_details(b)
+                    .map(|details| (0.0, details.with_positive_penetration()));
+            }
+            None => glam::Vec3A::ZERO,  // 허용 오차 안쪽으로 넘어간 경우
+        };
+        let distance = separation.length();
+        if distance <= config.tolerance {
+            // 거리가 너무 가까우면 방향이 부정확하므로 직전의 normal을 사용한다.
+            if normal == glam::Vec3A::ZERO {
+                normal = -separation.normalize_or_zero();
+            }
+            return Some((time, swept_details(velocity, time, normal)));
+        }
+        normal = -separation / distance;
+
+        // normal 방향으로 가까워지는 속도
+        let approach = -velocity.dot(normal);
+        if approach <= 0.0 {
+            return None;
+        }
+        time += distance / approach;
+        if time > 1.0 {
+            return None;
+        }
     }
 
-    fn check_dynamic_collision_details(&self, velocity: &glam::Vec3A, other: &Sphere) -> Option<CollisionDetails> {
-        todo!()
+    // 스치듯 지나가는 경우처럼 만나는지 확정하지 못했으므로 충돌로 보고하지 않는다.
+    None
+}
+
+/// 처음 만난 뒤 남은 이동으로 파고드는 깊이를 penetration으로 한다.
+fn swept_details(velocity: &glam::Vec3A, time: f32, normal: glam::Vec3A) -> CollisionDetails {
+    CollisionDetails {
+        normal,
+        penetration: (1.0 - time) * (-velocity.dot(normal)).max(0.0),
     }
-}
\ No newline at end of file
+}
@@ -1,12 +1,31 @@
 mod convex_hull;
 pub mod dynamic_collision;
+pub mod point_query;
+pub mod ray_cast;
+pub mod signed_distance;
 pub mod static_collision;
 
 pub use convex_hull::*;
 
 
+#[derive(Debug, Clone, Copy)]
 pub struct CollisionDetails {
     pub normal: glam::Vec3A,
     pub penetration: f32,
     // pub contact_point: Vec<glam::Vec3A>,
 }
+
+impl CollisionDetails {
+    /// penetration이 음수이면 normal과 함께 뒤집어 penetration이 0 이상이 되도록 한다.  
+    /// normal * penetration은 바뀌지 않는다.  
+    pub fn with_positive_penetration(self) -> Self {
+        if self.penetration < 0.0 {
+            Self {
+                normal: -self.normal,
+                penetration: -self.penetration,
+            }
+        } else {
+            self
+        }
+    }
+}
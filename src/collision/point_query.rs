@@ -0,0 +1,191 @@
+use crate::{BoundingBox, Capsule, PreparedBox, Sphere, Triangle, VertexBox};
+use super::{ConvexHull, GjkConfig, GjkError, Rounded};
+
+
+/// 점을 도형의 표면에 투영한 결과
+#[derive(Debug, Clone, Copy)]
+pub struct PointProjection {
+    /// 도형의 표면 위에서 가장 가까운 점
+    pub point: glam::Vec3A,
+    /// 투영 전의 점이 도형 안에 있었는지 여부
+    pub is_inside: bool,
+}
+
+/// 점과 도형 사이의 질의  
+/// 기본 구현은 GJK(도형 밖)와 EPA(도형 안)를 이용하므로, 해석적인 방법이 있는 도형은 project_point_with_config를 직접 구현한다.  
+pub trait PointQuery: ConvexHull {
+    /// 점이 도형 안(경계 포함)에 있는지 확인한다.
+    fn contains_point(&self, point: &glam::Vec3A) -> bool {
+        self.project_point(point).is_inside
+    }
+
+    /// 기본 설정으로 project_point_with_config를 호출한다.
+    fn project_point(&self, point: &glam::Vec3A) -> PointProjection {
+        self.project_point_with_config(point, &GjkConfig::default())
+    }
+
+    /// 도형의 표면 위에서 point와 가장 가까운 점을 구한다.  
+    /// point가 도형 안에 있는 경우에도 표면 위의 점을 반환한다.  
+    /// config는 GJK/EPA를 쓰는 기본 구현에만 쓰이며, 해석적으로 구하는 도형은 무시한다.  
+    fn project_point_with_config(&self, point: &glam::Vec3A, config: &GjkConfig) -> PointProjection {
+        if let Some(closest) = closest_point_outside(self, point, config) {
+            return PointProjection {
+                point: closest,
+                is_inside: false,
+            };
+        }
+
+        // 도형 안에 있는 경우, 크기가 0인 구와의 침투 깊이로 표면까지의 최단 경로를 구한다.
+        let probe = point_probe(point);
+        // 수렴하지 않았으면 마지막 추정값을 쓴다.
+        let details = self.gjk_epa_with_config(&probe, config).unwrap_or_else(GjkError::into_estimate);
+        let surface = match details {
+            Some(details) => point - details.normal * details.penetration,
+            None => *point,
+        };
+        PointProjection {
+            point: surface,
+            is_inside: true,
+        }
+    }
+
+    /// 점과 도형 사이의 거리  
+    /// 점이 도형 안에 있으면 0을 반환한다.  
+    fn distance_to_point(&self, point: &glam::Vec3A) -> f32 {
+        let projection = self.project_point(point);
+        if projection.is_inside {
+            0.0
+        } else {
+            (projection.point - point).length()
+        }
+    }
+}
+
+/// 점을 GJK에 넣기 위한 반지름 0인 구
+fn point_probe(point: &glam::Vec3A) -> Sphere {
+    Sphere {
+        center: glam::Vec3::from(*point),
+        radius: 0.0,
+    }
+}
+
+/// GJK 거리로 도형 위에서 point와 가장 가까운 점을 구한다.  
+/// point가 도형 안이나 경계 위에 있으면 None을 반환한다.  
+/// 수렴하지 않은 경우 마지막 추정값을 쓰고, 추정값도 없으면 None을 반환한다.  
+pub(crate) fn closest_point_outside<T: ConvexHull + ?Sized>(
+    hull: &T,
+    point: &glam::Vec3A,
+    config: &GjkConfig,
+) -> Option<glam::Vec3A> {
+    let closest = hull.gjk_distance(&point_probe(point), config)
+        .unwrap_or_else(GjkError::into_estimate)?;
+    Some(closest.point_a)
+}
+
+
+impl PointQuery for BoundingBox {
+    fn contains_point(&self, point: &glam::Vec3A) -> bool {
+        BoundingBox::contains_point(self, point)
+    }
+
+    fn project_point_with_config(&self, point: &glam::Vec3A, _config: &GjkConfig) -> PointProjection {
+        let local = self.world_to_local(point);
+        let extents = glam::Vec3A::from(self.extents());
+        let mut clamped = local.clamp(-extents, extents);
+        let is_inside = clamped == local;
+
+        if is_inside {
+            // 가장 가까운 면으로 밀어낸다.
+            let gap = extents - local.abs();
+            let axis = if gap.x <= gap.y && gap.x <= gap.z {
+                0
+            } else if gap.y <= gap.z {
+                1
+            } else {
+                2
+            };
+            clamped[axis] = if local[axis] < 0.0 { -extents[axis] } else { extents[axis] };
+        }
+
+        PointProjection {
+            point: self.local_to_world(&clamped),
+            is_inside,
+        }
+    }
+
+    fn distance_to_point(&self, point: &glam::Vec3A) -> f32 {
+        let local = self.world_to_local(point);
+        let outside = local.abs() - glam::Vec3A::from(self.extents());
+        outside.max(glam::Vec3A::ZERO).length()
+    }
+}
+
+impl PointQuery for Sphere {
+    fn contains_point(&self, point: &glam::Vec3A) -> bool {
+        self.check_point_collision(point)
+    }
+
+    fn project_point_with_config(&self, point: &glam::Vec3A, _config: &GjkConfig) -> PointProjection {
+        let center = glam::Vec3A::from(self.center);
+        let to_point = point - center;
+        // 중심과 같은 점은 임의의 방향으로 투영한다.
+        let direction = to_point.try_normalize().unwrap_or(glam::Vec3A::X);
+        PointProjection {
+            point: center + direction * self.radius,
+            is_inside: to_point.length_squared() <= self.radius.powi(2),
+        }
+    }
+
+    fn distance_to_point(&self, point: &glam::Vec3A) -> f32 {
+        let center = glam::Vec3A::from(self.center);
+        ((point - center).length() - self.radius).max(0.0)
+    }
+}
+
+impl PointQuery for VertexBox {}
+
+impl PointQuery for PreparedBox {
+    fn contains_point(&self, point: &glam::Vec3A) -> bool {
+        self.bounding_box().contains_point(point)
+    }
+
+    fn project_point_with_config(&self, point: &glam::Vec3A, config: &GjkConfig) -> PointProjection {
+        self.bounding_box().project_point_with_config(point, config)
+    }
+
+    fn distance_to_point(&self, point: &glam::Vec3A) -> f32 {
+        PointQuery::distance_to_point(self.bounding_box(), point)
+    }
+}
+
+impl PointQuery for Capsule {
+    fn contains_point(&self, point: &glam::Vec3A) -> bool {
+        self.check_point_collision(point)
+    }
+
+    /// 선분 위의 가장 가까운 점을 중심으로 하는 구에 투영한다.
+    fn project_point_with_config(&self, point: &glam::Vec3A, config: &GjkConfig) -> PointProjection {
+        self.sphere_at(point).project_point_with_config(point, config)
+    }
+
+    fn distance_to_point(&self, point: &glam::Vec3A) -> f32 {
+        self.sphere_at(point).distance_to_point(point)
+    }
+}
+
+impl<T: ConvexHull> PointQuery for Rounded<T> {}
+
+/// 삼각형은 두께가 없으므로, 삼각형 위의 점만 안에 있는 것으로 본다.
+impl PointQuery for Triangle {
+    fn project_point_with_config(&self, point: &glam::Vec3A, _config: &GjkConfig) -> PointProjection {
+        let closest = self.closest_point(point);
+        PointProjection {
+            point: closest,
+            is_inside: closest.distance_squared(*point) <= f32::EPSILON.powi(2),
+        }
+    }
+
+    fn distance_to_point(&self, point: &glam::Vec3A) -> f32 {
+        self.closest_point(point).distance(*point)
+    }
+}
@@ -0,0 +1,148 @@
+use crate::{BoundingBox, Ray, RayHit, Sphere, Triangle};
+
+
+/// Ray와 도형의 충돌 검사
+pub trait RayCast {
+    /// origin으로부터 max_distance 이내에서 ray가 처음으로 도형과 만나는 지점을 구한다.  
+    /// ray가 도형 안에서 시작하면 distance가 0인 결과를 반환한다.  
+    fn cast_ray(&self, ray: &Ray, max_distance: f32) -> Option<RayHit>;
+}
+
+
+impl RayCast for BoundingBox {
+    fn cast_ray(&self, ray: &Ray, max_distance: f32) -> Option<RayHit> {
+        // ray를 박스의 로컬 공간으로 변환하여 slab 검사를 한다.
+        let origin = self.world_to_local(&ray.origin);
+        let direction = match self.rotation() {
+            Some(rotation) => glam::Vec3A::from(rotation.transpose() * glam::Vec3::from(ray.direction())),
+            None => ray.direction(),
+        };
+        let extents = glam::Vec3A::from(self.extents());
+
+        let mut t_min = 0.0_f32;
+        let mut t_max = max_distance;
+        let mut hit_axis = None;
+
+        for i in 0..3 {
+            if direction[i].abs() < f32::EPSILON {
+                // 축과 평행한 경우 slab 밖에서 시작하면 만나지 않는다.
+                if origin[i].abs() > extents[i] {
+                    return None;
+                }
+                continue;
+            }
+
+            let inv = 1.0 / direction[i];
+            let mut t1 = (-extents[i] - origin[i]) * inv;
+            let mut t2 = (extents[i] - origin[i]) * inv;
+            if t1 > t2 {
+                std::mem::swap(&mut t1, &mut t2);
+            }
+
+            if t1 > t_min {
+                t_min = t1;
+                hit_axis = Some(i);
+            }
+            t_max = t_max.min(t2);
+            if t_min > t_max {
+                return None;
+            }
+        }
+
+        let normal = match hit_axis {
+            Some(axis) => {
+                let mut local_normal = glam::Vec3A::ZERO;
+                local_normal[axis] = -direction[axis].signum();
+                match self.rotation() {
+                    Some(rotation) => glam::Vec3A::from(rotation * glam::Vec3::from(local_normal)),
+                    None => local_normal,
+                }
+            }
+            // ray가 박스 안에서 시작함
+            None => -ray.direction(),
+        };
+
+        Some(RayHit {
+            distance: t_min,
+            point: ray.point_at(t_min),
+            normal,
+        })
+    }
+}
+
+impl RayCast for Sphere {
+    fn cast_ray(&self, ray: &Ray, max_distance: f32) -> Option<RayHit> {
+        let to_origin = ray.origin - glam::Vec3A::from(self.center);
+        let c = to_origin.length_squared() - self.radius.powi(2);
+        if c <= 0.0 {
+            // ray가 구 안에서 시작함
+            return Some(RayHit {
+                distance: 0.0,
+                point: ray.origin,
+                normal: -ray.direction(),
+            });
+        }
+
+        // |o + td - center|^2 = r^2 의 근 (d는 단위벡터)
+        let b = to_origin.dot(ray.direction());
+        if b > 0.0 {
+            return None;    // 구에서 멀어지는 방향
+        }
+        let discriminant = b * b - c;
+        if discriminant < 0.0 {
+            return None;
+        }
+
+        let distance = -b - discriminant.sqrt();
+        if distance > max_distance {
+            return None;
+        }
+
+        let point = ray.point_at(distance);
+        Some(RayHit {
+            distance,
+            point,
+            normal: (point - glam::Vec3A::from(self.center)).normalize_or_zero(),
+        })
+    }
+}
+
+impl RayCast for Triangle {
+    /// Möller–Trumbore 교차 검사 (양면)
+    fn cast_ray(&self, ray: &Ray, max_distance: f32) -> Option<RayHit> {
+        let [a, b, c] = self.vertices;
+        let edge1 = b - a;
+        let edge2 = c - a;
+        let p = ray.direction().cross(edge2);
+        let determinant = edge1.dot(p);
+        if determinant.abs() <= f32::EPSILON * edge1.length() * edge2.length() {
+            return None;    // ray가 평면과 평행함
+        }
+
+        // 무게중심 좌표 (u, v)가 삼각형 안에 있어야 한다.
+        let inv_determinant = 1.0 / determinant;
+        let s = ray.origin - a;
+        let u = s.dot(p) * inv_determinant;
+        if !(0.0..=1.0).contains(&u) {
+            return None;
+        }
+        let q = s.cross(edge1);
+        let v = ray.direction().dot(q) * inv_determinant;
+        if v < 0.0 || u + v > 1.0 {
+            return None;
+        }
+
+        let distance = edge2.dot(q) * inv_determinant;
+        if distance < 0.0 || distance > max_distance {
+            return None;
+        }
+
+        // 법선은 ray가 들어온 쪽을 향한다.
+        let normal = self.normal();
+        Some(RayHit {
+            distance,
+            point: ray.point_at(distance),
+            normal: if normal.dot(ray.direction()) > 0.0 { -normal } else { normal },
+        })
+    }
+}
@@ -0,0 +1,93 @@
+use crate::{BoundingBox, Capsule, Sphere, VertexBox};
+use super::{point_query::PointQuery, CollisionDetails};
+
+
+/// 부호 있는 거리 함수(SDF)  
+/// 도형 밖은 양수, 안은 음수이며, 경계에서 0이다.  
+pub trait SignedDistance: PointQuery {
+    /// point에서의 부호 있는 거리와 기울기(거리가 가장 빠르게 증가하는 방향의 단위벡터)를 반환한다.  
+    /// 기본 구현은 표면에 투영한 점을 이용한다.  
+    fn signed_distance(&self, point: &glam::Vec3A) -> (f32, glam::Vec3A) {
+        let projection = self.project_point(point);
+        let to_surface = projection.point - point;
+        let distance = to_surface.length();
+        if projection.is_inside {
+            (-distance, to_surface.normalize_or_zero())
+        } else {
+            (distance, -to_surface.normalize_or_zero())
+        }
+    }
+
+    /// point를 중심으로 하는 반지름 radius의 입자와 도형의 충돌 정보를 구한다.  
+    /// GJK 없이 SDF만으로 계산하며, normal은 입자에서 도형을 향한다. (check_static_collision_details와 같은 방향)  
+    fn sdf_penetration(&self, point: &glam::Vec3A, radius: f32) -> Option<CollisionDetails> {
+        let (distance, gradient) = self.signed_distance(point);
+        let penetration = radius - distance;
+        if penetration < 0.0 {
+            return None;
+        }
+
+        Some(CollisionDetails {
+            normal: -gradient,
+            penetration,
+        })
+    }
+}
+
+
+impl SignedDistance for BoundingBox {
+    fn signed_distance(&self, point: &glam::Vec3A) -> (f32, glam::Vec3A) {
+        let local = self.world_to_local(point);
+        let extents = glam::Vec3A::from(self.extents());
+        let q = local.abs() - extents;
+        let sign = glam::Vec3A::select(local.cmplt(glam::Vec3A::ZERO), -glam::Vec3A::ONE, glam::Vec3A::ONE);
+
+        let (distance, local_gradient) = if q.max_element() > 0.0 {
+            // 박스 밖: 벗어난 성분만으로 거리를 구한다.
+            let outside = q.max(glam::Vec3A::ZERO);
+            let distance = outside.length();
+            (distance, sign * outside / distance)
+        } else {
+            // 박스 안: 가장 가까운 면까지의 거리
+            let axis = if q.x >= q.y && q.x >= q.z {
+                0
+            } else if q.y >= q.z {
+                1
+            } else {
+                2
+            };
+            let mut gradient = glam::Vec3A::ZERO;
+            gradient[axis] = sign[axis];
+            (q[axis], gradient)
+        };
+
+        let gradient = match self.rotation() {
+            Some(rotation) => glam::Vec3A::from(rotation * glam::Vec3::from(local_gradient)),
+            None => local_gradient,
+        };
+        (distance, gradient)
+    }
+}
+
+impl SignedDistance for Sphere {
+    fn signed_distance(&self, point: &glam::Vec3A) -> (f32, glam::Vec3A) {
+        let to_point = point - glam::Vec3A::from(self.center);
+        let distance = to_point.length();
+        // 중심에서는 임의의 방향을 기울기로 사용한다.
+        let gradient = to_point.try_normalize().unwrap_or(glam::Vec3A::X);
+        (distance - self.radius, gradient)
+    }
+}
+
+impl SignedDistance for Capsule {
+    fn signed_distance(&self, point: &glam::Vec3A) -> (f32, glam::Vec3A) {
+        let to_point = point - self.closest_point_on_segment(point);
+        // 선분 위에서는 선분에 수직인 임의의 방향을 기울기로 사용한다.
+        let gradient = to_point.try_normalize()
+            .or_else(|| glam::Vec3A::from(self.half_segment).try_normalize().map(|axis| axis.any_orthonormal_vector()))
+            .unwrap_or(glam::Vec3A::X);
+        (to_point.length() - self.radius, gradient)
+    }
+}
+
+impl SignedDistance for VertexBox {}
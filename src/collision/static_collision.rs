@@ -1,5 +1,5 @@
-use crate::{BoundingBox, Sphere};
-use super::{CollisionDetails, ConvexHull};
+use crate::{bounds::ObbFrame, BoundingBox, Capsule, PreparedBox, Sphere, Triangle};
+use super::{point_query::closest_point_outside, CollisionDetails, ConvexHull, GjkConfig, MinkowskiDifference};
 
 
 /// 움직이지 않는 물체끼리의 충돌 검사
@@ -33,8 +33,7 @@ impl StaticCollision<Sphere> for BoundingBox {
         let local_sphere_center = match self.rotation() {
             Some(rotation) => {
                 let inv_rotation = rotation.transpose();    // 회전행렬의 전치행렬은 역행렬과 같다.
-                let local_origin = inv_rotation * (sphere.center - self.center);
-                local_origin
+                inv_rotation * (sphere.center - self.center)
             }
             None => sphere.center - self.center,
         };
@@ -59,8 +58,7 @@ impl StaticCollision<Sphere> for BoundingBox {
         let local_sphere_center = match self.rotation() {
             Some(rotation) => {
                 let inv_rotation = rotation.transpose();    // 회전행렬의 전치행렬은 역행렬과 같다.
-                let local_origin = inv_rotation * (sphere.center - self.center);
-                local_origin
+                inv_rotation * (sphere.center - self.center)
             }
             None => sphere.center - self.center,
         };
@@ -76,12 +74,26 @@ impl StaticCollision<Sphere> for BoundingBox {
             }
         }
 
-        let to_center = glam::Vec3A::from(to_center);
-        let penetration = sphere.radius - to_center.length();
+        let mut to_center = glam::Vec3A::from(to_center);
+        let mut penetration = sphere.radius - to_center.length();
 
         if penetration < 0.0 {
             return None;
         }
+
+        if to_center == glam::Vec3A::ZERO {
+            // 중심이 Box 안에 있으면 가장 가까운 면 밖으로 밀어낸다.
+            let gap = glam::Vec3A::from(aabb_extents - local_sphere_center.abs());
+            let axis = if gap.x <= gap.y && gap.x <= gap.z {
+                0
+            } else if gap.y <= gap.z {
+                1
+            } else {
+                2
+            };
+            to_center[axis] = if local_sphere_center[axis] < 0.0 { -1.0 } else { 1.0 };
+            penetration = sphere.radius + gap[axis];
+        }
         
         let to_center = match self.rotation() {
             Some(rotation) => {
@@ -105,8 +117,9 @@ impl StaticCollision<BoundingBox> for Sphere {
     }
 
     fn check_static_collision_details(&self, other: &BoundingBox) -> Option<CollisionDetails> {
+        // normal이 other에서 self를 향하도록 뒤집는다.
         let mut details = other.check_static_collision_details(self)?;
-        details.penetration = -details.penetration;
+        details.normal = -details.normal;
         Some(details)
     }
 }
@@ -134,3 +147,251 @@ impl StaticCollision<Sphere> for Sphere {
         })
     }
 }
+
+
+impl StaticCollision<BoundingBox> for Capsule {
+    fn check_static_collision(&self, other: &BoundingBox) -> bool {
+        self.check_static_collision_details(other).is_some()
+    }
+
+    fn check_static_collision_details(&self, other: &BoundingBox) -> Option<CollisionDetails> {
+        capsule_box_collision_details(self, other, other)
+    }
+}
+
+impl StaticCollision<Sphere> for Capsule {
+    fn check_static_collision(&self, other: &Sphere) -> bool {
+        self.sphere_at(&glam::Vec3A::from(other.center)).check_static_collision(other)
+    }
+
+    fn check_static_collision_details(&self, other: &Sphere) -> Option<CollisionDetails> {
+        self.sphere_at(&glam::Vec3A::from(other.center)).check_static_collision_details(other)
+    }
+}
+
+
+// PreparedBox는 미리 계산한 정점과 축을 쓰고, 정점이 필요 없는 검사는 BoundingBox에 맡긴다.
+impl StaticCollision<PreparedBox> for PreparedBox {
+    fn check_static_collision(&self, other: &PreparedBox) -> bool {
+        if self.bounding_box().rotation().is_some() || other.bounding_box().rotation().is_some() {
+            self.obb_collision(other)
+        } else {
+            self.bounding_box().aabb_collision(other.bounding_box())
+        }
+    }
+
+    fn check_static_collision_details(&self, other: &PreparedBox) -> Option<CollisionDetails> {
+        if self.bounding_box().rotation().is_some() || other.bounding_box().rotation().is_some() {
+            self.obb_collision_details(other)
+        } else {
+            self.bounding_box().aabb_collision_details(other.bounding_box())
+        }
+    }
+}
+
+impl StaticCollision<BoundingBox> for PreparedBox {
+    fn check_static_collision(&self, other: &BoundingBox) -> bool {
+        // 감싸는 AABB끼리 떨어져 있으면 분리축을 검사하지 않는다.
+        if !self.aabb().aabb_collision(&other.aabb()) {
+            false
+        } else if self.bounding_box().rotation().is_some() || other.rotation().is_some() {
+            ObbFrame::from(self).collides(&ObbFrame::from(other))
+        } else {
+            self.bounding_box().aabb_collision(other)
+        }
+    }
+
+    fn check_static_collision_details(&self, other: &BoundingBox) -> Option<CollisionDetails> {
+        if !self.aabb().aabb_collision(&other.aabb()) {
+            None
+        } else if self.bounding_box().rotation().is_some() || other.rotation().is_some() {
+            ObbFrame::from(self).collision_details(&ObbFrame::from(other))
+        } else {
+            self.bounding_box().aabb_collision_details(other)
+        }
+    }
+}
+
+impl StaticCollision<PreparedBox> for BoundingBox {
+    fn check_static_collision(&self, other: &PreparedBox) -> bool {
+        other.check_static_collision(self)
+    }
+
+    fn check_static_collision_details(&self, other: &PreparedBox) -> Option<CollisionDetails> {
+        // normal이 other에서 self를 향하도록 뒤집는다.
+        let mut details = other.check_static_collision_details(self)?;
+        details.normal = -details.normal;
+        Some(details)
+    }
+}
+
+impl StaticCollision<Sphere> for PreparedBox {
+    fn check_static_collision(&self, sphere: &Sphere) -> bool {
+        self.bounding_box().check_static_collision(sphere)
+    }
+
+    fn check_static_collision_details(&self, sphere: &Sphere) -> Option<CollisionDetails> {
+        self.bounding_box().check_static_collision_details(sphere)
+    }
+}
+
+impl StaticCollision<PreparedBox> for Sphere {
+    fn check_static_collision(&self, other: &PreparedBox) -> bool {
+        self.check_static_collision(other.bounding_box())
+    }
+
+    fn check_static_collision_details(&self, other: &PreparedBox) -> Option<CollisionDetails> {
+        self.check_static_collision_details(other.bounding_box())
+    }
+}
+
+impl StaticCollision<PreparedBox> for Capsule {
+    fn check_static_collision(&self, other: &PreparedBox) -> bool {
+        self.check_static_collision_details(other).is_some()
+    }
+
+    fn check_static_collision_details(&self, other: &PreparedBox) -> Option<CollisionDetails> {
+        capsule_box_collision_details(self, other, other.bounding_box())
+    }
+}
+
+
+// 삼각형과의 충돌 정보는 normal이 삼각형에서 self를 향하고, penetration은 0 이상이다.
+impl StaticCollision<Triangle> for Sphere {
+    fn check_static_collision(&self, other: &Triangle) -> bool {
+        let center = glam::Vec3A::from(self.center);
+        other.closest_point(&center).distance_squared(center) <= self.radius.powi(2)
+    }
+
+    fn check_static_collision_details(&self, other: &Triangle) -> Option<CollisionDetails> {
+        let center = glam::Vec3A::from(self.center);
+        let offset = center - other.closest_point(&center);
+        let distance = offset.length();
+        if distance > self.radius {
+            return None;
+        }
+        // 중심이 삼각형 위에 있으면 면의 법선 방향으로 밀어낸다.
+        let normal = if distance > f32::EPSILON { offset / distance } else { other.normal() };
+        Some(CollisionDetails {
+            normal,
+            penetration: self.radius - distance,
+        })
+    }
+}
+
+impl StaticCollision<Triangle> for BoundingBox {
+    fn check_static_collision(&self, other: &Triangle) -> bool {
+        box_triangle_collision_details(self, other).is_some()
+    }
+
+    fn check_static_collision_details(&self, other: &Triangle) -> Option<CollisionDetails> {
+        box_triangle_collision_details(self, other)
+    }
+}
+
+/// 박스와 삼각형의 분리축 검사 (박스의 축 3개, 삼각형의 법선, 박스의 축과 삼각형 모서리의 외적 9개)  
+/// 가장 적게 겹치는 축으로 박스를 밀어낸다.  
+fn box_triangle_collision_details(bounding_box: &BoundingBox, triangle: &Triangle) -> Option<CollisionDetails> {
+    let rotation = bounding_box.rotation().unwrap_or(glam::Mat3::IDENTITY);
+    let box_axes = [rotation.x_axis, rotation.y_axis, rotation.z_axis].map(glam::Vec3A::from);
+    let extents = glam::Vec3A::from(bounding_box.extents());
+    // 박스의 중심을 원점으로 옮긴다.
+    let center = glam::Vec3A::from(bounding_box.center);
+    let vertices = triangle.vertices.map(|vertex| vertex - center);
+    let edges = [vertices[1] - vertices[0], vertices[2] - vertices[1], vertices[0] - vertices[2]];
+
+    // 모서리의 방향을 정규화해 두어, 외적의 길이로 평행한지 판단할 수 있게 한다.
+    let mut axes = Vec::with_capacity(13);
+    axes.extend(box_axes);
+    axes.push(triangle.normal());
+    for box_axis in box_axes {
+        for edge in edges {
+            axes.push(box_axis.cross(edge.normalize_or_zero()));
+        }
+    }
+
+    let mut best: Option<CollisionDetails> = None;
+    for axis in axes {
+        // 거의 평행한 축끼리의 외적과 넓이가 0인 삼각형의 법선 제외
+        if axis.length_squared() <= 1.0e-6 {
+            continue;
+        }
+        let axis = axis.normalize();
+        let radius = (0..3).map(|i| extents[i] * axis.dot(box_axes[i]).abs()).sum::<f32>();
+        let projections = vertices.map(|vertex| vertex.dot(axis));
+        let (min, max) = (projections[0].min(projections[1]).min(projections[2]), projections[0].max(projections[1]).max(projections[2]));
+        if min > radius || max < -radius {
+            return None; // 분리된 축이 존재 => 충돌 없음
+        }
+
+        // 박스를 axis 방향 또는 반대 방향으로 밀어낼 때 필요한 거리
+        let (normal, penetration) = if max + radius < radius - min { (axis, max + radius) } else { (-axis, radius - min) };
+        if best.is_none_or(|best| penetration < best.penetration) {
+            best = Some(CollisionDetails {
+                normal,
+                penetration,
+            });
+        }
+    }
+    best
+}
+
+/// 캡슐과 박스의 충돌 정보  
+/// GJK의 support point는 convex_box로 구하므로, 정점을 미리 계산한 박스를 넘기면 그만큼 빨라진다.  
+fn capsule_box_collision_details(
+    capsule: &Capsule,
+    convex_box: &impl ConvexHull,
+    bounding_box: &BoundingBox,
+) -> Option<CollisionDetails> {
+    // 선분과 박스 사이의 최단 거리를 GJK로 구한다.
+    let segment = Capsule {
+        radius: 0.0,
+        ..*capsule
+    };
+    let difference = MinkowskiDifference { a: &segment, b: convex_box };
+    match closest_point_outside(&difference, &glam::Vec3A::ZERO, &GjkConfig::default()) {
+        // closest는 박스 위의 점에서 선분 위의 점을 향한다.
+        Some(closest) => {
+            let distance = closest.length();
+            if distance > capsule.radius {
+                return None;
+            }
+            Some(CollisionDetails {
+                normal: closest / distance,
+                penetration: capsule.radius - distance,
+            })
+        }
+        // 선분이 박스를 지나는 경우, 박스 안에 들어간 구간의 중점에 놓인 구로 밀어낼 방향을 정한다.
+        None => {
+            let sphere = Sphere {
+                center: glam::Vec3::from(segment_midpoint_inside(capsule, bounding_box)),
+                radius: capsule.radius,
+            };
+            sphere.check_static_collision_details(bounding_box).map(CollisionDetails::with_positive_penetration)
+        }
+    }
+}
+
+/// 캡슐의 선분 중 박스 안에 들어간 구간의 중점 (slab 방식으로 선분을 자른다.)
+fn segment_midpoint_inside(capsule: &Capsule, bounding_box: &BoundingBox) -> glam::Vec3A {
+    let (start, end) = capsule.endpoints();
+    let local_start = bounding_box.world_to_local(&start);
+    let direction = bounding_box.world_to_local(&end) - local_start;
+    let extents = glam::Vec3A::from(bounding_box.extents());
+
+    let (mut t_min, mut t_max) = (0.0_f32, 1.0_f32);
+    for i in 0..3 {
+        if direction[i].abs() <= f32::EPSILON {
+            continue;
+        }
+        let t1 = (-extents[i] - local_start[i]) / direction[i];
+        let t2 = (extents[i] - local_start[i]) / direction[i];
+        t_min = t_min.max(t1.min(t2));
+        t_max = t_max.min(t1.max(t2));
+    }
+    if t_min > t_max {
+        // 수치 오차로 겹치는 구간이 없으면 박스 중심에 가장 가까운 점
+        return capsule.closest_point_on_segment(&glam::Vec3A::from(bounding_box.center));
+    }
+    start + (end - start) * ((t_min + t_max) * 0.5)
+}
@@ -0,0 +1,153 @@
+use crate::{ColliderHandle, ColliderShape, CollisionDetails, ConvexHull};
+
+
+/// 매니폴드가 가질 수 있는 최대 접촉점 수
+const MAX_CONTACT_POINTS: usize = 4;
+/// 정점이 상대 도형 안에 있다고 판단하는 여유 거리
+const CONTACT_TOLERANCE: f32 = 0.01;
+/// 이 거리 안에 있는 지난 step의 접촉점은 같은 점으로 본다.
+const WARM_START_DISTANCE: f32 = 0.05;
+/// normal이 이 각도(cos) 이상 바뀌면 지난 step의 충격량을 쓰지 않는다.
+const WARM_START_NORMAL_COS: f32 = 0.95;
+
+/// 두 도형 사이의 접촉점 하나
+#[derive(Debug, Clone, Copy)]
+pub struct ContactPoint {
+    /// 월드 좌표
+    pub position: glam::Vec3A,
+    /// normal 방향으로 겹친 깊이
+    pub penetration: f32,
+    /// 솔버가 누적한 법선 방향 충격량
+    pub normal_impulse: f32,
+    /// 솔버가 누적한 두 접선 방향 충격량
+    pub tangent_impulse: [f32; 2],
+}
+
+impl ContactPoint {
+    pub fn new(position: glam::Vec3A, penetration: f32) -> Self {
+        Self {
+            position,
+            penetration,
+            normal_impulse: 0.0,
+            tangent_impulse: [0.0; 2],
+        }
+    }
+}
+
+/// 두 충돌체 사이의 접촉점 묶음  
+/// normal은 CollisionDetails와 같이 b에서 a를 향하는 방향이다.  
+#[derive(Debug, Clone)]
+pub struct ContactManifold {
+    pub a: ColliderHandle,
+    pub b: ColliderHandle,
+    pub normal: glam::Vec3A,
+    pub points: Vec<ContactPoint>,
+}
+
+impl ContactManifold {
+    /// 두 도형의 충돌 정보로부터 접촉점을 만든다.  
+    /// 구는 가장 깊은 점 하나, 박스끼리는 상대 안에 들어간 정점들(없으면 두 support point의 중점)을 사용한다.  
+    pub fn new(
+        a: ColliderHandle,
+        shape_a: &ColliderShape,
+        b: ColliderHandle,
+        shape_b: &ColliderShape,
+        details: &CollisionDetails,
+    ) -> Self {
+        let details = details.with_positive_penetration();
+        let normal = details.normal.try_normalize().unwrap_or_else(|| {
+            // 접하기만 하는 경우 등 normal이 없으면 중심을 잇는 방향을 사용한다.
+            glam::Vec3A::from(shape_a.center() - shape_b.center()).try_normalize().unwrap_or(glam::Vec3A::Y)
+        });
+
+        let points = match (shape_a, shape_b) {
+            (ColliderShape::Sphere(sphere), _) => {
+                let position = glam::Vec3A::from(sphere.center) - normal * (sphere.radius - details.penetration * 0.5);
+                vec![ContactPoint::new(position, details.penetration)]
+            }
+            (_, ColliderShape::Sphere(sphere)) => {
+                let position = glam::Vec3A::from(sphere.center) + normal * (sphere.radius - details.penetration * 0.5);
+                vec![ContactPoint::new(position, details.penetration)]
+            }
+            (ColliderShape::Box(box_a), ColliderShape::Box(box_b)) => {
+                box_contact_points(box_a, box_b, &normal, details.penetration)
+            }
+        };
+
+        Self {
+            a,
+            b,
+            normal,
+            points,
+        }
+    }
+
+    /// 지난 step의 매니폴드에서 가까운 접촉점의 누적 충격량을 가져온다.
+    pub fn warm_start_from(&mut self, previous: &ContactManifold) {
+        if self.normal.dot(previous.normal) < WARM_START_NORMAL_COS {
+            return;
+        }
+        for point in self.points.iter_mut() {
+            let nearest = previous.points.iter()
+                .min_by(|a, b| {
+                    let distance_a = (a.position - point.position).length_squared();
+                    let distance_b = (b.position - point.position).length_squared();
+                    distance_a.total_cmp(&distance_b)
+                });
+            if let Some(nearest) = nearest
+                && (nearest.position - point.position).length_squared() <= WARM_START_DISTANCE.powi(2)
+            {
+                point.normal_impulse = nearest.normal_impulse;
+                point.tangent_impulse = nearest.tangent_impulse;
+            }
+        }
+    }
+
+    /// 가장 깊은 접촉점의 깊이
+    pub fn max_penetration(&self) -> f32 {
+        self.points.iter().fold(0.0, |max, point| max.max(point.penetration))
+    }
+}
+
+fn box_contact_points(
+    box_a: &crate::BoundingBox,
+    box_b: &crate::BoundingBox,
+    normal: &glam::Vec3A,
+    penetration: f32,
+) -> Vec<ContactPoint> {
+    // normal 방향으로 b의 가장 바깥 면과 a의 가장 안쪽 면의 위치
+    let top_b = normal.dot(box_b.get_furthest_point(normal));
+    let bottom_a = normal.dot(box_a.get_furthest_point(&-*normal));
+
+    let expanded_a = box_a.expanded_by(CONTACT_TOLERANCE);
+    let expanded_b = box_b.expanded_by(CONTACT_TOLERANCE);
+    let candidates = box_a.get_vertices().into_iter()
+        .filter(|vertex| expanded_b.contains_point(vertex))
+        .map(|vertex| ContactPoint::new(vertex, (top_b - normal.dot(vertex)).max(0.0)))
+        .chain(
+            box_b.get_vertices().into_iter()
+                .filter(|vertex| expanded_a.contains_point(vertex))
+                .map(|vertex| ContactPoint::new(vertex, (normal.dot(vertex) - bottom_a).max(0.0)))
+        );
+
+    // 크기가 같은 박스가 포개진 경우처럼 겹치는 정점은 하나만 남긴다.
+    let mut points: Vec<ContactPoint> = Vec::with_capacity(16);
+    for candidate in candidates {
+        let duplicated = points.iter()
+            .any(|point| (point.position - candidate.position).length_squared() <= CONTACT_TOLERANCE.powi(2));
+        if !duplicated {
+            points.push(candidate);
+        }
+    }
+
+    if points.is_empty() {
+        // 모서리끼리 만나는 경우
+        let position = (box_a.get_furthest_point(&-*normal) + box_b.get_furthest_point(normal)) * 0.5;
+        points.push(ContactPoint::new(position, penetration));
+    }
+
+    // 깊은 점부터 최대 개수만큼 남긴다.
+    points.sort_by(|a, b| b.penetration.total_cmp(&a.penetration));
+    points.truncate(MAX_CONTACT_POINTS);
+    points
+}
@@ -0,0 +1,134 @@
+use crate::{Bounded, ColliderShape, CollisionDetails};
+use super::BodyType;
+
+
+/// 겹침 해소의 설정
+#[derive(Debug, Clone, Copy)]
+pub struct DepenetrationSettings {
+    pub max_iterations: usize,
+    /// 남은 침투가 모두 이 값 이하가 되면 멈춘다. (slop보다 커야 한다.)
+    pub tolerance: f32,
+    /// 이 깊이까지의 침투는 남겨 두어, 맞닿은 물체가 떨어졌다 붙었다 하지 않게 한다.
+    pub slop: f32,
+    /// 한 번의 반복에서 (침투 - slop) 중 되돌리는 비율 (Baumgarte 계수, 0 ~ 1)
+    pub correction_factor: f32,
+}
+
+impl Default for DepenetrationSettings {
+    fn default() -> Self {
+        Self {
+            max_iterations: 16,
+            tolerance: 0.01,
+            slop: 0.001,
+            correction_factor: 0.8,
+        }
+    }
+}
+
+/// 겹침 해소에 넘길 물체
+#[derive(Debug, Clone)]
+pub struct DepenetrationObject {
+    pub shape: ColliderShape,
+    pub body_type: BodyType,
+    /// Dynamic인 경우에만 사용한다.
+    pub mass: f32,
+}
+
+impl DepenetrationObject {
+    pub fn new(shape: impl Into<ColliderShape>, body_type: BodyType, mass: f32) -> Self {
+        Self {
+            shape: shape.into(),
+            body_type,
+            mass,
+        }
+    }
+
+    /// Static, Kinematic은 밀리지 않는다. (질량 무한대)
+    fn inverse_mass(&self) -> f32 {
+        match self.body_type {
+            BodyType::Dynamic => 1.0 / self.mass.max(f32::EPSILON),
+            BodyType::Static | BodyType::Kinematic => 0.0,
+        }
+    }
+}
+
+/// 겹침 해소의 결과
+#[derive(Debug, Clone)]
+pub struct DepenetrationResult {
+    /// 입력과 같은 순서의 물체별 이동량
+    pub corrections: Vec<glam::Vec3A>,
+    /// 밀어내기를 수행한 횟수 (처음부터 겹치지 않았으면 0)
+    pub iterations: usize,
+    /// corrections를 적용한 위치에서 측정한 가장 깊은 침투
+    pub residual_penetration: f32,
+    /// 남은 침투가 tolerance 이하가 되었는지 여부
+    pub converged: bool,
+}
+
+/// 겹친 물체들을 서로 밀어내는 이동량을 구한다.  
+/// 쌍마다 check_static_collision_details의 normal과 penetration으로 (침투 - slop)만큼을  
+/// 역질량의 비율로 나누어 밀어내고, 옮긴 위치에서 남은 침투를 다시 측정하는 것을 반복한다.  
+pub fn depenetrate(objects: &[DepenetrationObject], settings: &DepenetrationSettings) -> DepenetrationResult {
+    let mut shapes = objects.iter().map(|object| object.shape).collect::<Vec<_>>();
+    let inverse_masses = objects.iter().map(DepenetrationObject::inverse_mass).collect::<Vec<_>>();
+    let mut corrections = vec![glam::Vec3A::ZERO; objects.len()];
+
+    let mut iterations = 0;
+    // 밀어낸 뒤의 위치로 다시 측정해야 결과가 돌려주는 위치의 상태를 나타낸다.
+    let mut residual_penetration = deepest_penetration(&shapes, &inverse_masses);
+    while residual_penetration > settings.tolerance && iterations < settings.max_iterations {
+        iterations += 1;
+
+        for i in 0..shapes.len() {
+            for j in i + 1..shapes.len() {
+                let total_inverse_mass = inverse_masses[i] + inverse_masses[j];
+                let Some(details) = movable_contact(&shapes[i], &shapes[j], total_inverse_mass) else {
+                    continue;
+                };
+
+                // normal은 j에서 i를 향한다.
+                let amount = (details.penetration - settings.slop).max(0.0) * settings.correction_factor;
+                let push = details.normal * (amount / total_inverse_mass);
+                move_shape(&mut shapes[i], &mut corrections[i], push * inverse_masses[i]);
+                move_shape(&mut shapes[j], &mut corrections[j], -push * inverse_masses[j]);
+            }
+        }
+
+        residual_penetration = deepest_penetration(&shapes, &inverse_masses);
+    }
+
+    DepenetrationResult {
+        corrections,
+        iterations,
+        residual_penetration,
+        converged: residual_penetration <= settings.tolerance,
+    }
+}
+
+/// 둘 중 하나라도 밀 수 있는 쌍의 충돌 정보 (normal은 b에서 a를 향하고, penetration은 0 이상이다.)
+fn movable_contact(a: &ColliderShape, b: &ColliderShape, total_inverse_mass: f32) -> Option<CollisionDetails> {
+    if total_inverse_mass <= 0.0 || !a.aabb().aabb_collision(&b.aabb()) {
+        return None;
+    }
+    a.collision_details(b)
+}
+
+/// 밀 수 있는 쌍 중 가장 깊은 침투
+fn deepest_penetration(shapes: &[ColliderShape], inverse_masses: &[f32]) -> f32 {
+    let mut deepest = 0.0_f32;
+    for i in 0..shapes.len() {
+        for j in i + 1..shapes.len() {
+            if let Some(details) = movable_contact(&shapes[i], &shapes[j], inverse_masses[i] + inverse_masses[j]) {
+                deepest = deepest.max(details.penetration);
+            }
+        }
+    }
+    deepest
+}
+
+fn move_shape(shape: &mut ColliderShape, correction: &mut glam::Vec3A, offset: glam::Vec3A) {
+    if offset != glam::Vec3A::ZERO {
+        *correction += offset;
+        shape.set_center(shape.center() + glam::Vec3::from(offset));
+    }
+}
@@ -0,0 +1,92 @@
+use std::collections::HashMap;
+use crate::ColliderHandle;
+use super::RigidBody;
+
+
+/// 강체를 재우는 기준
+#[derive(Debug, Clone, Copy)]
+pub struct SleepSettings {
+    /// false이면 아무것도 재우지 않는다.
+    pub enabled: bool,
+    /// 이보다 느린 선속도는 멈춘 것으로 본다.
+    pub linear_threshold: f32,
+    /// 이보다 느린 각속도는 멈춘 것으로 본다.
+    pub angular_threshold: f32,
+    /// 섬의 모든 강체가 이 시간(초) 동안 멈춰 있으면 섬 전체를 재운다.
+    pub time_to_sleep: f32,
+}
+
+impl Default for SleepSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            linear_threshold: 0.05,
+            angular_threshold: 0.05,
+            time_to_sleep: 0.5,
+        }
+    }
+}
+
+/// 접촉으로 이어진 Dynamic 강체의 묶음  
+/// 섬은 함께 잠들고 함께 깨어난다.  
+#[derive(Debug, Clone)]
+pub struct Island {
+    /// 섬에 속한 강체의 핸들 (오름차순)
+    pub bodies: Vec<ColliderHandle>,
+    pub sleeping: bool,
+}
+
+/// 두 Dynamic 강체를 잇는 접촉을 간선으로 하는 그래프의 연결 요소를 구한다. (union-find)  
+/// 다른 강체와 닿지 않은 Dynamic 강체는 혼자서 섬이 된다.  
+pub(crate) fn build_islands(
+    bodies: &HashMap<ColliderHandle, RigidBody>,
+    contacts: impl Iterator<Item = (ColliderHandle, ColliderHandle)>,
+) -> Vec<Vec<ColliderHandle>> {
+    let mut handles = bodies.iter()
+        .filter(|(_, body)| body.is_dynamic())
+        .map(|(&handle, _)| handle)
+        .collect::<Vec<_>>();
+    handles.sort();
+    let indices = handles.iter()
+        .enumerate()
+        .map(|(index, &handle)| (handle, index))
+        .collect::<HashMap<_, _>>();
+
+    let mut parents = (0..handles.len()).collect::<Vec<_>>();
+    for (a, b) in contacts {
+        if let (Some(&a), Some(&b)) = (indices.get(&a), indices.get(&b)) {
+            let (root_a, root_b) = (find(&mut parents, a), find(&mut parents, b));
+            if root_a != root_b {
+                parents[root_a.max(root_b)] = root_a.min(root_b);
+            }
+        }
+    }
+
+    // 뿌리가 가장 작은 인덱스이므로, 핸들 순서대로 섬이 만들어진다.
+    let mut islands: Vec<Vec<ColliderHandle>> = Vec::new();
+    let mut island_of_root = HashMap::new();
+    for (index, &handle) in handles.iter().enumerate() {
+        let root = find(&mut parents, index);
+        let island = *island_of_root.entry(root).or_insert_with(|| {
+            islands.push(Vec::new());
+            islands.len() - 1
+        });
+        islands[island].push(handle);
+    }
+    islands
+}
+
+fn find(parents: &mut [usize], index: usize) -> usize {
+    let mut root = index;
+    while parents[root] != root {
+        root = parents[root];
+    }
+    // 경로 압축
+    let mut current = index;
+    while parents[current] != root {
+        let next = parents[current];
+        parents[current] = root;
+        current = next;
+    }
+    root
+}
@@ -0,0 +1,13 @@
+mod contact;
+mod depenetration;
+mod island;
+mod physics_world;
+mod rigid_body;
+mod solver;
+
+pub use contact::*;
+pub use depenetration::*;
+pub use island::{Island, SleepSettings};
+pub use physics_world::*;
+pub use rigid_body::*;
+pub use solver::SolverSettings;
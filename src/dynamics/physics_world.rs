@@ -0,0 +1,266 @@
+use std::collections::{HashMap, HashSet};
+use crate::{Broadphase, Collider, ColliderHandle, CollisionEvent, CollisionWorld, DynamicTree};
+use super::{
+    island::build_islands,
+    solver::{solve_contacts, SolverBody},
+    BodyType, ContactManifold, Island, RigidBody, SleepSettings, SolverSettings,
+};
+
+
+/// CollisionWorld 위에서 강체를 움직이고 접촉을 푸는 물리 월드  
+/// 강체는 자신이 붙어 있는 충돌체의 핸들로 구분하며, 강체가 없는 충돌체는 움직이지 않는 것으로 취급한다.  
+/// 멈춘 강체는 접촉으로 이어진 섬 단위로 잠들어, 적분과 접촉 계산을 건너뛴다.  
+pub struct PhysicsWorld<B = DynamicTree<ColliderHandle>> {
+    pub gravity: glam::Vec3A,
+    pub solver_settings: SolverSettings,
+    pub sleep_settings: SleepSettings,
+    collision_world: CollisionWorld<B>,
+    bodies: HashMap<ColliderHandle, RigidBody>,
+    /// body_mut으로 바뀌어 다음 step의 충돌 검사 전에 충돌체에 반영해야 하는 강체
+    modified: HashSet<ColliderHandle>,
+    manifolds: Vec<ContactManifold>,
+    islands: Vec<Island>,
+}
+
+impl Default for PhysicsWorld {
+    fn default() -> Self {
+        Self::new(CollisionWorld::default())
+    }
+}
+
+impl<B: Broadphase<ColliderHandle>> PhysicsWorld<B> {
+    pub fn new(collision_world: CollisionWorld<B>) -> Self {
+        Self {
+            gravity: glam::Vec3A::new(0.0, -9.81, 0.0),
+            solver_settings: SolverSettings::default(),
+            sleep_settings: SleepSettings::default(),
+            collision_world,
+            bodies: HashMap::new(),
+            modified: HashSet::new(),
+            manifolds: Vec::new(),
+            islands: Vec::new(),
+        }
+    }
+
+    pub fn collision_world(&self) -> &CollisionWorld<B> {
+        &self.collision_world
+    }
+
+    /// 강체가 붙은 충돌체를 추가한다. 강체의 위치와 방향은 충돌체의 도형을 따른다.
+    pub fn insert(&mut self, collider: impl Into<Collider>, body: RigidBody) -> ColliderHandle {
+        let collider = collider.into();
+        let mut body = body;
+        body.set_position(glam::Vec3A::from(collider.shape.center()));
+        if let crate::ColliderShape::Box(bounding_box) = &collider.shape {
+            body.set_orientation(bounding_box.rotation().map_or(glam::Quat::IDENTITY, |rotation| glam::Quat::from_mat3(&rotation)));
+        }
+
+        let handle = self.collision_world.insert(collider);
+        self.bodies.insert(handle, body);
+        handle
+    }
+
+    /// 강체 없이 움직이지 않는 충돌체를 추가한다. (잠든 충돌체와의 쌍은 narrowphase를 건너뛴다.)
+    pub fn insert_collider(&mut self, collider: impl Into<Collider>) -> ColliderHandle {
+        let handle = self.collision_world.insert(collider);
+        self.collision_world.set_sleeping(handle, true);
+        handle
+    }
+
+    /// 제거된 충돌체와 닿아 있던 강체는 깨어난다.
+    pub fn remove(&mut self, handle: ColliderHandle) -> Option<(Collider, Option<RigidBody>)> {
+        let touching = self.collision_world.contacts()
+            .filter_map(|(a, b, _)| if a == handle { Some(b) } else if b == handle { Some(a) } else { None })
+            .collect::<Vec<_>>();
+        for other in touching {
+            self.wake_up(other);
+        }
+
+        let collider = self.collision_world.remove(handle)?;
+        self.modified.remove(&handle);
+        Some((collider, self.bodies.remove(&handle)))
+    }
+
+    pub fn collider(&self, handle: ColliderHandle) -> Option<&Collider> {
+        self.collision_world.get(handle)
+    }
+
+    pub fn body(&self, handle: ColliderHandle) -> Option<&RigidBody> {
+        self.bodies.get(&handle)
+    }
+
+    /// 강체의 위치나 방향을 바꾼 경우 다음 step에서 충돌체에 반영된다.  
+    /// 잠들어 있던 강체는 깨어난다.  
+    pub fn body_mut(&mut self, handle: ColliderHandle) -> Option<&mut RigidBody> {
+        let body = self.bodies.get_mut(&handle)?;
+        body.wake_up();
+        self.collision_world.set_sleeping(handle, false);
+        self.modified.insert(handle);
+        Some(body)
+    }
+
+    /// 강체를 깨운다. 같은 섬의 강체들은 다음 step에서 함께 깨어난다.
+    pub fn wake_up(&mut self, handle: ColliderHandle) {
+        if let Some(body) = self.bodies.get_mut(&handle) {
+            body.wake_up();
+            self.collision_world.set_sleeping(handle, false);
+        }
+    }
+
+    pub fn bodies(&self) -> impl Iterator<Item = (ColliderHandle, &RigidBody)> {
+        self.bodies.iter().map(|(&handle, body)| (handle, body))
+    }
+
+    /// 마지막 step에서 푼 접촉 매니폴드 (잠든 강체끼리의 접촉은 포함하지 않는다.)
+    pub fn manifolds(&self) -> &[ContactManifold] {
+        &self.manifolds
+    }
+
+    /// 마지막 step에서 나눈 Dynamic 강체의 섬
+    pub fn islands(&self) -> &[Island] {
+        &self.islands
+    }
+
+    /// dt만큼 시뮬레이션을 진행하고, 이번 step의 충돌 이벤트를 반환한다.
+    pub fn step(&mut self, dt: f32) -> Vec<CollisionEvent> {
+        // 1. step 밖에서 바뀐 강체의 자세를 충돌체에 반영하고, 깨어 있는 강체의 속도를 적분한다.
+        //    (나머지 강체는 지난 step의 6에서 이미 반영되었다.)
+        for handle in self.modified.drain() {
+            if let (Some(body), Some(collider)) = (self.bodies.get(&handle), self.collision_world.get_mut(handle)) {
+                body.apply_to_shape(&mut collider.shape);
+            }
+        }
+        for body in self.bodies.values_mut() {
+            if !body.is_sleeping() {
+                body.integrate_velocity(dt, &self.gravity);
+            }
+        }
+
+        // 2. 충돌 검사
+        let events = self.collision_world.step();
+
+        // 3. 섬을 나누어 재우거나 깨운다.
+        self.update_islands(&events);
+
+        // 4. 매니폴드를 만들고 솔버에 넘길 강체 상태를 모은다.
+        let mut solver_bodies = Vec::new();
+        let mut body_indices: HashMap<ColliderHandle, usize> = HashMap::new();
+        let mut manifold_bodies = Vec::new();
+        let mut previous_manifolds = std::mem::take(&mut self.manifolds).into_iter()
+            .map(|manifold| ((manifold.a, manifold.b), manifold))
+            .collect::<HashMap<_, _>>();
+        for (a, b, details) in self.collision_world.contacts() {
+            let awake = |handle| self.bodies.get(&handle).is_some_and(|body| body.is_dynamic() && !body.is_sleeping());
+            if !awake(a) && !awake(b) {
+                continue;
+            }
+            let (Some(collider_a), Some(collider_b)) = (self.collision_world.get(a), self.collision_world.get(b)) else {
+                continue;
+            };
+
+            let mut index_of = |handle: ColliderHandle, collider: &Collider| {
+                *body_indices.entry(handle).or_insert_with(|| {
+                    solver_bodies.push(match self.bodies.get(&handle) {
+                        Some(body) => SolverBody::new(body),
+                        None => SolverBody::fixed(glam::Vec3A::from(collider.shape.center())),
+                    });
+                    solver_bodies.len() - 1
+                })
+            };
+            manifold_bodies.push((index_of(a, collider_a), index_of(b, collider_b)));
+            let mut manifold = ContactManifold::new(a, &collider_a.shape, b, &collider_b.shape, details);
+            if let Some(previous) = previous_manifolds.remove(&(a, b)) {
+                manifold.warm_start_from(&previous);
+            }
+            self.manifolds.push(manifold);
+        }
+
+        // 5. 접촉을 풀고 속도를 되돌려 놓는다.
+        solve_contacts(&mut solver_bodies, &mut self.manifolds, &manifold_bodies, &self.solver_settings, dt);
+        for (handle, index) in body_indices {
+            if let Some(body) = self.bodies.get_mut(&handle)
+                && body.is_dynamic()
+            {
+                body.linear_velocity = solver_bodies[index].linear_velocity;
+                body.angular_velocity = solver_bodies[index].angular_velocity;
+            }
+        }
+
+        // 6. 위치를 적분하고 충돌체에 반영한다.
+        for (&handle, body) in self.bodies.iter_mut() {
+            if body.is_sleeping() {
+                continue;
+            }
+            body.integrate_position(dt);
+            body.update_sleep_time(dt, &self.sleep_settings);
+            if let Some(collider) = self.collision_world.get_mut(handle) {
+                body.apply_to_shape(&mut collider.shape);
+            }
+        }
+
+        events
+    }
+
+    /// 접촉으로 이어진 Dynamic 강체를 섬으로 묶고, 섬의 모든 강체가 time_to_sleep 동안 멈춰 있었으면 재운다.  
+    /// 새로 닿은 충돌체가 있거나, 움직이는 Kinematic 강체 또는 옮겨진 Static 강체와 닿은 섬은 깨운다.  
+    fn update_islands(&mut self, events: &[CollisionEvent]) {
+        let settings = self.sleep_settings;
+
+        // 섬을 깨워야 하는 강체
+        let mut active = self.bodies.iter()
+            .filter(|(_, body)| body.is_dynamic() && !body.is_sleeping() && body.sleep_time() < settings.time_to_sleep)
+            .map(|(&handle, _)| handle)
+            .collect::<HashSet<_>>();
+        for event in events {
+            if let CollisionEvent::Started { a, b, .. } = *event {
+                active.insert(a);
+                active.insert(b);
+            }
+        }
+        let moving = |body: &RigidBody| match body.body_type {
+            BodyType::Dynamic => false,
+            BodyType::Static => !body.is_sleeping(),
+            BodyType::Kinematic => body.linear_velocity.length_squared() >= settings.linear_threshold.powi(2)
+                || body.angular_velocity.length_squared() >= settings.angular_threshold.powi(2),
+        };
+        for (a, b, _) in self.collision_world.contacts() {
+            if self.bodies.get(&a).is_some_and(moving) {
+                active.insert(b);
+            }
+            if self.bodies.get(&b).is_some_and(moving) {
+                active.insert(a);
+            }
+        }
+
+        // 옮겨진 Static 강체는 반영이 끝났으므로 다시 재운다.
+        for (&handle, body) in self.bodies.iter_mut() {
+            if body.body_type == BodyType::Static && !body.is_sleeping() {
+                body.sleep();
+                self.collision_world.set_sleeping(handle, true);
+            }
+        }
+
+        let contacts = self.collision_world.contacts().map(|(a, b, _)| (a, b));
+        self.islands = build_islands(&self.bodies, contacts).into_iter()
+            .map(|bodies| {
+                let sleeping = settings.enabled && !bodies.iter().any(|handle| active.contains(handle));
+                for &handle in bodies.iter() {
+                    let Some(body) = self.bodies.get_mut(&handle) else {
+                        continue;
+                    };
+                    if sleeping && !body.is_sleeping() {
+                        body.sleep();
+                        self.collision_world.set_sleeping(handle, true);
+                    } else if !sleeping && body.is_sleeping() {
+                        body.wake_up();
+                        self.collision_world.set_sleeping(handle, false);
+                    }
+                }
+                Island {
+                    bodies,
+                    sleeping,
+                }
+            })
+            .collect();
+    }
+}
@@ -0,0 +1,244 @@
+use crate::ColliderShape;
+use super::SleepSettings;
+
+
+/// 강체의 종류
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BodyType {
+    /// 움직이지 않는다. (질량 무한대)
+    Static,
+    /// 속도대로 움직이지만 힘과 충격량의 영향을 받지 않는다. (질량 무한대)
+    Kinematic,
+    /// 힘과 충격량에 따라 움직인다.
+    Dynamic,
+}
+
+/// 강체  
+/// 위치는 충돌체 도형의 중심이며, 관성 텐서는 도형의 로컬 축 기준 대각 성분만 가진다.  
+#[derive(Debug, Clone)]
+pub struct RigidBody {
+    pub body_type: BodyType,
+    pub linear_velocity: glam::Vec3A,
+    pub angular_velocity: glam::Vec3A,
+    /// 반발 계수 (0 ~ 1)
+    pub restitution: f32,
+    /// 마찰 계수
+    pub friction: f32,
+    /// 초당 감쇠 비율
+    pub linear_damping: f32,
+    pub angular_damping: f32,
+    pub gravity_scale: f32,
+    position: glam::Vec3A,
+    orientation: glam::Quat,
+    mass: f32,
+    inverse_mass: f32,
+    local_inertia: glam::Vec3A,
+    inverse_local_inertia: glam::Vec3A,
+    force: glam::Vec3A,
+    torque: glam::Vec3A,
+    sleeping: bool,
+    /// 속도가 잠들기 기준 아래로 유지된 시간
+    sleep_time: f32,
+}
+
+impl RigidBody {
+    /// shape과 mass로부터 관성 텐서를 구한다.  
+    /// Static, Kinematic 강체는 mass와 관계없이 질량이 무한대로 취급된다.  
+    pub fn new(body_type: BodyType, shape: &ColliderShape, mass: f32) -> Self {
+        let mass = mass.max(f32::EPSILON);
+        let (local_inertia, orientation) = match shape {
+            ColliderShape::Box(bounding_box) => {
+                // 모서리 길이가 2e인 직육면체: I = m / 3 * (e_j^2 + e_k^2)
+                let e = glam::Vec3A::from(bounding_box.extents()).powf(2.0);
+                let inertia = glam::Vec3A::new(e.y + e.z, e.x + e.z, e.x + e.y) * (mass / 3.0);
+                let orientation = bounding_box.rotation()
+                    .map_or(glam::Quat::IDENTITY, |rotation| glam::Quat::from_mat3(&rotation));
+                (inertia, orientation)
+            }
+            ColliderShape::Sphere(sphere) => {
+                (glam::Vec3A::splat(0.4 * mass * sphere.radius.powi(2)), glam::Quat::IDENTITY)
+            }
+        };
+
+        let is_dynamic = body_type == BodyType::Dynamic;
+        Self {
+            body_type,
+            linear_velocity: glam::Vec3A::ZERO,
+            angular_velocity: glam::Vec3A::ZERO,
+            restitution: 0.0,
+            friction: 0.5,
+            linear_damping: 0.0,
+            angular_damping: 0.05,
+            gravity_scale: 1.0,
+            position: glam::Vec3A::from(shape.center()),
+            orientation,
+            mass,
+            inverse_mass: if is_dynamic { 1.0 / mass } else { 0.0 },
+            local_inertia,
+            inverse_local_inertia: if is_dynamic {
+                local_inertia.max(glam::Vec3A::splat(f32::EPSILON)).recip()
+            } else {
+                glam::Vec3A::ZERO
+            },
+            force: glam::Vec3A::ZERO,
+            torque: glam::Vec3A::ZERO,
+            sleeping: false,
+            sleep_time: 0.0,
+        }
+    }
+
+    pub fn with_restitution(mut self, restitution: f32) -> Self {
+        self.restitution = restitution;
+        self
+    }
+
+    pub fn with_friction(mut self, friction: f32) -> Self {
+        self.friction = friction;
+        self
+    }
+
+    pub fn with_linear_velocity(mut self, velocity: glam::Vec3A) -> Self {
+        self.linear_velocity = velocity;
+        self
+    }
+
+    pub fn with_angular_velocity(mut self, velocity: glam::Vec3A) -> Self {
+        self.angular_velocity = velocity;
+        self
+    }
+
+    pub fn is_dynamic(&self) -> bool {
+        self.body_type == BodyType::Dynamic
+    }
+
+    pub fn mass(&self) -> f32 {
+        self.mass
+    }
+
+    /// 질량의 역수 (Dynamic이 아니면 0)
+    pub fn inverse_mass(&self) -> f32 {
+        self.inverse_mass
+    }
+
+    /// 로컬 축 기준 관성 텐서의 대각 성분
+    pub fn local_inertia(&self) -> glam::Vec3A {
+        self.local_inertia
+    }
+
+    /// 월드 공간의 관성 텐서의 역행렬 (Dynamic이 아니면 0)
+    pub fn world_inverse_inertia(&self) -> glam::Mat3A {
+        let rotation = glam::Mat3A::from_quat(self.orientation);
+        rotation * glam::Mat3A::from_diagonal(self.inverse_local_inertia.into()) * rotation.transpose()
+    }
+
+    pub fn position(&self) -> glam::Vec3A {
+        self.position
+    }
+
+    pub fn set_position(&mut self, position: glam::Vec3A) {
+        self.position = position;
+    }
+
+    pub fn orientation(&self) -> glam::Quat {
+        self.orientation
+    }
+
+    pub fn set_orientation(&mut self, orientation: glam::Quat) {
+        self.orientation = orientation.normalize();
+    }
+
+    pub fn is_sleeping(&self) -> bool {
+        self.sleeping
+    }
+
+    /// 깨우고, 잠들기까지의 시간을 처음부터 다시 잰다.
+    pub fn wake_up(&mut self) {
+        self.sleeping = false;
+        self.sleep_time = 0.0;
+    }
+
+    /// 속도와 누적된 힘을 버리고 재운다.
+    pub fn sleep(&mut self) {
+        self.sleeping = true;
+        self.linear_velocity = glam::Vec3A::ZERO;
+        self.angular_velocity = glam::Vec3A::ZERO;
+        self.force = glam::Vec3A::ZERO;
+        self.torque = glam::Vec3A::ZERO;
+    }
+
+    pub(crate) fn sleep_time(&self) -> f32 {
+        self.sleep_time
+    }
+
+    /// 속도가 기준 아래이면 잠들기까지의 시간을 늘리고, 아니면 처음부터 다시 잰다.
+    pub(crate) fn update_sleep_time(&mut self, dt: f32, settings: &SleepSettings) {
+        if self.linear_velocity.length_squared() < settings.linear_threshold.powi(2)
+            && self.angular_velocity.length_squared() < settings.angular_threshold.powi(2)
+        {
+            self.sleep_time += dt;
+        } else {
+            self.sleep_time = 0.0;
+        }
+    }
+
+    /// 강체 위의 점(월드 좌표)의 속도
+    pub fn velocity_at(&self, point: &glam::Vec3A) -> glam::Vec3A {
+        self.linear_velocity + self.angular_velocity.cross(point - self.position)
+    }
+
+    /// 다음 integrate_velocity까지 작용하는 힘 (질량 중심에 작용)
+    pub fn apply_force(&mut self, force: &glam::Vec3A) {
+        self.force += *force;
+    }
+
+    /// 다음 integrate_velocity까지 작용하는 힘 (point에 작용)
+    pub fn apply_force_at(&mut self, force: &glam::Vec3A, point: &glam::Vec3A) {
+        self.force += *force;
+        self.torque += (point - self.position).cross(*force);
+    }
+
+    pub fn apply_torque(&mut self, torque: &glam::Vec3A) {
+        self.torque += *torque;
+    }
+
+    /// point(월드 좌표)에 충격량을 가해 속도를 바로 바꾼다.
+    pub fn apply_impulse_at(&mut self, impulse: &glam::Vec3A, point: &glam::Vec3A) {
+        self.linear_velocity += *impulse * self.inverse_mass;
+        self.angular_velocity += self.world_inverse_inertia() * (point - self.position).cross(*impulse);
+    }
+
+    /// 중력과 누적된 힘으로 속도를 갱신하고, 누적된 힘을 비운다. (semi-implicit Euler의 첫 단계)
+    pub fn integrate_velocity(&mut self, dt: f32, gravity: &glam::Vec3A) {
+        if self.is_dynamic() {
+            let acceleration = *gravity * self.gravity_scale + self.force * self.inverse_mass;
+            self.linear_velocity += acceleration * dt;
+            self.angular_velocity += self.world_inverse_inertia() * self.torque * dt;
+            self.linear_velocity *= 1.0 / (1.0 + self.linear_damping * dt);
+            self.angular_velocity *= 1.0 / (1.0 + self.angular_damping * dt);
+        }
+        self.force = glam::Vec3A::ZERO;
+        self.torque = glam::Vec3A::ZERO;
+    }
+
+    /// 갱신된 속도로 위치와 방향을 옮긴다. (semi-implicit Euler의 두 번째 단계)
+    pub fn integrate_position(&mut self, dt: f32) {
+        if self.body_type == BodyType::Static {
+            return;
+        }
+        self.position += self.linear_velocity * dt;
+
+        // dq/dt = 0.5 * w * q
+        let spin = glam::Quat::from_xyzw(self.angular_velocity.x, self.angular_velocity.y, self.angular_velocity.z, 0.0);
+        self.orientation = (self.orientation + spin * self.orientation * (0.5 * dt)).normalize();
+    }
+
+    /// 현재 위치와 방향을 shape에 반영한다. (구는 방향을 무시한다.)
+    pub fn apply_to_shape(&self, shape: &mut ColliderShape) {
+        shape.set_center(glam::Vec3::from(self.position));
+        if let ColliderShape::Box(bounding_box) = shape
+            && (bounding_box.rotation().is_some() || self.orientation != glam::Quat::IDENTITY)
+        {
+            bounding_box.set_rotation(glam::Mat3::from_quat(self.orientation));
+        }
+    }
+}
@@ -0,0 +1,219 @@
+use super::{ContactManifold, RigidBody};
+
+
+/// 접촉 솔버의 설정
+#[derive(Debug, Clone, Copy)]
+pub struct SolverSettings {
+    /// 속도 제약을 반복해서 푸는 횟수
+    pub iterations: usize,
+    /// 한 step에 침투를 되돌리는 비율 (Baumgarte 안정화, 0 ~ 1)
+    pub baumgarte: f32,
+    /// 이 깊이까지의 침투는 허용하여 떨림을 막는다.
+    pub penetration_slop: f32,
+    /// 이보다 느리게 부딪히면 튕기지 않는다.
+    pub restitution_threshold: f32,
+}
+
+impl Default for SolverSettings {
+    fn default() -> Self {
+        Self {
+            iterations: 10,
+            baumgarte: 0.2,
+            penetration_slop: 0.005,
+            restitution_threshold: 0.5,
+        }
+    }
+}
+
+/// 솔버가 다루는 강체의 속도 상태  
+/// Static, Kinematic 강체와 강체가 없는 충돌체는 질량이 무한대이다.  
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct SolverBody {
+    pub position: glam::Vec3A,
+    pub linear_velocity: glam::Vec3A,
+    pub angular_velocity: glam::Vec3A,
+    pub inverse_mass: f32,
+    pub inverse_inertia: glam::Mat3A,
+    pub restitution: f32,
+    pub friction: f32,
+}
+
+impl SolverBody {
+    pub fn new(body: &RigidBody) -> Self {
+        Self {
+            position: body.position(),
+            linear_velocity: body.linear_velocity,
+            angular_velocity: body.angular_velocity,
+            inverse_mass: body.inverse_mass(),
+            inverse_inertia: body.world_inverse_inertia(),
+            restitution: body.restitution,
+            friction: body.friction,
+        }
+    }
+
+    /// 강체가 없는 충돌체
+    pub fn fixed(position: glam::Vec3A) -> Self {
+        Self {
+            position,
+            linear_velocity: glam::Vec3A::ZERO,
+            angular_velocity: glam::Vec3A::ZERO,
+            inverse_mass: 0.0,
+            inverse_inertia: glam::Mat3A::ZERO,
+            restitution: 0.0,
+            friction: 0.5,
+        }
+    }
+
+    fn velocity_at(&self, r: &glam::Vec3A) -> glam::Vec3A {
+        self.linear_velocity + self.angular_velocity.cross(*r)
+    }
+
+    fn apply_impulse(&mut self, impulse: &glam::Vec3A, r: &glam::Vec3A) {
+        self.linear_velocity += *impulse * self.inverse_mass;
+        self.angular_velocity += self.inverse_inertia * r.cross(*impulse);
+    }
+
+    /// r에서 direction 방향으로 단위 충격량을 가했을 때의 속도 변화량
+    fn response(&self, r: &glam::Vec3A, direction: &glam::Vec3A) -> f32 {
+        let angular = (self.inverse_inertia * r.cross(*direction)).cross(*r);
+        self.inverse_mass + direction.dot(angular)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct PointConstraint {
+    manifold: usize,
+    point: usize,
+    body_a: usize,
+    body_b: usize,
+    r_a: glam::Vec3A,
+    r_b: glam::Vec3A,
+    normal: glam::Vec3A,
+    tangents: [glam::Vec3A; 2],
+    normal_mass: f32,
+    tangent_mass: [f32; 2],
+    /// 침투 보정과 반발을 위한 목표 분리 속도
+    target_velocity: f32,
+    friction: f32,
+    normal_impulse: f32,
+    tangent_impulse: [f32; 2],
+}
+
+/// Sequential impulse 방식의 접촉 솔버  
+/// 접촉점마다 법선 방향 충격량(0 이상)과 두 접선 방향의 마찰 충격량(Coulomb 원뿔을 상자로 근사)을  
+/// 누적 충격량을 기준으로 잘라가며 반복해서 푼다.  
+pub(crate) fn solve_contacts(
+    bodies: &mut [SolverBody],
+    manifolds: &mut [ContactManifold],
+    manifold_bodies: &[(usize, usize)],
+    settings: &SolverSettings,
+    dt: f32,
+) {
+    // 1. 접촉점마다 유효 질량과 목표 속도를 미리 구한다.
+    let mut constraints = Vec::new();
+    for (m, manifold) in manifolds.iter().enumerate() {
+        let (body_a, body_b) = manifold_bodies[m];
+        let (a, b) = (&bodies[body_a], &bodies[body_b]);
+        let normal = manifold.normal;
+        let tangents = tangent_basis(&normal);
+        let restitution = a.restitution.max(b.restitution);
+        let friction = (a.friction * b.friction).sqrt();
+
+        for (p, point) in manifold.points.iter().enumerate() {
+            let r_a = point.position - a.position;
+            let r_b = point.position - b.position;
+            let normal_mass = a.response(&r_a, &normal) + b.response(&r_b, &normal);
+            let tangent_mass = tangents.map(|t| a.response(&r_a, &t) + b.response(&r_b, &t));
+            if normal_mass <= 0.0 {
+                continue;   // 두 강체 모두 질량이 무한대
+            }
+
+            let relative_velocity = normal.dot(a.velocity_at(&r_a) - b.velocity_at(&r_b));
+            let mut target_velocity = settings.baumgarte / dt * (point.penetration - settings.penetration_slop).max(0.0);
+            if relative_velocity < -settings.restitution_threshold {
+                target_velocity = target_velocity.max(-restitution * relative_velocity);
+            }
+
+            constraints.push(PointConstraint {
+                manifold: m,
+                point: p,
+                body_a,
+                body_b,
+                r_a,
+                r_b,
+                normal,
+                tangents,
+                normal_mass: 1.0 / normal_mass,
+                tangent_mass: tangent_mass.map(|k| if k > 0.0 { 1.0 / k } else { 0.0 }),
+                target_velocity,
+                friction,
+                normal_impulse: point.normal_impulse,
+                tangent_impulse: point.tangent_impulse,
+            });
+        }
+    }
+
+    // 2. 지난 step에서 이어진 접촉점은 그때의 충격량을 먼저 가한다. (warm starting)
+    for constraint in constraints.iter() {
+        let (a, b) = pair_mut(bodies, constraint.body_a, constraint.body_b);
+        let impulse = constraint.normal * constraint.normal_impulse
+            + constraint.tangents[0] * constraint.tangent_impulse[0]
+            + constraint.tangents[1] * constraint.tangent_impulse[1];
+        a.apply_impulse(&impulse, &constraint.r_a);
+        b.apply_impulse(&-impulse, &constraint.r_b);
+    }
+
+    // 3. 반복해서 푼다.
+    for _ in 0..settings.iterations {
+        for constraint in constraints.iter_mut() {
+            let (a, b) = pair_mut(bodies, constraint.body_a, constraint.body_b);
+
+            // 마찰: 현재 법선 충격량에 비례하는 범위로 자른다.
+            let max_friction = constraint.friction * constraint.normal_impulse;
+            for i in 0..2 {
+                let tangent = constraint.tangents[i];
+                let relative_velocity = tangent.dot(a.velocity_at(&constraint.r_a) - b.velocity_at(&constraint.r_b));
+                let lambda = -relative_velocity * constraint.tangent_mass[i];
+                let previous = constraint.tangent_impulse[i];
+                constraint.tangent_impulse[i] = (previous + lambda).clamp(-max_friction, max_friction);
+                let impulse = tangent * (constraint.tangent_impulse[i] - previous);
+                a.apply_impulse(&impulse, &constraint.r_a);
+                b.apply_impulse(&-impulse, &constraint.r_b);
+            }
+
+            // 법선: 밀어내는 방향으로만
+            let relative_velocity = constraint.normal.dot(a.velocity_at(&constraint.r_a) - b.velocity_at(&constraint.r_b));
+            let lambda = (constraint.target_velocity - relative_velocity) * constraint.normal_mass;
+            let previous = constraint.normal_impulse;
+            constraint.normal_impulse = (previous + lambda).max(0.0);
+            let impulse = constraint.normal * (constraint.normal_impulse - previous);
+            a.apply_impulse(&impulse, &constraint.r_a);
+            b.apply_impulse(&-impulse, &constraint.r_b);
+        }
+    }
+
+    // 4. 누적 충격량을 매니폴드에 기록한다. (다음 step의 warm starting에 사용)
+    for constraint in constraints.iter() {
+        let point = &mut manifolds[constraint.manifold].points[constraint.point];
+        point.normal_impulse = constraint.normal_impulse;
+        point.tangent_impulse = constraint.tangent_impulse;
+    }
+}
+
+/// normal에 수직인 두 단위 벡터
+fn tangent_basis(normal: &glam::Vec3A) -> [glam::Vec3A; 2] {
+    let (t1, t2) = normal.any_orthonormal_pair();
+    [t1, t2]
+}
+
+/// 서로 다른 두 원소의 가변 참조  
+/// 질량이 무한대인 강체끼리는 제약이 만들어지지 않으므로 a와 b는 항상 다르다.  
+fn pair_mut(bodies: &mut [SolverBody], a: usize, b: usize) -> (&mut SolverBody, &mut SolverBody) {
+    if a < b {
+        let (left, right) = bodies.split_at_mut(b);
+        (&mut left[a], &mut right[0])
+    } else {
+        let (left, right) = bodies.split_at_mut(a);
+        (&mut right[0], &mut left[b])
+    }
+}
@@ -0,0 +1,81 @@
+use crate::{BoundingBox, Sphere};
+
+
+/// normal · p = distance 인 평면  
+/// normal이 향하는 쪽이 양수이다.  
+#[derive(Debug, Clone, Copy)]
+pub struct Plane {
+    pub normal: glam::Vec3A,
+    pub distance: f32,
+}
+
+impl Plane {
+    /// normal을 정규화하여 평면을 만든다.
+    pub fn new(normal: glam::Vec3A, distance: f32) -> Self {
+        let length = normal.length();
+        Self {
+            normal: normal / length,
+            distance: distance / length,
+        }
+    }
+
+    /// 점과 평면 사이의 부호 있는 거리 (normal 방향이 양수)
+    pub fn signed_distance(&self, point: &glam::Vec3A) -> f32 {
+        self.normal.dot(*point) - self.distance
+    }
+}
+
+
+/// 6개의 평면으로 이루어진 절두체  
+/// 각 평면의 normal은 절두체 안쪽을 향한다.  
+#[derive(Debug, Clone, Copy)]
+pub struct Frustum {
+    pub planes: [Plane; 6],
+}
+
+impl Frustum {
+    /// view-projection 행렬에서 절두체를 추출한다. (Gribb-Hartmann, 깊이 범위 0..1)
+    pub fn from_view_projection(view_projection: &glam::Mat4) -> Self {
+        let row = |i| view_projection.row(i);
+        let (r0, r1, r2, r3) = (row(0), row(1), row(2), row(3));
+        let plane = |v: glam::Vec4| Plane::new(glam::Vec3A::from(v.truncate()), -v.w);
+        Self {
+            planes: [
+                plane(r3 + r0), // left
+                plane(r3 - r0), // right
+                plane(r3 + r1), // bottom
+                plane(r3 - r1), // top
+                plane(r2),      // near
+                plane(r3 - r2), // far
+            ],
+        }
+    }
+
+    pub fn contains_point(&self, point: &glam::Vec3A) -> bool {
+        self.planes.iter().all(|plane| plane.signed_distance(point) >= 0.0)
+    }
+
+    /// 박스가 절두체와 겹치는지 보수적으로 검사한다.  
+    /// (모서리 근처에서는 겹치지 않는데도 true를 반환할 수 있다.)  
+    pub fn intersects_box(&self, boundingbox: &BoundingBox) -> bool {
+        let center = glam::Vec3A::from(boundingbox.center);
+        let extents = glam::Vec3A::from(boundingbox.extents());
+        let axes = match boundingbox.rotation() {
+            Some(rotation) => [rotation.x_axis, rotation.y_axis, rotation.z_axis].map(glam::Vec3A::from),
+            None => [glam::Vec3A::X, glam::Vec3A::Y, glam::Vec3A::Z],
+        };
+
+        self.planes.iter().all(|plane| {
+            // 평면의 normal 방향으로 투영한 박스의 반지름
+            let radius = extents.x * plane.normal.dot(axes[0]).abs()
+                + extents.y * plane.normal.dot(axes[1]).abs()
+                + extents.z * plane.normal.dot(axes[2]).abs();
+            plane.signed_distance(&center) >= -radius
+        })
+    }
+
+    pub fn intersects_sphere(&self, sphere: &Sphere) -> bool {
+        let center = glam::Vec3A::from(sphere.center);
+        self.planes.iter().all(|plane| plane.signed_distance(&center) >= -sphere.radius)
+    }
+}
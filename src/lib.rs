@@ -1,7 +1,21 @@
 mod collision;
+mod broadphase;
 mod bounds;
 mod sphere;
+mod capsule;
+mod triangle;
+mod ray;
+mod frustum;
+mod world;
+mod dynamics;
 
 pub use collision::*;
+pub use broadphase::*;
 pub use bounds::*;
 pub use sphere::*;
+pub use capsule::*;
+pub use triangle::*;
+pub use ray::*;
+pub use frustum::*;
+pub use world::*;
+pub use dynamics::*;
@@ -1,11 +1,21 @@
 use collision_test::{
     static_collision::StaticCollision, 
-    dynamic_collision::DynamicCollision,
     *
 };
 
 
 const LOOP_COUNT: usize = 10;
+const BROADPHASE_COUNT: usize = 1_000_000;
+/// broadphase 박스는 -500 ~ 500 범위에 놓는다. -50 ~ 50에 100만 개를 넣으면 겹침 쌍이 12억 개 정도가 된다. (README 7차)
+const BROADPHASE_RANGE: f32 = 1000.0;
+/// Brute-force는 이 개수의 박스만 모든 박스와 비교하고, 전체 시간과 쌍의 수는 비율로 추정한다.
+const BRUTE_FORCE_SAMPLE_COUNT: usize = 100;
+/// EPA는 겹친 쌍에서만 실행되므로 겹친 쌍만 따로 만들어 측정한다.
+const EPA_PAIR_COUNT: usize = 100_000;
+/// GJK warm start는 쌍마다 여러 프레임을 움직이며 측정한다. (README 8차)
+const WARM_START_PAIR_COUNT: usize = 10_000;
+const WARM_START_FRAME_COUNT: usize = 60;
+
 
 
 fn main() {
@@ -228,6 +238,106 @@ fn main() {
             avg_fps /= LOOP_COUNT as f32;
             println!("OBB vs OBB (GJK-EPA) avg: {:<8.2?} (fps: {})", avg_elapsed, avg_fps);
         }
+
+        // main_collider의 정점, 축, AABB를 한 번만 계산한다.
+        let prepared_collider = PreparedBox::from(&main_collider);
+
+        {
+            let mut avg_elapsed = std::time::Duration::new(0, 0);
+            let mut avg_fps = 0.0;
+            for _ in 0..LOOP_COUNT {
+                let start = std::time::Instant::now();
+
+                for collider in colliders.iter() {
+                    let _ = prepared_collider.check_static_collision(collider);
+                }
+
+                let elapsed = start.elapsed();
+                let fps = 1.0 / elapsed.as_secs_f32();
+                avg_elapsed += elapsed;
+                avg_fps += fps;
+                println!("OBB vs OBB (SAT-prepared): {:<8.2?} (fps: {})", elapsed, fps);
+            }
+            avg_elapsed /= LOOP_COUNT as u32;
+            avg_fps /= LOOP_COUNT as f32;
+            println!("OBB vs OBB (SAT-prepared) avg: {:<8.2?} (fps: {})", avg_elapsed, avg_fps);
+        }
+
+        {
+            let mut avg_elapsed = std::time::Duration::new(0, 0);
+            let mut avg_fps = 0.0;
+            for _ in 0..LOOP_COUNT {
+                let start = std::time::Instant::now();
+
+                for collider in colliders.iter() {
+                    let _ = prepared_collider.check_static_collision_details(collider);
+                }
+
+                let elapsed = start.elapsed();
+                let fps = 1.0 / elapsed.as_secs_f32();
+                avg_elapsed += elapsed;
+                avg_fps += fps;
+                println!("OBB vs OBB (SAT-with normal-prepared): {:<8.2?} (fps: {})", elapsed, fps);
+            }
+            avg_elapsed /= LOOP_COUNT as u32;
+            avg_fps /= LOOP_COUNT as f32;
+            println!("OBB vs OBB (SAT-with normal-prepared) avg: {:<8.2?} (fps: {})", avg_elapsed, avg_fps);
+        }
+
+        {
+            let mut avg_elapsed = std::time::Duration::new(0, 0);
+            let mut avg_fps = 0.0;
+            for _ in 0..LOOP_COUNT {
+                let start = std::time::Instant::now();
+
+                for collider in colliders.iter() {
+                    let _ = prepared_collider.gjk_epa(collider);
+                }
+
+                let elapsed = start.elapsed();
+                let fps = 1.0 / elapsed.as_secs_f32();
+                avg_elapsed += elapsed;
+                avg_fps += fps;
+                println!("OBB vs OBB (GJK-EPA-prepared): {:<8.2?} (fps: {})", elapsed, fps);
+            }
+            avg_elapsed /= LOOP_COUNT as u32;
+            avg_fps /= LOOP_COUNT as f32;
+            println!("OBB vs OBB (GJK-EPA-prepared) avg: {:<8.2?} (fps: {})", avg_elapsed, avg_fps);
+        }
+
+        // GJK-EPA가 SAT보다 느린 이유를 보기 위해, 질의 하나당 한 일의 양을 센다.
+        {
+            let config = GjkConfig::default();
+            let mut diagnostics = GjkDiagnostics::default();
+            let mut total = GjkDiagnostics::default();
+            let mut hits = 0;
+            let mut terminations = std::collections::HashMap::new();
+            for collider in colliders.iter() {
+                let _ = main_collider.gjk_epa_with_diagnostics(collider, &config, &mut diagnostics);
+                total.gjk_iterations += diagnostics.gjk_iterations;
+                total.support_calls += diagnostics.support_calls;
+                if diagnostics.epa_iterations > 0 {
+                    hits += 1;
+                    total.epa_iterations += diagnostics.epa_iterations;
+                    total.polytope_faces += diagnostics.polytope_faces;
+                }
+                *terminations.entry(diagnostics.termination).or_insert(0) += 1;
+            }
+
+            let queries = colliders.len() as f32;
+            let hits_or_one = hits.max(1) as f32;
+            println!(
+                "OBB vs OBB (GJK-EPA) per query: support calls {:.2}, GJK iterations {:.2}",
+                total.support_calls as f32 / queries, total.gjk_iterations as f32 / queries,
+            );
+            println!(
+                "OBB vs OBB (GJK-EPA) per hit ({}): EPA iterations {:.2}, polytope faces {:.2}",
+                hits, total.epa_iterations as f32 / hits_or_one, total.polytope_faces as f32 / hits_or_one,
+            );
+            let mut terminations = terminations.into_iter().collect::<Vec<_>>();
+            terminations.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+            println!("OBB vs OBB (GJK-EPA) terminations: {:?}", terminations);
+        }
     }
 
     println!();
@@ -341,5 +451,254 @@ fn main() {
             avg_fps /= LOOP_COUNT as f32;
             println!("OBB vs Sphere (GJK-EPA) avg: {:<8.2?} (fps: {})", avg_elapsed, avg_fps);
         }
+
+        {
+            let config = GjkConfig::default();
+            let mut avg_elapsed = std::time::Duration::new(0, 0);
+            let mut avg_fps = 0.0;
+            for _ in 0..LOOP_COUNT {
+                let start = std::time::Instant::now();
+
+                for collider in colliders.iter() {
+                    let _ = main_collider.gjk_epa_with_margin(collider, &config);
+                }
+
+                let elapsed = start.elapsed();
+                let fps = 1.0 / elapsed.as_secs_f32();
+                avg_elapsed += elapsed;
+                avg_fps += fps;
+                println!("OBB vs Sphere (GJK-margin): {:<8.2?} (fps: {})", elapsed, fps);
+            }
+            avg_elapsed /= LOOP_COUNT as u32;
+            avg_fps /= LOOP_COUNT as f32;
+            println!("OBB vs Sphere (GJK-margin) avg: {:<8.2?} (fps: {})", avg_elapsed, avg_fps);
+        }
+    }
+
+    println!();
+
+    // 4. Broadphase: Sweep and Prune vs Brute-force (AABB vs AABB)
+    {
+        let mut colliders = Vec::new();
+        let mut velocities = Vec::new();
+        for _ in 0..BROADPHASE_COUNT {
+            let x = (rand::random::<f32>() - 0.5) * BROADPHASE_RANGE;
+            let y = (rand::random::<f32>() - 0.5) * BROADPHASE_RANGE;
+            let z = (rand::random::<f32>() - 0.5) * BROADPHASE_RANGE;
+            let ex = rand::random::<f32>() * 5.0 + 1.0;
+            let ey = rand::random::<f32>() * 5.0 + 1.0;
+            let ez = rand::random::<f32>() * 5.0 + 1.0;
+            let collider = BoundingBox::new(
+                glam::Vec3::new(x, y, z),
+                glam::Vec3::new(ex, ey, ez),
+            );
+            colliders.push(collider);
+
+            // 매 프레임 조금씩 움직인다.
+            let vx = rand::random::<f32>() * 0.2 - 0.1;
+            let vy = rand::random::<f32>() * 0.2 - 0.1;
+            let vz = rand::random::<f32>() * 0.2 - 0.1;
+            velocities.push(glam::Vec3::new(vx, vy, vz));
+        }
+
+        // SingleAxis는 매 프레임 처음 정렬만큼 전체를 다시 훑으므로 MultiAxis만 측정한다.
+        // 쌍과 이벤트는 모으지 않고 개수만 센다.
+        let mut sap = SweepAndPrune::new(SweepMode::MultiAxis);
+        let ids = colliders.iter()
+            .map(|collider| sap.insert(collider, ()))
+            .collect::<Vec<_>>();
+
+        let start = std::time::Instant::now();
+        let mut events = 0;
+        sap.update_pairs_with(|_| events += 1);
+        println!("Broadphase build: {:<8.2?} (pairs: {}, events: {})", start.elapsed(), sap.pair_count(), events);
+
+        // 표본의 비교 횟수는 BRUTE_FORCE_SAMPLE_COUNT * (n - 1), 전체는 n * (n - 1) / 2
+        let brute_scale = BROADPHASE_COUNT as f64 / (2 * BRUTE_FORCE_SAMPLE_COUNT) as f64;
+        let mut avg_sap = std::time::Duration::new(0, 0);
+        let mut avg_brute = std::time::Duration::new(0, 0);
+        for _ in 0..LOOP_COUNT {
+            for (collider, velocity) in colliders.iter_mut().zip(velocities.iter()) {
+                collider.center += *velocity;
+            }
+
+            let start = std::time::Instant::now();
+            for (id, collider) in ids.iter().zip(colliders.iter()) {
+                sap.update(*id, collider);
+            }
+            let mut events = 0;
+            sap.update_pairs_with(|_| events += 1);
+            let sap_elapsed = start.elapsed();
+
+            let start = std::time::Instant::now();
+            let mut brute_count = 0;
+            for i in 0..BRUTE_FORCE_SAMPLE_COUNT {
+                for j in 0..colliders.len() {
+                    if i != j && colliders[i].aabb_collision(&colliders[j]) {
+                        brute_count += 1;
+                    }
+                }
+            }
+            let brute_elapsed = start.elapsed().mul_f64(brute_scale);
+
+            avg_sap += sap_elapsed;
+            avg_brute += brute_elapsed;
+            println!(
+                "Broadphase: SAP {:<8.2?} / Brute-force {:<8.2?} (추정) (pairs: {} / {:.0} (추정), events: {})",
+                sap_elapsed, brute_elapsed, sap.pair_count(), brute_count as f64 * brute_scale, events,
+            );
+        }
+        avg_sap /= LOOP_COUNT as u32;
+        avg_brute /= LOOP_COUNT as u32;
+        println!("Broadphase avg: SAP {:<8.2?} / Brute-force {:<8.2?} (추정)", avg_sap, avg_brute);
+    }
+
+    println!();
+
+    // 5. GJK: cold start vs warm start (OBB vs OBB, 매 프레임 조금씩 움직이는 쌍)
+    {
+        let mut pairs = Vec::new();
+        let mut velocities = Vec::new();
+        for _ in 0..WARM_START_PAIR_COUNT {
+            let random_box = |center: glam::Vec3| {
+                let extents = glam::Vec3::new(
+                    rand::random::<f32>() + 0.5,
+                    rand::random::<f32>() + 0.5,
+                    rand::random::<f32>() + 0.5,
+                );
+                let rotation = glam::Mat3::from_euler(
+                    glam::EulerRot::XYZ,
+                    rand::random::<f32>() * std::f32::consts::TAU,
+                    rand::random::<f32>() * std::f32::consts::TAU,
+                    rand::random::<f32>() * std::f32::consts::TAU,
+                );
+                BoundingBox::new_rotated(center, extents, rotation)
+            };
+            // 닿을락 말락 한 거리에 놓는다.
+            let offset = glam::Vec3::new(
+                rand::random::<f32>() * 2.0 - 1.0,
+                rand::random::<f32>() * 2.0 - 1.0,
+                rand::random::<f32>() * 2.0 - 1.0,
+            ).normalize_or(glam::Vec3::X) * (rand::random::<f32>() * 2.0 + 1.5);
+            pairs.push((random_box(glam::Vec3::ZERO), random_box(offset)));
+
+            let vx = rand::random::<f32>() * 0.02 - 0.01;
+            let vy = rand::random::<f32>() * 0.02 - 0.01;
+            let vz = rand::random::<f32>() * 0.02 - 0.01;
+            velocities.push(glam::Vec3::new(vx, vy, vz));
+        }
+        let config = GjkConfig::default();
+
+        let mut cold_elapsed = std::time::Duration::new(0, 0);
+        let mut warm_elapsed = std::time::Duration::new(0, 0);
+        let mut cold_supports = 0;
+        let mut warm_supports = 0;
+        let mut hits = 0;
+        let mut caches = vec![GjkCache::default(); pairs.len()];
+        let mut count_caches = caches.clone();
+        let mut diagnostics = GjkDiagnostics::default();
+        for _ in 0..WARM_START_FRAME_COUNT {
+            for ((_, b), velocity) in pairs.iter_mut().zip(velocities.iter()) {
+                b.center += *velocity;
+            }
+
+            let start = std::time::Instant::now();
+            for (a, b) in pairs.iter() {
+                let _ = a.gjk_with_config(b, &config);
+            }
+            cold_elapsed += start.elapsed();
+
+            let start = std::time::Instant::now();
+            for ((a, b), cache) in pairs.iter().zip(caches.iter_mut()) {
+                let _ = a.gjk_with_cache(b, &config, cache);
+            }
+            warm_elapsed += start.elapsed();
+
+            // 시간 측정과 별도로 support point를 구한 횟수를 센다.
+            for ((a, b), cache) in pairs.iter().zip(count_caches.iter_mut()) {
+                if let Ok(Some(_)) = a.gjk_with_diagnostics(b, &config, &mut GjkCache::default(), &mut diagnostics) {
+                    hits += 1;
+                }
+                cold_supports += diagnostics.support_calls;
+                let _ = a.gjk_with_diagnostics(b, &config, cache, &mut diagnostics);
+                warm_supports += diagnostics.support_calls;
+            }
+        }
+
+        let queries = (WARM_START_PAIR_COUNT * WARM_START_FRAME_COUNT) as f32;
+        println!(
+            "GJK warm start ({} pairs x {} frames, hit rate: {:.1}%)",
+            WARM_START_PAIR_COUNT, WARM_START_FRAME_COUNT, hits as f32 / queries * 100.0,
+        );
+        println!(
+            "GJK cold start: {:<8.2?} (support calls per query: {:.2})",
+            cold_elapsed / WARM_START_FRAME_COUNT as u32, cold_supports as f32 / queries,
+        );
+        println!(
+            "GJK warm start: {:<8.2?} (support calls per query: {:.2}, {:.1}% fewer)",
+            warm_elapsed / WARM_START_FRAME_COUNT as u32, warm_supports as f32 / queries,
+            (1.0 - warm_supports as f32 / cold_supports as f32) * 100.0,
+        );
+    }
+
+    println!();
+
+    // 6. EPA: 겹친 OBB vs OBB
+    {
+        let random_rotation = || glam::Mat3::from_euler(
+            glam::EulerRot::XYZ,
+            rand::random::<f32>() * std::f32::consts::TAU,
+            rand::random::<f32>() * std::f32::consts::TAU,
+            rand::random::<f32>() * std::f32::consts::TAU,
+        );
+        let random_extents = || glam::Vec3::new(
+            rand::random::<f32>() + 0.5,
+            rand::random::<f32>() + 0.5,
+            rand::random::<f32>() + 0.5,
+        );
+        let mut pairs = Vec::new();
+        while pairs.len() < EPA_PAIR_COUNT {
+            let a = BoundingBox::new_rotated(glam::Vec3::ZERO, random_extents(), random_rotation());
+            let offset = glam::Vec3::new(
+                rand::random::<f32>() * 4.0 - 2.0,
+                rand::random::<f32>() * 4.0 - 2.0,
+                rand::random::<f32>() * 4.0 - 2.0,
+            );
+            let b = BoundingBox::new_rotated(offset, random_extents(), random_rotation());
+            if a.obb_collision(&b) {
+                pairs.push((a, b));
+            }
+        }
+
+        let mut avg_elapsed = std::time::Duration::new(0, 0);
+        for _ in 0..LOOP_COUNT {
+            let start = std::time::Instant::now();
+            for (a, b) in pairs.iter() {
+                let _ = a.gjk_epa(b);
+            }
+            let elapsed = start.elapsed();
+            avg_elapsed += elapsed;
+            println!("EPA OBB vs OBB ({} overlapping pairs): {:<8.2?}", EPA_PAIR_COUNT, elapsed);
+        }
+        avg_elapsed /= LOOP_COUNT as u32;
+        println!("EPA OBB vs OBB ({} overlapping pairs) avg: {:<8.2?}", EPA_PAIR_COUNT, avg_elapsed);
+
+        let config = GjkConfig::default();
+        let mut diagnostics = GjkDiagnostics::default();
+        let mut total = GjkDiagnostics::default();
+        let mut errors = 0;
+        for (a, b) in pairs.iter() {
+            if a.gjk_epa_with_diagnostics(b, &config, &mut diagnostics).is_err() {
+                errors += 1;
+            }
+            total.epa_iterations += diagnostics.epa_iterations;
+            total.polytope_faces += diagnostics.polytope_faces;
+        }
+        println!(
+            "EPA OBB vs OBB per pair: EPA iterations {:.2}, polytope faces {:.2}, errors {}",
+            total.epa_iterations as f32 / EPA_PAIR_COUNT as f32,
+            total.polytope_faces as f32 / EPA_PAIR_COUNT as f32,
+            errors,
+        );
     }
 }
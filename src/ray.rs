@@ -0,0 +1,37 @@
+#[derive(Debug, Clone, Copy)]
+pub struct Ray {
+    pub origin: glam::Vec3A,
+    /// 항상 단위벡터
+    direction: glam::Vec3A,
+}
+
+impl Ray {
+    /// direction은 정규화하여 저장한다.  
+    /// direction이 0인 경우 X축 방향을 사용한다.  
+    pub fn new(origin: glam::Vec3A, direction: glam::Vec3A) -> Self {
+        Self {
+            origin,
+            direction: direction.try_normalize().unwrap_or(glam::Vec3A::X),
+        }
+    }
+
+    pub fn direction(&self) -> glam::Vec3A {
+        self.direction
+    }
+
+    /// origin에서 direction 방향으로 distance만큼 떨어진 점
+    pub fn point_at(&self, distance: f32) -> glam::Vec3A {
+        self.origin + self.direction * distance
+    }
+}
+
+
+#[derive(Debug, Clone, Copy)]
+pub struct RayHit {
+    /// ray의 origin으로부터 충돌 지점까지의 거리
+    pub distance: f32,
+    pub point: glam::Vec3A,
+    /// 충돌 지점에서 도형 표면의 바깥쪽 법선벡터  
+    /// ray가 도형 안에서 시작한 경우 -direction  
+    pub normal: glam::Vec3A,
+}
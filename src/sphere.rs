@@ -1,4 +1,7 @@
-#[derive(Debug, Clone)]
+use crate::BoundingBox;
+
+
+#[derive(Debug, Clone, Copy)]
 pub struct Sphere {
     pub center: glam::Vec3,
     pub radius: f32,
@@ -10,6 +13,11 @@ impl Sphere {
         (point - center).length_squared() <= self.radius.powi(2)
     }
 
+    /// 구를 감싸는 AABB를 반환한다.  
+    pub fn bounding_box(&self) -> BoundingBox {
+        BoundingBox::new(self.center, glam::Vec3::splat(self.radius))
+    }
+
     pub fn inflated(&self, amound: f32) -> Sphere {
         Sphere {
             center: self.center,
@@ -0,0 +1,99 @@
+use crate::BoundingBox;
+
+
+/// 세 정점으로 이루어진 삼각형 (삼각형 메시를 StaticBvh에 넣기 위한 도형)  
+/// 앞뒤 구분 없이 양면으로 취급한다.  
+#[derive(Debug, Clone, Copy)]
+pub struct Triangle {
+    pub vertices: [glam::Vec3A; 3],
+}
+
+impl Triangle {
+    pub fn new(a: glam::Vec3A, b: glam::Vec3A, c: glam::Vec3A) -> Self {
+        Self {
+            vertices: [a, b, c],
+        }
+    }
+
+    /// (b - a) x (c - a) 방향의 단위 법선벡터 (넓이가 0이면 0)
+    pub fn normal(&self) -> glam::Vec3A {
+        let [a, b, c] = self.vertices;
+        (b - a).cross(c - a).normalize_or_zero()
+    }
+
+    /// 삼각형을 감싸는 AABB를 반환한다.
+    pub fn bounding_box(&self) -> BoundingBox {
+        let [a, b, c] = self.vertices;
+        BoundingBox::from_min_max(a.min(b).min(c).into(), a.max(b).max(c).into())
+    }
+
+    /// 삼각형 위에서 point와 가장 가까운 점  
+    /// point가 어느 정점, 모서리, 면의 영역(Voronoi region)에 있는지에 따라 나눠 구한다.  
+    pub fn closest_point(&self, point: &glam::Vec3A) -> glam::Vec3A {
+        let [a, b, c] = self.vertices;
+        let ab = b - a;
+        let ac = c - a;
+
+        // 정점 a의 영역
+        let ap = point - a;
+        let d1 = ab.dot(ap);
+        let d2 = ac.dot(ap);
+        if d1 <= 0.0 && d2 <= 0.0 {
+            return a;
+        }
+
+        // 정점 b의 영역
+        let bp = point - b;
+        let d3 = ab.dot(bp);
+        let d4 = ac.dot(bp);
+        if d3 >= 0.0 && d4 <= d3 {
+            return b;
+        }
+
+        // 모서리 ab의 영역
+        let vc = d1 * d4 - d3 * d2;
+        if vc <= 0.0 && d1 >= 0.0 && d3 <= 0.0 {
+            return a + ab * (d1 / (d1 - d3));
+        }
+
+        // 정점 c의 영역
+        let cp = point - c;
+        let d5 = ab.dot(cp);
+        let d6 = ac.dot(cp);
+        if d6 >= 0.0 && d5 <= d6 {
+            return c;
+        }
+
+        // 모서리 ac의 영역
+        let vb = d5 * d2 - d1 * d6;
+        if vb <= 0.0 && d2 >= 0.0 && d6 <= 0.0 {
+            return a + ac * (d2 / (d2 - d6));
+        }
+
+        // 모서리 bc의 영역
+        let va = d3 * d6 - d5 * d4;
+        if va <= 0.0 && d4 - d3 >= 0.0 && d5 - d6 >= 0.0 {
+            return b + (c - b) * ((d4 - d3) / ((d4 - d3) + (d5 - d6)));
+        }
+
+        // 면의 영역
+        let sum = va + vb + vc;
+        if sum <= 0.0 {
+            // 넓이가 0인 삼각형은 세 모서리 중 가장 가까운 점을 쓴다.
+            return [(a, b), (b, c), (c, a)].into_iter()
+                .map(|(start, end)| closest_point_on_segment(point, start, end))
+                .min_by(|p, q| p.distance_squared(*point).total_cmp(&q.distance_squared(*point)))
+                .unwrap_or(a);
+        }
+        a + ab * (vb / sum) + ac * (vc / sum)
+    }
+}
+
+fn closest_point_on_segment(point: &glam::Vec3A, start: glam::Vec3A, end: glam::Vec3A) -> glam::Vec3A {
+    let segment = end - start;
+    let length_sq = segment.length_squared();
+    if length_sq <= f32::EPSILON {
+        return start;
+    }
+    start + segment * ((point - start).dot(segment) / length_sq).clamp(0.0, 1.0)
+}
@@ -0,0 +1,436 @@
+use crate::{
+    dynamic_collision::{DynamicCollision, SweepConfig},
+    Bounded, BoundingBox, Broadphase, Capsule, CollisionDetails, CollisionWorld, Sphere,
+};
+use super::{ColliderHandle, CollisionGroups};
+
+/// 이보다 짧은 이동은 무시한다.
+const MIN_MOVE_DISTANCE: f32 = 1.0e-5;
+
+
+/// 캐릭터 컨트롤러가 움직이는 도형
+#[derive(Debug, Clone, Copy)]
+pub enum CharacterShape {
+    Capsule(Capsule),
+    Sphere(Sphere),
+    Box(BoundingBox),
+}
+
+impl CharacterShape {
+    pub fn center(&self) -> glam::Vec3 {
+        match self {
+            CharacterShape::Capsule(capsule) => capsule.center,
+            CharacterShape::Sphere(sphere) => sphere.center,
+            CharacterShape::Box(bounding_box) => bounding_box.center,
+        }
+    }
+
+    pub fn set_center(&mut self, center: glam::Vec3) {
+        match self {
+            CharacterShape::Capsule(capsule) => capsule.center = center,
+            CharacterShape::Sphere(sphere) => sphere.center = center,
+            CharacterShape::Box(bounding_box) => bounding_box.center = center,
+        }
+    }
+
+    fn moved_to(&self, position: glam::Vec3A) -> Self {
+        let mut shape = *self;
+        shape.set_center(glam::Vec3::from(position));
+        shape
+    }
+}
+
+impl From<Capsule> for CharacterShape {
+    fn from(capsule: Capsule) -> Self {
+        CharacterShape::Capsule(capsule)
+    }
+}
+
+impl From<Sphere> for CharacterShape {
+    fn from(sphere: Sphere) -> Self {
+        CharacterShape::Sphere(sphere)
+    }
+}
+
+impl From<BoundingBox> for CharacterShape {
+    fn from(bounding_box: BoundingBox) -> Self {
+        CharacterShape::Box(bounding_box)
+    }
+}
+
+impl Bounded for CharacterShape {
+    fn aabb(&self) -> BoundingBox {
+        match self {
+            CharacterShape::Capsule(capsule) => capsule.bounding_box(),
+            CharacterShape::Sphere(sphere) => sphere.bounding_box(),
+            CharacterShape::Box(bounding_box) => bounding_box.aabb(),
+        }
+    }
+}
+
+impl DynamicCollision<BoundingBox> for CharacterShape {
+    fn time_of_impact(&self, velocity: &glam::Vec3A, other: &BoundingBox) -> Option<(f32, CollisionDetails)> {
+        match self {
+            CharacterShape::Capsule(capsule) => capsule.time_of_impact(velocity, other),
+            CharacterShape::Sphere(sphere) => sphere.time_of_impact(velocity, other),
+            CharacterShape::Box(bounding_box) => bounding_box.time_of_impact(velocity, other),
+        }
+    }
+
+    fn time_of_impact_with_config(
+        &self,
+        velocity: &glam::Vec3A,
+        other: &BoundingBox,
+        config: &SweepConfig,
+    ) -> Option<(f32, CollisionDetails)> {
+        match self {
+            CharacterShape::Capsule(capsule) => capsule.time_of_impact_with_config(velocity, other, config),
+            CharacterShape::Sphere(sphere) => sphere.time_of_impact_with_config(velocity, other, config),
+            CharacterShape::Box(bounding_box) => bounding_box.time_of_impact_with_config(velocity, other, config),
+        }
+    }
+}
+
+impl DynamicCollision<Sphere> for CharacterShape {
+    fn time_of_impact(&self, velocity: &glam::Vec3A, other: &Sphere) -> Option<(f32, CollisionDetails)> {
+        match self {
+            CharacterShape::Capsule(capsule) => capsule.time_of_impact(velocity, other),
+            CharacterShape::Sphere(sphere) => sphere.time_of_impact(velocity, other),
+            CharacterShape::Box(bounding_box) => bounding_box.time_of_impact(velocity, other),
+        }
+    }
+
+    fn time_of_impact_with_config(
+        &self,
+        velocity: &glam::Vec3A,
+        other: &Sphere,
+        config: &SweepConfig,
+    ) -> Option<(f32, CollisionDetails)> {
+        match self {
+            CharacterShape::Capsule(capsule) => capsule.time_of_impact_with_config(velocity, other, config),
+            CharacterShape::Sphere(sphere) => sphere.time_of_impact_with_config(velocity, other, config),
+            CharacterShape::Box(bounding_box) => bounding_box.time_of_impact_with_config(velocity, other, config),
+        }
+    }
+}
+
+
+/// 이동 중에 부딪힌 충돌체
+#[derive(Debug, Clone, Copy)]
+pub struct CharacterContact {
+    pub handle: ColliderHandle,
+    /// 충돌체에서 캐릭터를 향하는 방향
+    pub normal: glam::Vec3A,
+    /// 부딪힌 순간의 캐릭터 위치
+    pub position: glam::Vec3A,
+}
+
+/// 캐릭터가 서 있는 바닥
+#[derive(Debug, Clone, Copy)]
+pub struct CharacterGround {
+    pub handle: ColliderHandle,
+    pub normal: glam::Vec3A,
+}
+
+/// move_and_slide의 결과
+#[derive(Debug, Clone)]
+pub struct CharacterMovement {
+    /// 이동을 마친 도형의 중심
+    pub position: glam::Vec3A,
+    /// 실제로 이동한 변위
+    pub translation: glam::Vec3A,
+    /// 이동을 마친 뒤 걸을 수 있는 바닥 위에 있으면 Some
+    pub ground: Option<CharacterGround>,
+    /// 계단을 올랐는지 여부
+    pub stepped_up: bool,
+    /// 부딪힌 순서대로의 충돌체 (같은 충돌체가 여러 번 나올 수 있다.)
+    pub contacts: Vec<CharacterContact>,
+}
+
+impl CharacterMovement {
+    pub fn is_grounded(&self) -> bool {
+        self.ground.is_some()
+    }
+}
+
+
+/// CollisionWorld의 충돌체 사이에서 도형을 미끄러지듯 움직이는 키네마틱 캐릭터 컨트롤러  
+/// 벽을 만나면 면을 따라 미끄러지고, step_height 이하의 턱은 올라서며, 내려가는 중에는 바닥에 붙는다.  
+/// 센서와 exclude로 지정한 충돌체(캐릭터 자신 등)는 무시한다.  
+#[derive(Debug, Clone)]
+pub struct CharacterController {
+    pub shape: CharacterShape,
+    /// 위쪽 방향 (단위 벡터)
+    pub up: glam::Vec3A,
+    /// 걸어서 오를 수 있는 바닥의 최대 경사 (라디안)
+    pub max_slope: f32,
+    /// 올라설 수 있는 턱의 최대 높이
+    pub step_height: f32,
+    /// 올라설 수 있는 턱 윗면의 최소 폭
+    pub step_min_width: f32,
+    /// 충돌체와 유지하는 간격
+    pub skin_width: f32,
+    /// 한 번의 이동에서 면을 따라 미끄러지는 최대 횟수
+    pub max_slide_iterations: usize,
+    /// 올라가는 중이 아니면 이 거리 안의 바닥으로 끌어내린다.
+    pub snap_distance: f32,
+    pub groups: CollisionGroups,
+    pub exclude: Option<ColliderHandle>,
+}
+
+impl CharacterController {
+    pub fn new(shape: impl Into<CharacterShape>) -> Self {
+        Self {
+            shape: shape.into(),
+            up: glam::Vec3A::Y,
+            max_slope: 45.0_f32.to_radians(),
+            step_height: 0.3,
+            step_min_width: 0.1,
+            skin_width: 0.02,
+            max_slide_iterations: 4,
+            snap_distance: 0.2,
+            groups: CollisionGroups::ALL,
+            exclude: None,
+        }
+    }
+
+    pub fn with_groups(mut self, groups: CollisionGroups) -> Self {
+        self.groups = groups;
+        self
+    }
+
+    pub fn with_exclude(mut self, handle: ColliderHandle) -> Self {
+        self.exclude = Some(handle);
+        self
+    }
+
+    pub fn position(&self) -> glam::Vec3A {
+        glam::Vec3A::from(self.shape.center())
+    }
+
+    /// normal을 가진 면이 걸을 수 있는 바닥인지 확인한다.
+    pub fn is_walkable(&self, normal: &glam::Vec3A) -> bool {
+        normal.dot(self.up) >= self.max_slope.cos() - 1.0e-4
+    }
+
+    /// 도형을 translation만큼 움직이고, 멈춘 위치를 shape에 반영한다.
+    pub fn move_and_slide<B: Broadphase<ColliderHandle>>(
+        &mut self,
+        world: &CollisionWorld<B>,
+        translation: &glam::Vec3A,
+    ) -> CharacterMovement {
+        let movement = self.compute_movement(world, &self.position(), translation);
+        self.shape.set_center(glam::Vec3::from(movement.position));
+        movement
+    }
+
+    /// 도형의 중심을 position에 두고 translation만큼 움직였을 때의 결과 (shape은 바꾸지 않는다.)  
+    /// 수평 이동(필요하면 계단 오르기), 수직 이동, 바닥 붙이기 순서로 처리한다.  
+    pub fn compute_movement<B: Broadphase<ColliderHandle>>(
+        &self,
+        world: &CollisionWorld<B>,
+        position: &glam::Vec3A,
+        translation: &glam::Vec3A,
+    ) -> CharacterMovement {
+        let start = *position;
+        let vertical = self.up * translation.dot(self.up);
+        let horizontal = *translation - vertical;
+        let mut contacts = Vec::new();
+
+        // 1. 수평 이동, 가파른 벽에 막히면 올라서서 다시 시도한다.
+        let slide = self.slide(world, start, horizontal, true, &mut contacts);
+        let mut current = slide.position;
+        let mut stepped_up = false;
+        if slide.blocked && vertical.dot(self.up) <= 0.0
+            && let Some((position, step_contacts)) = self.step_up(world, start, horizontal)
+        {
+            let progress = |position: glam::Vec3A| (position - start).dot(horizontal);
+            if progress(position) > progress(current) + MIN_MOVE_DISTANCE {
+                current = position;
+                contacts.extend(step_contacts);
+                stepped_up = true;
+            }
+        }
+
+        // 2. 수직 이동
+        current = self.slide(world, current, vertical, false, &mut contacts).position;
+
+        // 3. 올라가는 중이 아니면 바닥을 찾아 붙인다.
+        let rising = translation.dot(self.up) > MIN_MOVE_DISTANCE;
+        let mut ground = None;
+        if !rising {
+            let probe = if stepped_up { self.step_height } else { self.snap_distance }.max(0.0) + self.skin_width * 2.0;
+            if let Some((handle, distance, details)) = self.cast(world, current, -self.up * probe)
+                && self.is_walkable(&details.normal)
+            {
+                current -= self.up * (distance - self.skin_width);
+                ground = Some(CharacterGround {
+                    handle,
+                    normal: details.normal,
+                });
+            }
+        }
+
+        CharacterMovement {
+            position: current,
+            translation: current - start,
+            ground,
+            stepped_up,
+            contacts,
+        }
+    }
+
+    /// 위로 올라선 뒤 앞으로 움직이고 다시 내려서 걸을 수 있는 바닥에 닿으면 그 위치와 부딪힌 충돌체를 반환한다.
+    fn step_up<B: Broadphase<ColliderHandle>>(
+        &self,
+        world: &CollisionWorld<B>,
+        start: glam::Vec3A,
+        horizontal: glam::Vec3A,
+    ) -> Option<(glam::Vec3A, Vec<CharacterContact>)> {
+        if self.step_height <= 0.0 {
+            return None;
+        }
+        let mut contacts = Vec::new();
+
+        let rise = match self.cast(world, start, self.up * self.step_height) {
+            Some((_, distance, _)) => (distance - self.skin_width).max(0.0),
+            None => self.step_height,
+        };
+        if rise <= MIN_MOVE_DISTANCE {
+            return None;    // 천장에 막힘
+        }
+        let raised = start + self.up * rise;
+
+        let forward = self.slide(world, raised, horizontal, true, &mut contacts).position;
+        let drop = -self.up * (rise + self.skin_width);
+        let (handle, distance, details) = self.cast(world, forward, drop)?;
+        let position = forward - self.up * (distance - self.skin_width).max(0.0);
+        if (position - start).dot(self.up) <= MIN_MOVE_DISTANCE {
+            return None;    // 올라서지 못함
+        }
+
+        // 둥근 도형은 턱의 모서리에 걸쳐 normal이 기울어지므로, step_min_width만큼 앞에서 윗면을 확인한다.
+        if !self.is_walkable(&details.normal) {
+            let ahead = horizontal.normalize_or_zero() * self.step_min_width;
+            if self.cast(world, forward, ahead).is_some() {
+                return None;
+            }
+            let (_, _, top) = self.cast(world, forward + ahead, drop)?;
+            if !self.is_walkable(&top.normal) {
+                return None;
+            }
+        }
+        contacts.push(CharacterContact {
+            handle,
+            normal: details.normal,
+            position,
+        });
+
+        Some((position, contacts))
+    }
+
+    /// translation만큼 움직이며 부딪힌 면을 따라 미끄러진다.  
+    /// block_steep이면 걸을 수 없는 면을 수직인 벽으로 보아, 면을 타고 올라가지 않는다.  
+    fn slide<B: Broadphase<ColliderHandle>>(
+        &self,
+        world: &CollisionWorld<B>,
+        start: glam::Vec3A,
+        translation: glam::Vec3A,
+        block_steep: bool,
+        contacts: &mut Vec<CharacterContact>,
+    ) -> SlideResult {
+        let mut position = start;
+        let mut remaining = translation;
+        let mut previous_plane: Option<glam::Vec3A> = None;
+        let mut blocked = false;
+
+        for _ in 0..self.max_slide_iterations {
+            let length = remaining.length();
+            if length <= MIN_MOVE_DISTANCE {
+                break;
+            }
+            // skin_width만큼 더 멀리 검사해서, 멈춘 뒤에도 간격이 남도록 한다.
+            let direction = remaining / length;
+            let hit = self.cast(world, position, direction * (length + self.skin_width))
+                .filter(|(_, distance, _)| *distance - self.skin_width < length);
+            let Some((handle, distance, details)) = hit else {
+                position += remaining;
+                break;
+            };
+            let normal = details.normal;
+            contacts.push(CharacterContact {
+                handle,
+                normal,
+                position,
+            });
+
+            if distance <= 0.0 {
+                // 처음부터 겹쳐 있으면 밀어낸다.
+                position += normal * (details.penetration + self.skin_width);
+            } else {
+                // skin_width만큼 앞에서 멈춘다.
+                let travel = (distance - self.skin_width).max(0.0);
+                position += direction * travel;
+                remaining -= direction * travel;
+            }
+
+            // 남은 이동에서 면을 파고드는 성분을 없앤다.
+            let mut plane = normal;
+            if block_steep && !self.is_walkable(&normal) {
+                blocked = true;
+                let wall = normal - self.up * normal.dot(self.up);
+                if let Some(wall) = wall.try_normalize() {
+                    plane = wall;
+                }
+            }
+            let into_plane = remaining.dot(plane);
+            if into_plane < 0.0 {
+                remaining -= plane * into_plane;
+            }
+
+            // 두 면 사이에 끼면 두 면이 만나는 모서리를 따라 움직인다.
+            if let Some(previous) = previous_plane
+                && remaining.dot(previous) < 0.0
+            {
+                remaining = match previous.cross(plane).try_normalize() {
+                    Some(crease) => crease * remaining.dot(crease),
+                    None => glam::Vec3A::ZERO,
+                };
+            }
+            previous_plane = Some(plane);
+
+            // 원래 이동 방향을 거스르면 멈춘다.
+            if remaining.dot(translation) <= 0.0 {
+                remaining = glam::Vec3A::ZERO;
+            }
+        }
+
+        SlideResult {
+            position,
+            blocked,
+        }
+    }
+
+    /// position에 둔 도형을 translation만큼 움직일 때 처음 만나는 충돌체와 그때까지의 거리
+    fn cast<B: Broadphase<ColliderHandle>>(
+        &self,
+        world: &CollisionWorld<B>,
+        position: glam::Vec3A,
+        translation: glam::Vec3A,
+    ) -> Option<(ColliderHandle, f32, CollisionDetails)> {
+        let shape = self.shape.moved_to(position);
+        let (handle, time, details) = world.sweep_shape_with(&shape, &translation, |handle, collider| {
+            !collider.is_sensor
+                && self.exclude != Some(handle)
+                && self.groups.interacts_with(&collider.groups)
+        })?;
+        let normal = details.normal.try_normalize().unwrap_or(self.up);
+        Some((handle, time * translation.length(), CollisionDetails { normal, ..details }))
+    }
+}
+
+struct SlideResult {
+    position: glam::Vec3A,
+    /// 걸을 수 없는 면에 막혔는지 여부
+    blocked: bool,
+}
@@ -0,0 +1,209 @@
+use crate::{
+    dynamic_collision::{DynamicCollision, SweepConfig},
+    ray_cast::RayCast,
+    static_collision::StaticCollision,
+    Bounded, BoundingBox, CollisionDetails, Ray, RayHit, Sphere,
+};
+
+
+/// CollisionWorld에 등록된 충돌체의 식별자  
+/// 제거된 충돌체의 자리가 재사용되어도 예전 핸들과 구분되도록 세대(generation)를 함께 가진다.  
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ColliderHandle {
+    pub(crate) index: u32,
+    pub(crate) generation: u32,
+}
+
+/// 충돌 그룹  
+/// 두 충돌체는 서로의 memberships가 상대의 filter에 포함되는 경우에만 충돌한다.  
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CollisionGroups {
+    /// 이 충돌체가 속한 그룹의 비트
+    pub memberships: u32,
+    /// 이 충돌체와 충돌할 수 있는 그룹의 비트
+    pub filter: u32,
+}
+
+impl CollisionGroups {
+    /// 모든 그룹에 속하고 모든 그룹과 충돌한다.
+    pub const ALL: CollisionGroups = CollisionGroups::new(u32::MAX, u32::MAX);
+    /// 어떤 것과도 충돌하지 않는다.
+    pub const NONE: CollisionGroups = CollisionGroups::new(0, 0);
+
+    pub const fn new(memberships: u32, filter: u32) -> Self {
+        Self {
+            memberships,
+            filter,
+        }
+    }
+
+    pub fn interacts_with(&self, other: &CollisionGroups) -> bool {
+        self.memberships & other.filter != 0 && other.memberships & self.filter != 0
+    }
+}
+
+impl Default for CollisionGroups {
+    fn default() -> Self {
+        Self::ALL
+    }
+}
+
+
+/// 충돌체가 가질 수 있는 도형
+#[derive(Debug, Clone, Copy)]
+pub enum ColliderShape {
+    Box(BoundingBox),
+    Sphere(Sphere),
+}
+
+impl ColliderShape {
+    pub fn center(&self) -> glam::Vec3 {
+        match self {
+            ColliderShape::Box(bounding_box) => bounding_box.center,
+            ColliderShape::Sphere(sphere) => sphere.center,
+        }
+    }
+
+    pub fn set_center(&mut self, center: glam::Vec3) {
+        match self {
+            ColliderShape::Box(bounding_box) => bounding_box.center = center,
+            ColliderShape::Sphere(sphere) => sphere.center = center,
+        }
+    }
+
+    pub fn check_collision(&self, other: &ColliderShape) -> bool {
+        match (self, other) {
+            (ColliderShape::Box(a), ColliderShape::Box(b)) => a.check_static_collision(b),
+            (ColliderShape::Box(a), ColliderShape::Sphere(b)) => a.check_static_collision(b),
+            (ColliderShape::Sphere(a), ColliderShape::Box(b)) => a.check_static_collision(b),
+            (ColliderShape::Sphere(a), ColliderShape::Sphere(b)) => a.check_static_collision(b),
+        }
+    }
+
+    /// normal은 other에서 self를 향하는 방향이고, penetration은 0 이상이다.
+    pub fn collision_details(&self, other: &ColliderShape) -> Option<CollisionDetails> {
+        let details = match (self, other) {
+            (ColliderShape::Box(a), ColliderShape::Box(b)) => a.check_static_collision_details(b),
+            (ColliderShape::Box(a), ColliderShape::Sphere(b)) => a.check_static_collision_details(b),
+            (ColliderShape::Sphere(a), ColliderShape::Box(b)) => a.check_static_collision_details(b),
+            (ColliderShape::Sphere(a), ColliderShape::Sphere(b)) => a.check_static_collision_details(b),
+        };
+        details.map(CollisionDetails::with_positive_penetration)
+    }
+
+    /// self가 velocity만큼 움직이는 동안 other와 처음 만나는 시점(0 ~ 1)과 그때의 충돌 정보
+    pub fn time_of_impact(&self, velocity: &glam::Vec3A, other: &ColliderShape) -> Option<(f32, CollisionDetails)> {
+        match other {
+            ColliderShape::Box(other) => DynamicCollision::<BoundingBox>::time_of_impact(self, velocity, other),
+            ColliderShape::Sphere(other) => DynamicCollision::<Sphere>::time_of_impact(self, velocity, other),
+        }
+    }
+}
+
+impl DynamicCollision<BoundingBox> for ColliderShape {
+    fn time_of_impact(&self, velocity: &glam::Vec3A, other: &BoundingBox) -> Option<(f32, CollisionDetails)> {
+        match self {
+            ColliderShape::Box(bounding_box) => bounding_box.time_of_impact(velocity, other),
+            ColliderShape::Sphere(sphere) => sphere.time_of_impact(velocity, other),
+        }
+    }
+
+    fn time_of_impact_with_config(
+        &self,
+        velocity: &glam::Vec3A,
+        other: &BoundingBox,
+        config: &SweepConfig,
+    ) -> Option<(f32, CollisionDetails)> {
+        match self {
+            ColliderShape::Box(bounding_box) => bounding_box.time_of_impact_with_config(velocity, other, config),
+            ColliderShape::Sphere(sphere) => sphere.time_of_impact_with_config(velocity, other, config),
+        }
+    }
+}
+
+impl DynamicCollision<Sphere> for ColliderShape {
+    fn time_of_impact(&self, velocity: &glam::Vec3A, other: &Sphere) -> Option<(f32, CollisionDetails)> {
+        match self {
+            ColliderShape::Box(bounding_box) => bounding_box.time_of_impact(velocity, other),
+            ColliderShape::Sphere(sphere) => sphere.time_of_impact(velocity, other),
+        }
+    }
+
+    fn time_of_impact_with_config(
+        &self,
+        velocity: &glam::Vec3A,
+        other: &Sphere,
+        config: &SweepConfig,
+    ) -> Option<(f32, CollisionDetails)> {
+        match self {
+            ColliderShape::Box(bounding_box) => bounding_box.time_of_impact_with_config(velocity, other, config),
+            ColliderShape::Sphere(sphere) => sphere.time_of_impact_with_config(velocity, other, config),
+        }
+    }
+}
+
+impl From<BoundingBox> for ColliderShape {
+    fn from(bounding_box: BoundingBox) -> Self {
+        ColliderShape::Box(bounding_box)
+    }
+}
+
+impl From<Sphere> for ColliderShape {
+    fn from(sphere: Sphere) -> Self {
+        ColliderShape::Sphere(sphere)
+    }
+}
+
+impl Bounded for ColliderShape {
+    fn aabb(&self) -> BoundingBox {
+        match self {
+            ColliderShape::Box(bounding_box) => bounding_box.aabb(),
+            ColliderShape::Sphere(sphere) => sphere.bounding_box(),
+        }
+    }
+}
+
+impl RayCast for ColliderShape {
+    fn cast_ray(&self, ray: &Ray, max_distance: f32) -> Option<RayHit> {
+        match self {
+            ColliderShape::Box(bounding_box) => bounding_box.cast_ray(ray, max_distance),
+            ColliderShape::Sphere(sphere) => sphere.cast_ray(ray, max_distance),
+        }
+    }
+}
+
+
+/// CollisionWorld가 소유하는 충돌체
+#[derive(Debug, Clone)]
+pub struct Collider {
+    pub shape: ColliderShape,
+    pub groups: CollisionGroups,
+    /// 센서는 충돌 정보 없이 겹침 여부만 검사하고, 접촉 대신 Overlap 이벤트를 만든다.
+    pub is_sensor: bool,
+}
+
+impl Collider {
+    pub fn new(shape: impl Into<ColliderShape>) -> Self {
+        Self {
+            shape: shape.into(),
+            groups: CollisionGroups::ALL,
+            is_sensor: false,
+        }
+    }
+
+    pub fn with_groups(mut self, groups: CollisionGroups) -> Self {
+        self.groups = groups;
+        self
+    }
+
+    pub fn with_sensor(mut self, is_sensor: bool) -> Self {
+        self.is_sensor = is_sensor;
+        self
+    }
+}
+
+impl<S: Into<ColliderShape>> From<S> for Collider {
+    fn from(shape: S) -> Self {
+        Collider::new(shape)
+    }
+}
@@ -0,0 +1,477 @@
+use std::collections::{HashMap, HashSet};
+use crate::{
+    dynamic_collision::DynamicCollision,
+    ray_cast::RayCast,
+    Bounded, BoundingBox, Broadphase, CollisionDetails, DynamicTree, ProxyId, Ray, RayHit, Sphere,
+};
+use super::{Collider, ColliderHandle, ColliderShape, CollisionGroups};
+
+
+/// step에서 보고되는 충돌 쌍의 변화 (항상 작은 핸들이 a)  
+/// details의 normal은 b에서 a를 향하는 방향이다.  
+#[derive(Debug, Clone, Copy)]
+pub enum CollisionEvent {
+    /// 이번 step에 충돌하기 시작함
+    Started {
+        a: ColliderHandle,
+        b: ColliderHandle,
+        details: CollisionDetails,
+    },
+    /// 이전 step에 이어 계속 충돌 중
+    Persisted {
+        a: ColliderHandle,
+        b: ColliderHandle,
+        details: CollisionDetails,
+    },
+    /// 이번 step에 떨어졌거나, 둘 중 하나가 제거됨
+    Ended {
+        a: ColliderHandle,
+        b: ColliderHandle,
+    },
+    /// 센서가 포함된 쌍이 이번 step에 겹치기 시작함 (겹쳐 있는 동안은 is_overlapping으로 확인)
+    OverlapStarted {
+        a: ColliderHandle,
+        b: ColliderHandle,
+    },
+    /// 센서가 포함된 쌍이 이번 step에 떨어졌거나, 둘 중 하나가 제거됨
+    OverlapEnded {
+        a: ColliderHandle,
+        b: ColliderHandle,
+    },
+}
+
+/// narrowphase 전에 후보 쌍을 거르는 사용자 콜백, false를 반환하면 그 쌍은 무시된다.
+pub type PairFilter = Box<dyn Fn(ColliderHandle, &Collider, ColliderHandle, &Collider) -> bool>;
+
+/// 쌍 캐시에 저장되는 충돌 중인 쌍의 상태
+#[derive(Debug, Clone, Copy)]
+struct ContactPair {
+    details: CollisionDetails,
+    /// 충돌이 이어진 step 수
+    age: u32,
+}
+
+#[derive(Debug, Clone)]
+struct ColliderEntry {
+    collider: Collider,
+    proxy: ProxyId,
+    /// 마지막 step 이후 get_mut으로 접근되어 broadphase를 갱신해야 함
+    dirty: bool,
+    /// 잠든 충돌체끼리의 쌍은 narrowphase를 건너뛰고 지난 결과를 유지한다.
+    sleeping: bool,
+}
+
+#[derive(Debug, Clone)]
+struct ColliderSlot {
+    generation: u32,
+    entry: Option<ColliderEntry>,
+}
+
+/// 충돌체를 소유하고, 매 step마다 broadphase와 narrowphase를 거쳐 충돌 이벤트를 만든다.  
+/// broadphase는 Broadphase를 구현한 어떤 구조로든 바꿀 수 있다.  
+pub struct CollisionWorld<B = DynamicTree<ColliderHandle>> {
+    slots: Vec<ColliderSlot>,
+    free_list: Vec<u32>,
+    broadphase: B,
+    /// 지난 step에 충돌 중이던 쌍 (작은 핸들이 앞)
+    pairs: HashMap<(ColliderHandle, ColliderHandle), ContactPair>,
+    /// 지난 step에 겹쳐 있던 센서 쌍 (작은 핸들이 앞)
+    overlaps: HashSet<(ColliderHandle, ColliderHandle)>,
+    /// 충돌체가 제거되어 끝난 쌍, 다음 step에서 보고한다.
+    pending_events: Vec<CollisionEvent>,
+    pair_filter: Option<PairFilter>,
+}
+
+impl Default for CollisionWorld {
+    fn default() -> Self {
+        Self::new(DynamicTree::default())
+    }
+}
+
+impl<B: Broadphase<ColliderHandle>> CollisionWorld<B> {
+    /// 비어 있는 broadphase로 만든다.
+    pub fn new(broadphase: B) -> Self {
+        Self {
+            slots: Vec::new(),
+            free_list: Vec::new(),
+            broadphase,
+            pairs: HashMap::new(),
+            overlaps: HashSet::new(),
+            pending_events: Vec::new(),
+            pair_filter: None,
+        }
+    }
+
+    pub fn broadphase(&self) -> &B {
+        &self.broadphase
+    }
+
+    /// 충돌 그룹을 통과한 후보 쌍에 대해 narrowphase 전에 호출할 콜백을 정한다.  
+    /// step과 충돌체를 기준으로 하는 질의(query_collider, sweep_collider)에 적용된다.  
+    pub fn set_pair_filter(&mut self, filter: impl Fn(ColliderHandle, &Collider, ColliderHandle, &Collider) -> bool + 'static) {
+        self.pair_filter = Some(Box::new(filter));
+    }
+
+    pub fn clear_pair_filter(&mut self) {
+        self.pair_filter = None;
+    }
+
+    /// 등록된 충돌체의 개수
+    pub fn len(&self) -> usize {
+        self.slots.len() - self.free_list.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn contains(&self, handle: ColliderHandle) -> bool {
+        self.entry(handle).is_some()
+    }
+
+    pub fn get(&self, handle: ColliderHandle) -> Option<&Collider> {
+        self.entry(handle).map(|entry| &entry.collider)
+    }
+
+    /// 충돌체를 수정할 수 있다. 바뀐 모양은 다음 step에서 broadphase에 반영된다.  
+    /// 잠들어 있던 충돌체는 깨어난다.  
+    pub fn get_mut(&mut self, handle: ColliderHandle) -> Option<&mut Collider> {
+        let entry = self.entry_mut(handle)?;
+        entry.dirty = true;
+        entry.sleeping = false;
+        Some(&mut entry.collider)
+    }
+
+    pub fn is_sleeping(&self, handle: ColliderHandle) -> bool {
+        self.entry(handle).is_some_and(|entry| entry.sleeping)
+    }
+
+    /// 충돌체를 재우거나 깨운다.  
+    /// 둘 다 잠든 쌍은 narrowphase를 건너뛰고, 충돌 중이던 쌍은 지난 충돌 정보로 Persisted를 보고한다.  
+    pub fn set_sleeping(&mut self, handle: ColliderHandle, sleeping: bool) {
+        if let Some(entry) = self.entry_mut(handle) {
+            entry.sleeping = sleeping;
+        }
+    }
+
+    /// 등록된 모든 충돌체
+    pub fn iter(&self) -> impl Iterator<Item = (ColliderHandle, &Collider)> {
+        self.slots.iter()
+            .enumerate()
+            .filter_map(|(i, slot)| {
+                let handle = ColliderHandle {
+                    index: i as u32,
+                    generation: slot.generation,
+                };
+                slot.entry.as_ref().map(|entry| (handle, &entry.collider))
+            })
+    }
+
+    pub fn insert(&mut self, collider: impl Into<Collider>) -> ColliderHandle {
+        let collider = collider.into();
+        let index = match self.free_list.pop() {
+            Some(index) => index,
+            None => {
+                self.slots.push(ColliderSlot {
+                    generation: 0,
+                    entry: None,
+                });
+                (self.slots.len() - 1) as u32
+            }
+        };
+        let handle = ColliderHandle {
+            index,
+            generation: self.slots[index as usize].generation,
+        };
+
+        let proxy = self.broadphase.insert_proxy(&collider.shape.aabb(), handle);
+        self.slots[index as usize].entry = Some(ColliderEntry {
+            collider,
+            proxy,
+            dirty: false,
+            sleeping: false,
+        });
+        handle
+    }
+
+    /// 충돌체를 제거한다. 충돌 중이던 쌍은 다음 step에서 Ended(센서는 OverlapEnded)로 보고된다.
+    pub fn remove(&mut self, handle: ColliderHandle) -> Option<Collider> {
+        let slot = self.slots.get_mut(handle.index as usize)
+            .filter(|slot| slot.generation == handle.generation)?;
+        let entry = slot.entry.take()?;
+        slot.generation = slot.generation.wrapping_add(1);
+        self.free_list.push(handle.index);
+        self.broadphase.remove_proxy(entry.proxy);
+
+        let pending_events = &mut self.pending_events;
+        self.pairs.retain(|&(a, b), _| {
+            let removed = a == handle || b == handle;
+            if removed {
+                pending_events.push(CollisionEvent::Ended { a, b });
+            }
+            !removed
+        });
+        self.overlaps.retain(|&(a, b)| {
+            let removed = a == handle || b == handle;
+            if removed {
+                pending_events.push(CollisionEvent::OverlapEnded { a, b });
+            }
+            !removed
+        });
+        Some(entry.collider)
+    }
+
+    /// 지금 충돌 중인 모든 쌍 (작은 핸들이 앞, normal은 뒤에서 앞을 향함)
+    pub fn contacts(&self) -> impl Iterator<Item = (ColliderHandle, ColliderHandle, &CollisionDetails)> {
+        self.pairs.iter().map(|(&(a, b), pair)| (a, b, &pair.details))
+    }
+
+    /// 두 충돌체가 지금 충돌 중이면 a를 기준으로 한 충돌 정보를 반환한다.  
+    /// normal은 b에서 a를 향하는 방향이다.  
+    pub fn contact(&self, a: ColliderHandle, b: ColliderHandle) -> Option<CollisionDetails> {
+        let details = self.pairs.get(&(a.min(b), a.max(b)))?.details;
+        if a < b {
+            Some(details)
+        } else {
+            Some(CollisionDetails {
+                normal: -details.normal,
+                penetration: details.penetration,
+            })
+        }
+    }
+
+    /// 지금 겹쳐 있는 모든 센서 쌍 (작은 핸들이 앞)
+    pub fn sensor_overlaps(&self) -> impl Iterator<Item = (ColliderHandle, ColliderHandle)> + '_ {
+        self.overlaps.iter().copied()
+    }
+
+    /// 센서가 포함된 두 충돌체가 지금 겹쳐 있는지 확인한다.
+    pub fn is_overlapping(&self, a: ColliderHandle, b: ColliderHandle) -> bool {
+        self.overlaps.contains(&(a.min(b), a.max(b)))
+    }
+
+    /// 두 충돌체가 충돌한 채로 이어진 step 수 (충돌 중이 아니면 None)
+    pub fn contact_age(&self, a: ColliderHandle, b: ColliderHandle) -> Option<u32> {
+        self.pairs.get(&(a.min(b), a.max(b))).map(|pair| pair.age)
+    }
+
+    /// 바뀐 충돌체를 broadphase에 반영하고, 후보 쌍에 narrowphase를 수행하여  
+    /// 지난 step과 비교한 충돌 이벤트를 반환한다.  
+    pub fn step(&mut self) -> Vec<CollisionEvent> {
+        // 1. broadphase 갱신
+        for slot in self.slots.iter_mut() {
+            if let Some(entry) = slot.entry.as_mut()
+                && entry.dirty
+            {
+                self.broadphase.update_proxy(entry.proxy, &entry.collider.shape.aabb());
+                entry.dirty = false;
+            }
+        }
+
+        // 2. narrowphase
+        let mut events = std::mem::take(&mut self.pending_events);
+        let mut pairs = HashMap::with_capacity(self.pairs.len());
+        let mut overlaps = HashSet::with_capacity(self.overlaps.len());
+        for (a, b) in self.broadphase.candidate_pairs() {
+            let (a, b) = (a.min(b), a.max(b));
+            let (Some(entry_a), Some(entry_b)) = (self.entry(a), self.entry(b)) else {
+                continue;
+            };
+            if !self.should_collide(a, &entry_a.collider, b, &entry_b.collider) {
+                continue;
+            }
+
+            // 잠든 쌍은 지난 결과를 그대로 이어간다.
+            if entry_a.sleeping && entry_b.sleeping {
+                if self.overlaps.remove(&(a, b)) {
+                    overlaps.insert((a, b));
+                } else if let Some(previous) = self.pairs.remove(&(a, b)) {
+                    events.push(CollisionEvent::Persisted { a, b, details: previous.details });
+                    pairs.insert((a, b), ContactPair {
+                        details: previous.details,
+                        age: previous.age + 1,
+                    });
+                }
+                continue;
+            }
+
+            // 센서는 겹침 여부만 필요하므로 충돌 정보를 계산하지 않는다.
+            if entry_a.collider.is_sensor || entry_b.collider.is_sensor {
+                if entry_a.collider.shape.check_collision(&entry_b.collider.shape) {
+                    if !self.overlaps.remove(&(a, b)) {
+                        events.push(CollisionEvent::OverlapStarted { a, b });
+                    }
+                    overlaps.insert((a, b));
+                }
+                continue;
+            }
+
+            let Some(details) = entry_a.collider.shape.collision_details(&entry_b.collider.shape) else {
+                continue;
+            };
+
+            let pair = match self.pairs.remove(&(a, b)) {
+                Some(previous) => {
+                    events.push(CollisionEvent::Persisted { a, b, details });
+                    ContactPair {
+                        details,
+                        age: previous.age + 1,
+                    }
+                }
+                None => {
+                    events.push(CollisionEvent::Started { a, b, details });
+                    ContactPair {
+                        details,
+                        age: 0,
+                    }
+                }
+            };
+            pairs.insert((a, b), pair);
+        }
+
+        // 3. 이번 step에 다시 만나지 않은 쌍은 끝난 것
+        events.extend(self.pairs.drain().map(|((a, b), _)| CollisionEvent::Ended { a, b }));
+        events.extend(self.overlaps.drain().map(|(a, b)| CollisionEvent::OverlapEnded { a, b }));
+        self.pairs = pairs;
+        self.overlaps = overlaps;
+        events
+    }
+
+    /// ray와 처음 만나는 충돌체 (groups와 상호작용하는 충돌체만)  
+    /// pair filter는 등록된 두 충돌체를 받으므로 등록되지 않은 ray에는 적용하지 않는다.  
+    pub fn cast_ray(&self, ray: &Ray, max_distance: f32, groups: CollisionGroups) -> Option<(ColliderHandle, RayHit)> {
+        let mut closest: Option<(ColliderHandle, RayHit)> = None;
+        for handle in self.broadphase.ray_candidates(ray, max_distance) {
+            let Some(entry) = self.entry(handle) else {
+                continue;
+            };
+            if !groups.interacts_with(&entry.collider.groups) {
+                continue;
+            }
+            let limit = closest.map_or(max_distance, |(_, hit)| hit.distance);
+            if let Some(hit) = entry.collider.shape.cast_ray(ray, limit) {
+                closest = Some((handle, hit));
+            }
+        }
+        closest
+    }
+
+    /// shape과 겹치는 모든 충돌체 (groups와 상호작용하는 충돌체만)  
+    /// pair filter는 적용하지 않는다. 등록된 충돌체 기준으로 필터까지 적용하려면 query_collider를 사용한다.  
+    pub fn query_shape(&self, shape: &ColliderShape, groups: CollisionGroups) -> Vec<ColliderHandle> {
+        self.overlaps(shape, |_, collider| groups.interacts_with(&collider.groups))
+    }
+
+    /// 등록된 충돌체와 겹치는 다른 모든 충돌체 (충돌 그룹과 pair filter를 적용)
+    pub fn query_collider(&self, handle: ColliderHandle) -> Vec<ColliderHandle> {
+        let Some(entry) = self.entry(handle) else {
+            return Vec::new();
+        };
+        let collider = &entry.collider;
+        self.overlaps(&collider.shape, |other, other_collider| {
+            other != handle && self.should_collide(handle, collider, other, other_collider)
+        })
+    }
+
+    /// shape을 velocity만큼 움직일 때 처음 만나는 충돌체와 그 시점(0 ~ 1), 충돌 정보  
+    /// groups와 상호작용하는 충돌체만 검사하고, pair filter는 적용하지 않는다. (sweep_collider는 적용한다.)  
+    pub fn sweep_shape(
+        &self,
+        shape: &ColliderShape,
+        velocity: &glam::Vec3A,
+        groups: CollisionGroups,
+    ) -> Option<(ColliderHandle, f32, CollisionDetails)> {
+        self.sweep(shape, velocity, |_, collider| groups.interacts_with(&collider.groups))
+    }
+
+    /// ColliderShape이 아닌 도형(캡슐 등)도 받을 수 있는 sweep_shape  
+    /// filter가 true를 반환하는 충돌체만 검사한다. 충돌 그룹과 pair filter는 filter에서 직접 확인해야 한다.  
+    pub fn sweep_shape_with<S>(
+        &self,
+        shape: &S,
+        velocity: &glam::Vec3A,
+        filter: impl Fn(ColliderHandle, &Collider) -> bool,
+    ) -> Option<(ColliderHandle, f32, CollisionDetails)>
+    where
+        S: Bounded + DynamicCollision<BoundingBox> + DynamicCollision<Sphere>,
+    {
+        self.sweep(shape, velocity, filter)
+    }
+
+    /// 등록된 충돌체를 velocity만큼 움직일 때 처음 만나는 다른 충돌체 (충돌 그룹과 pair filter를 적용)  
+    /// 충돌체 자체는 옮기지 않는다.  
+    pub fn sweep_collider(&self, handle: ColliderHandle, velocity: &glam::Vec3A) -> Option<(ColliderHandle, f32, CollisionDetails)> {
+        let entry = self.entry(handle)?;
+        let collider = &entry.collider;
+        self.sweep(&collider.shape, velocity, |other, other_collider| {
+            other != handle && self.should_collide(handle, collider, other, other_collider)
+        })
+    }
+
+    fn overlaps(
+        &self,
+        shape: &ColliderShape,
+        filter: impl Fn(ColliderHandle, &Collider) -> bool,
+    ) -> Vec<ColliderHandle> {
+        let mut result = self.broadphase.query_candidates(&shape.aabb());
+        result.retain(|&handle| {
+            self.entry(handle).is_some_and(|entry| {
+                filter(handle, &entry.collider) && shape.check_collision(&entry.collider.shape)
+            })
+        });
+        result
+    }
+
+    fn sweep<S>(
+        &self,
+        shape: &S,
+        velocity: &glam::Vec3A,
+        filter: impl Fn(ColliderHandle, &Collider) -> bool,
+    ) -> Option<(ColliderHandle, f32, CollisionDetails)>
+    where
+        S: Bounded + DynamicCollision<BoundingBox> + DynamicCollision<Sphere>,
+    {
+        // 이동 전후의 AABB를 모두 감싸는 범위
+        let start = shape.aabb();
+        let mut end = start;
+        end.center += glam::Vec3::from(*velocity);
+        let swept = start.union(&end);
+
+        let mut closest: Option<(ColliderHandle, f32, CollisionDetails)> = None;
+        for handle in self.broadphase.query_candidates(&swept) {
+            let Some(entry) = self.entry(handle) else {
+                continue;
+            };
+            if !filter(handle, &entry.collider) {
+                continue;
+            }
+            let time_of_impact = match &entry.collider.shape {
+                ColliderShape::Box(other) => shape.time_of_impact(velocity, other),
+                ColliderShape::Sphere(other) => shape.time_of_impact(velocity, other),
+            };
+            if let Some((time, details)) = time_of_impact
+                && closest.is_none_or(|(_, closest_time, _)| time < closest_time)
+            {
+                closest = Some((handle, time, details));
+            }
+        }
+        closest
+    }
+
+    fn should_collide(&self, a: ColliderHandle, collider_a: &Collider, b: ColliderHandle, collider_b: &Collider) -> bool {
+        collider_a.groups.interacts_with(&collider_b.groups)
+            && self.pair_filter.as_ref().is_none_or(|filter| filter(a, collider_a, b, collider_b))
+    }
+
+    fn entry(&self, handle: ColliderHandle) -> Option<&ColliderEntry> {
+        self.slots.get(handle.index as usize)
+            .filter(|slot| slot.generation == handle.generation)?
+            .entry.as_ref()
+    }
+
+    fn entry_mut(&mut self, handle: ColliderHandle) -> Option<&mut ColliderEntry> {
+        self.slots.get_mut(handle.index as usize)
+            .filter(|slot| slot.generation == handle.generation)?
+            .entry.as_mut()
+    }
+}
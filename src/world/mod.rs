@@ -0,0 +1,7 @@
+mod character_controller;
+mod collider;
+mod collision_world;
+
+pub use character_controller::*;
+pub use collider::*;
+pub use collision_world::*;
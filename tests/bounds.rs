@@ -0,0 +1,150 @@
+use collision_test::*;
+
+fn rotated_box() -> BoundingBox {
+    let rotation = glam::Mat3::from_euler(glam::EulerRot::XYZ, 0.4, 0.9, -0.3);
+    BoundingBox::new_rotated(glam::Vec3::new(1.0, -0.5, 2.0), glam::Vec3::new(1.0, 0.5, 1.5), rotation)
+}
+
+#[test]
+fn union_encloses_both_boxes() {
+    let a = BoundingBox::from_min_max(glam::Vec3::new(-1.0, 0.0, 0.0), glam::Vec3::new(1.0, 1.0, 1.0));
+    let b = BoundingBox::from_min_max(glam::Vec3::new(0.5, -2.0, 0.5), glam::Vec3::new(3.0, 0.5, 0.75));
+    let union = a.union(&b);
+    assert_eq!(union.min(), glam::Vec3::new(-1.0, -2.0, 0.0));
+    assert_eq!(union.max(), glam::Vec3::new(3.0, 1.0, 1.0));
+    assert!(union.contains(&a) && union.contains(&b));
+
+    // OBB는 감싸는 AABB로 합친다.
+    let obb = rotated_box();
+    let union = a.union(&obb);
+    assert!(union.rotation().is_none());
+    assert!(union.contains(&a) && union.contains(&obb));
+    assert_eq!(union.max(), a.max().max(obb.aabb().max()));
+}
+
+#[test]
+fn intersection_of_overlapping_touching_and_disjoint_boxes() {
+    let a = BoundingBox::from_min_max(glam::Vec3::ZERO, glam::Vec3::splat(2.0));
+    let b = BoundingBox::from_min_max(glam::Vec3::new(1.0, -1.0, 0.5), glam::Vec3::new(3.0, 1.5, 1.0));
+    let overlap = a.intersection(&b).unwrap();
+    assert_eq!(overlap.min(), glam::Vec3::new(1.0, 0.0, 0.5));
+    assert_eq!(overlap.max(), glam::Vec3::new(2.0, 1.5, 1.0));
+    assert_eq!(b.intersection(&a).map(|b| (b.min(), b.max())), Some((overlap.min(), overlap.max())));
+
+    // 접하면 두께가 0인 박스
+    let touching = BoundingBox::from_min_max(glam::Vec3::new(2.0, 0.0, 0.0), glam::Vec3::new(3.0, 1.0, 1.0));
+    let face = a.intersection(&touching).unwrap();
+    assert_eq!(face.extents().x, 0.0);
+    assert_eq!(face.volume(), 0.0);
+
+    // 한 축이라도 떨어져 있으면 None
+    let disjoint = BoundingBox::from_min_max(glam::Vec3::new(0.5, 0.5, 2.5), glam::Vec3::new(1.0, 1.0, 3.0));
+    assert!(a.intersection(&disjoint).is_none());
+    assert!(disjoint.intersection(&a).is_none());
+}
+
+#[test]
+fn contains_boxes_and_points() {
+    let outer = BoundingBox::new(glam::Vec3::ZERO, glam::Vec3::splat(3.0));
+    let inner = BoundingBox::new(glam::Vec3::new(1.0, 0.0, -1.0), glam::Vec3::splat(1.0));
+    assert!(outer.contains(&inner));
+    assert!(!inner.contains(&outer));
+    // 경계에 닿는 것은 포함한다.
+    assert!(outer.contains(&BoundingBox::new(glam::Vec3::new(2.0, 0.0, 0.0), glam::Vec3::splat(1.0))));
+    assert!(!outer.contains(&BoundingBox::new(glam::Vec3::new(2.5, 0.0, 0.0), glam::Vec3::splat(1.0))));
+
+    // OBB는 정점으로 판단한다: 축 정렬 박스로는 들어가지만 45도 돌리면 모서리가 튀어나온다.
+    let rotation = glam::Mat3::from_rotation_z(std::f32::consts::FRAC_PI_4);
+    let rotated = BoundingBox::new_rotated(glam::Vec3::new(1.8, 0.0, 0.0), glam::Vec3::splat(1.0), rotation);
+    assert!(outer.contains(&BoundingBox::new(glam::Vec3::new(1.8, 0.0, 0.0), glam::Vec3::splat(1.0))));
+    assert!(!outer.contains(&rotated));
+
+    let obb = rotated_box();
+    let center = glam::Vec3A::from(obb.center);
+    for vertex in obb.get_vertices() {
+        // 정점은 반올림 오차로 경계를 넘을 수 있으므로 조금 안쪽에서 확인한다.
+        assert!(obb.contains_point(&(center + (vertex - center) * 0.999)), "{vertex}");
+        assert!(!obb.contains_point(&(center + (vertex - center) * 1.001)), "{vertex}");
+    }
+    assert!(obb.contains_point(&center));
+    // 회전된 로컬 x축 방향으로 extents를 조금 넘으면 밖이다.
+    let x_axis = glam::Vec3A::from(obb.rotation().unwrap().x_axis);
+    assert!(!obb.contains_point(&(center + x_axis * 1.01)));
+    assert!(obb.contains_point(&(center + x_axis * 0.99)));
+}
+
+#[test]
+fn aabb_of_obb_is_tight() {
+    let obb = rotated_box();
+    let aabb = obb.aabb();
+    assert!(aabb.rotation().is_none());
+    assert!(aabb.contains(&obb));
+
+    // 각 축으로 가장 먼 정점이 AABB의 면 위에 있다.
+    let vertices = obb.get_vertices();
+    let min = vertices.iter().fold(glam::Vec3A::splat(f32::MAX), |min, v| min.min(*v));
+    let max = vertices.iter().fold(glam::Vec3A::splat(f32::MIN), |max, v| max.max(*v));
+    assert!(glam::Vec3A::from(aabb.min()).abs_diff_eq(min, 1.0e-5), "{} != {min}", aabb.min());
+    assert!(glam::Vec3A::from(aabb.max()).abs_diff_eq(max, 1.0e-5), "{} != {max}", aabb.max());
+
+    // AABB는 자기 자신과 같다.
+    let box_ = BoundingBox::new(glam::Vec3::ONE, glam::Vec3::new(1.0, 2.0, 3.0));
+    assert_eq!((box_.aabb().min(), box_.aabb().max()), (box_.min(), box_.max()));
+}
+
+#[test]
+fn volume_and_surface_area_of_a_known_box() {
+    // 2 x 4 x 6 박스
+    let box_ = BoundingBox::new(glam::Vec3::ZERO, glam::Vec3::new(1.0, 2.0, 3.0));
+    assert_eq!(box_.volume(), 48.0);
+    assert_eq!(box_.surface_area(), 2.0 * (8.0 + 24.0 + 12.0));
+    // 회전해도 같다.
+    let rotated = BoundingBox::new_rotated(glam::Vec3::ZERO, glam::Vec3::new(1.0, 2.0, 3.0), rotated_box().rotation().unwrap());
+    assert_eq!(rotated.volume(), 48.0);
+    assert_eq!(rotated.surface_area(), 88.0);
+}
+
+#[test]
+fn bounding_sphere_contains_box() {
+    for box_ in [BoundingBox::new(glam::Vec3::ONE, glam::Vec3::new(1.0, 2.0, 3.0)), rotated_box()] {
+        let sphere = box_.bounding_sphere();
+        assert_eq!(sphere.center, box_.center);
+        // 모든 정점이 구의 표면 위에 있다.
+        for vertex in box_.get_vertices() {
+            let distance = (vertex - glam::Vec3A::from(sphere.center)).length();
+            assert!((distance - sphere.radius).abs() < 1.0e-5, "{vertex}: {distance} != {}", sphere.radius);
+            assert!(sphere.check_point_collision(&(glam::Vec3A::from(sphere.center) + (vertex - glam::Vec3A::from(sphere.center)) * 0.999)));
+        }
+    }
+}
+
+#[test]
+fn sphere_bounding_box_is_tight() {
+    let sphere = Sphere {
+        center: glam::Vec3::new(1.0, -2.0, 0.5),
+        radius: 1.5,
+    };
+    let aabb = sphere.bounding_box();
+    assert_eq!(aabb.min(), sphere.center - 1.5);
+    assert_eq!(aabb.max(), sphere.center + 1.5);
+    for axis in [glam::Vec3A::X, glam::Vec3A::Y, glam::Vec3A::Z] {
+        let center = glam::Vec3A::from(sphere.center);
+        assert!(aabb.contains_point(&(center + axis * sphere.radius)));
+        assert!(aabb.contains_point(&(center - axis * sphere.radius)));
+    }
+}
+
+#[test]
+fn expanded_by_grows_and_shrinks_every_axis() {
+    let obb = rotated_box();
+    let grown = obb.expanded_by(0.5);
+    assert_eq!(grown.center, obb.center);
+    assert_eq!(grown.rotation(), obb.rotation());
+    assert_eq!(grown.extents(), obb.extents() + 0.5);
+    assert!(grown.contains(&obb));
+
+    // 축소하면 extents는 0에서 멈춘다.
+    let shrunk = obb.expanded_by(-0.75);
+    assert_eq!(shrunk.extents(), glam::Vec3::new(0.25, 0.0, 0.75));
+    assert!(obb.contains(&shrunk));
+}
@@ -0,0 +1,109 @@
+use collision_test::*;
+
+const SKIN: f32 = 0.02;
+
+/// 바닥(윗면 y = 0)에 서 있는 높이 2, 반지름 0.5의 캡슐
+fn character() -> CharacterController {
+    let controller = CharacterController::new(Capsule::upright(glam::Vec3::new(0.0, 1.0 + SKIN, 0.0), 2.0, 0.5));
+    assert_eq!(controller.skin_width, SKIN);
+    controller
+}
+
+/// x가 min_x ~ max_x인 구간에 윗면이 top인 블록을 놓는다.
+fn block(world: &mut CollisionWorld, min_x: f32, max_x: f32, top: f32) -> ColliderHandle {
+    let center = glam::Vec3::new((min_x + max_x) * 0.5, top - 0.5, 0.0);
+    world.insert(BoundingBox::new(center, glam::Vec3::new((max_x - min_x) * 0.5, 0.5, 5.0)))
+}
+
+#[test]
+fn slides_along_wall() {
+    let mut world = CollisionWorld::default();
+    block(&mut world, -5.0, 5.0, 0.0);
+    let wall = world.insert(BoundingBox::new(glam::Vec3::new(1.5, 2.0, 0.0), glam::Vec3::new(0.5, 2.0, 5.0)));
+    let mut controller = character();
+
+    // 벽을 향해 비스듬히 움직이면 벽을 파고드는 x 성분만 막히고 z 방향으로는 끝까지 간다.
+    let movement = controller.move_and_slide(&world, &glam::Vec3A::new(2.0, 0.0, 1.0));
+    let position = movement.position;
+    assert!((position.x - (1.0 - 0.5 - SKIN)).abs() < 1.0e-2, "{position}");
+    assert!((position.z - 1.0).abs() < 1.0e-2, "{position}");
+    assert!((position.y - (1.0 + SKIN)).abs() < 1.0e-2, "{position}");
+    assert!(movement.contacts.iter().any(|contact| contact.handle == wall && contact.normal.abs_diff_eq(glam::Vec3A::NEG_X, 1.0e-3)));
+    assert!(!movement.stepped_up);
+    assert!(movement.is_grounded());
+    assert_eq!(controller.position(), position);
+}
+
+#[test]
+fn steps_up_ledge_lower_than_step_height() {
+    let mut world = CollisionWorld::default();
+    block(&mut world, -5.0, 1.0, 0.0);
+    let ledge = block(&mut world, 1.0, 7.0, 0.2);
+    let mut controller = character();
+    assert!(0.2 < controller.step_height);
+
+    let movement = controller.move_and_slide(&world, &glam::Vec3A::new(2.0, 0.0, 0.0));
+    let position = movement.position;
+    assert!(movement.stepped_up, "{movement:?}");
+    assert!((position.x - 2.0).abs() < 1.0e-2, "{position}");
+    assert!((position.y - (1.2 + SKIN)).abs() < 1.0e-2, "{position}");
+    assert!(matches!(movement.ground, Some(ground) if ground.handle == ledge), "{movement:?}");
+}
+
+#[test]
+fn is_blocked_by_ledge_higher_than_step_height() {
+    let mut world = CollisionWorld::default();
+    block(&mut world, -5.0, 1.0, 0.0);
+    let ledge = block(&mut world, 1.0, 7.0, 0.5);
+    let mut controller = character();
+    assert!(0.5 > controller.step_height);
+
+    let movement = controller.move_and_slide(&world, &glam::Vec3A::new(2.0, 0.0, 0.0));
+    let position = movement.position;
+    assert!(!movement.stepped_up, "{movement:?}");
+    assert!((position.x - (1.0 - 0.5 - SKIN)).abs() < 1.0e-2, "{position}");
+    assert!((position.y - (1.0 + SKIN)).abs() < 1.0e-2, "{position}");
+    assert!(movement.contacts.iter().any(|contact| contact.handle == ledge));
+}
+
+#[test]
+fn snaps_down_small_drop() {
+    let mut world = CollisionWorld::default();
+    block(&mut world, -5.0, 1.0, 0.0);
+    let lower = block(&mut world, 1.0, 7.0, -0.1);
+    let mut controller = character();
+    assert!(0.1 < controller.snap_distance);
+
+    let movement = controller.move_and_slide(&world, &glam::Vec3A::new(2.0, 0.0, 0.0));
+    let position = movement.position;
+    assert!((position.x - 2.0).abs() < 1.0e-2, "{position}");
+    assert!((position.y - (0.9 + SKIN)).abs() < 1.0e-2, "{position}");
+    assert!(matches!(movement.ground, Some(ground) if ground.handle == lower), "{movement:?}");
+}
+
+#[test]
+fn does_not_snap_over_drop_larger_than_snap_distance() {
+    let mut world = CollisionWorld::default();
+    block(&mut world, -5.0, 1.0, 0.0);
+    block(&mut world, 1.0, 7.0, -1.0);
+    let mut controller = character();
+    assert!(1.0 > controller.snap_distance);
+
+    // 바닥에서 떨어진 채로 수평 이동만 하고, 떨어지는 것은 호출하는 쪽의 중력에 맡긴다.
+    let movement = controller.move_and_slide(&world, &glam::Vec3A::new(2.0, 0.0, 0.0));
+    let position = movement.position;
+    assert!((position.x - 2.0).abs() < 1.0e-2, "{position}");
+    assert!((position.y - (1.0 + SKIN)).abs() < 1.0e-2, "{position}");
+    assert!(!movement.is_grounded(), "{movement:?}");
+}
+
+#[test]
+fn does_not_snap_while_rising() {
+    let mut world = CollisionWorld::default();
+    block(&mut world, -5.0, 5.0, 0.0);
+    let mut controller = character();
+
+    let movement = controller.move_and_slide(&world, &glam::Vec3A::new(0.0, 0.1, 0.0));
+    assert!((movement.position.y - (1.1 + SKIN)).abs() < 1.0e-3, "{movement:?}");
+    assert!(!movement.is_grounded());
+}
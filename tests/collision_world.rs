@@ -0,0 +1,239 @@
+use collision_test::*;
+
+fn cube(x: f32) -> BoundingBox {
+    BoundingBox::new(glam::Vec3::new(x, 0.0, 0.0), glam::Vec3::splat(0.5))
+}
+
+fn move_to(world: &mut CollisionWorld, handle: ColliderHandle, x: f32) {
+    world.get_mut(handle).unwrap().shape.set_center(glam::Vec3::new(x, 0.0, 0.0));
+}
+
+fn is_contact_event(event: &CollisionEvent) -> bool {
+    matches!(event, CollisionEvent::Started { .. } | CollisionEvent::Persisted { .. } | CollisionEvent::Ended { .. })
+}
+
+#[test]
+fn contact_events_start_persist_and_end() {
+    let mut world = CollisionWorld::default();
+    let a = world.insert(cube(0.0));
+    let b = world.insert(cube(0.8));
+    let pair = (a.min(b), a.max(b));
+
+    let events = world.step();
+    assert!(matches!(events[..], [CollisionEvent::Started { a, b, .. }] if (a, b) == pair), "{events:?}");
+    assert_eq!(world.contact_age(a, b), Some(0));
+
+    move_to(&mut world, b, 0.7);
+    let events = world.step();
+    let [CollisionEvent::Persisted { a: first, b: second, details }] = events[..] else {
+        panic!("{events:?}");
+    };
+    assert_eq!((first, second), pair);
+    // 캐시된 정보가 아니라 옮긴 위치로 다시 계산한 정보여야 한다.
+    assert!((details.penetration - 0.3).abs() < 1.0e-5, "{details:?}");
+    assert_eq!(world.contact_age(a, b), Some(1));
+
+    move_to(&mut world, b, 3.0);
+    let events = world.step();
+    assert!(matches!(events[..], [CollisionEvent::Ended { a, b }] if (a, b) == pair), "{events:?}");
+    assert!(world.contact(a, b).is_none());
+    assert!(world.step().is_empty());
+}
+
+#[test]
+fn contact_normal_points_from_b_to_a() {
+    let mut world = CollisionWorld::default();
+    let left = world.insert(cube(0.0));
+    let right = world.insert(cube(0.8));
+    world.step();
+
+    assert!(world.contact(left, right).unwrap().normal.abs_diff_eq(glam::Vec3A::NEG_X, 1.0e-6));
+    assert!(world.contact(right, left).unwrap().normal.abs_diff_eq(glam::Vec3A::X, 1.0e-6));
+}
+
+#[test]
+fn removing_collider_ends_its_contacts() {
+    let mut world = CollisionWorld::default();
+    let a = world.insert(cube(0.0));
+    let b = world.insert(cube(0.8));
+    let c = world.insert(cube(-0.8));
+    world.step();
+    assert_eq!(world.contacts().count(), 2);
+
+    world.remove(a);
+    let events = world.step();
+    let mut ended = events.iter()
+        .filter_map(|event| match *event {
+            CollisionEvent::Ended { a, b } => Some((a, b)),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    ended.sort();
+    let mut expected = vec![(a.min(b), a.max(b)), (a.min(c), a.max(c))];
+    expected.sort();
+    assert_eq!(ended, expected, "{events:?}");
+    assert_eq!(events.len(), 2, "{events:?}");
+    assert_eq!(world.contacts().count(), 0);
+
+    // 제거된 자리를 재사용한 충돌체는 예전 핸들과 구분된다.
+    let d = world.insert(cube(5.0));
+    assert_ne!(d, a);
+    assert!(world.get(a).is_none());
+}
+
+#[test]
+fn sensor_reports_enter_stay_exit() {
+    let mut world = CollisionWorld::default();
+    let zone = world.insert(Collider::new(cube(0.0)).with_sensor(true));
+    let visitor = world.insert(cube(3.0));
+    let pair = (zone.min(visitor), zone.max(visitor));
+
+    assert!(world.step().is_empty());
+
+    // 들어옴
+    move_to(&mut world, visitor, 0.6);
+    let events = world.step();
+    assert!(matches!(events[..], [CollisionEvent::OverlapStarted { a, b }] if (a, b) == pair), "{events:?}");
+    assert!(world.is_overlapping(zone, visitor));
+
+    // 머묾: 겹쳐 있는 동안은 이벤트가 없다.
+    for x in [0.4, 0.0, -0.3] {
+        move_to(&mut world, visitor, x);
+        let events = world.step();
+        assert!(events.is_empty(), "{events:?}");
+        assert!(world.is_overlapping(zone, visitor));
+    }
+
+    // 나감
+    move_to(&mut world, visitor, -3.0);
+    let events = world.step();
+    assert!(matches!(events[..], [CollisionEvent::OverlapEnded { a, b }] if (a, b) == pair), "{events:?}");
+    assert!(!world.is_overlapping(zone, visitor));
+    assert!(world.step().is_empty());
+}
+
+#[test]
+fn sensor_pair_never_produces_contacts() {
+    let mut world = CollisionWorld::default();
+    let a = world.insert(Collider::new(cube(0.0)).with_sensor(true));
+    let b = world.insert(Collider::new(cube(0.5)).with_sensor(true));
+
+    let mut all_events = Vec::new();
+    for x in [0.5, 0.2, 0.0, 3.0] {
+        move_to(&mut world, b, x);
+        all_events.extend(world.step());
+        assert_eq!(world.contacts().count(), 0);
+        assert!(world.contact(a, b).is_none());
+    }
+
+    assert!(!all_events.iter().any(is_contact_event), "{all_events:?}");
+    assert_eq!(all_events.iter().filter(|event| matches!(event, CollisionEvent::OverlapStarted { .. })).count(), 1);
+    assert_eq!(all_events.iter().filter(|event| matches!(event, CollisionEvent::OverlapEnded { .. })).count(), 1);
+}
+
+#[test]
+fn sensor_with_solid_collider_reports_overlap_only() {
+    let mut world = CollisionWorld::default();
+    let zone = world.insert(Collider::new(cube(0.0)).with_sensor(true));
+    let solid = world.insert(cube(0.5));
+    let other_solid = world.insert(cube(1.2));
+
+    let events = world.step();
+    // 센서와 일반 충돌체는 겹침만, 일반 충돌체끼리는 접촉을 보고한다.
+    assert!(world.is_overlapping(zone, solid));
+    assert!(world.contact(zone, solid).is_none());
+    assert!(world.contact(solid, other_solid).is_some());
+    assert_eq!(world.sensor_overlaps().count(), 1);
+
+    for event in &events {
+        match *event {
+            CollisionEvent::Started { a, b, .. } => assert_eq!((a, b), (solid.min(other_solid), solid.max(other_solid))),
+            CollisionEvent::OverlapStarted { a, b } => assert_eq!((a, b), (zone.min(solid), zone.max(solid))),
+            _ => panic!("예상하지 못한 이벤트: {event:?}"),
+        }
+    }
+    assert_eq!(events.len(), 2, "{events:?}");
+
+    // 센서를 없애면 겹침이 끝나고, 접촉 이벤트는 생기지 않는다.
+    world.remove(zone);
+    let events = world.step();
+    assert!(events.iter().any(|event| matches!(event, CollisionEvent::OverlapEnded { .. })), "{events:?}");
+    assert!(!events.iter().any(|event| matches!(event, CollisionEvent::Ended { .. })), "{events:?}");
+}
+
+const PLAYER: u32 = 1 << 0;
+const BULLET: u32 = 1 << 1;
+const WALL: u32 = 1 << 2;
+
+#[test]
+fn group_filtered_pairs_emit_no_events() {
+    let mut world = CollisionWorld::default();
+    let player = world.insert(Collider::new(cube(0.0)).with_groups(CollisionGroups::new(PLAYER, WALL | BULLET)));
+    // 플레이어가 쏜 총알은 플레이어와 충돌하지 않는다.
+    let bullet = world.insert(Collider::new(cube(0.5)).with_groups(CollisionGroups::new(BULLET, WALL)));
+
+    for x in [0.5, 0.2, 3.0] {
+        move_to(&mut world, bullet, x);
+        let events = world.step();
+        assert!(events.is_empty(), "{events:?}");
+        assert!(world.contact(player, bullet).is_none());
+    }
+}
+
+#[test]
+fn pair_filter_vetoes_pairs_in_step_and_collider_queries() {
+    let mut world = CollisionWorld::default();
+    let a = world.insert(cube(0.0));
+    let b = world.insert(cube(0.8));
+    let c = world.insert(cube(-0.8));
+    world.set_pair_filter(move |first, _, second, _| first != b && second != b);
+
+    let events = world.step();
+    assert_eq!(events.len(), 1, "{events:?}");
+    assert!(world.contact(a, c).is_some());
+    assert!(world.contact(a, b).is_none());
+
+    assert_eq!(world.query_collider(a), vec![c]);
+    let hit = world.sweep_collider(c, &glam::Vec3A::new(3.0, 0.0, 0.0));
+    assert!(matches!(hit, Some((handle, ..)) if handle == a), "{hit:?}");
+    // a까지 걸러 내면 c의 경로에는 만날 충돌체가 없다.
+    world.set_pair_filter(move |first, _, second, _| first != b && second != b && first != a && second != a);
+    assert!(world.sweep_collider(c, &glam::Vec3A::new(3.0, 0.0, 0.0)).is_none());
+
+    world.clear_pair_filter();
+    let mut overlapping = world.query_collider(a);
+    overlapping.sort();
+    let mut expected = vec![b, c];
+    expected.sort();
+    assert_eq!(overlapping, expected);
+}
+
+#[test]
+fn shape_queries_respect_groups() {
+    let mut world = CollisionWorld::default();
+    let player = world.insert(Collider::new(cube(0.0)).with_groups(CollisionGroups::new(PLAYER, WALL | BULLET)));
+    let wall = world.insert(Collider::new(cube(3.0)).with_groups(CollisionGroups::new(WALL, PLAYER | BULLET)));
+    let bullet_groups = CollisionGroups::new(BULLET, WALL);
+
+    // 총알은 플레이어를 지나쳐 벽에 맞는다.
+    let ray = Ray::new(glam::Vec3A::new(-3.0, 0.0, 0.0), glam::Vec3A::X);
+    assert!(matches!(world.cast_ray(&ray, 10.0, bullet_groups), Some((handle, _)) if handle == wall));
+    assert!(matches!(world.cast_ray(&ray, 10.0, CollisionGroups::ALL), Some((handle, _)) if handle == player));
+    assert!(world.cast_ray(&ray, 10.0, CollisionGroups::NONE).is_none());
+
+    let probe = ColliderShape::from(BoundingBox::new(glam::Vec3::new(1.5, 0.0, 0.0), glam::Vec3::new(2.0, 0.5, 0.5)));
+    assert_eq!(world.query_shape(&probe, bullet_groups), vec![wall]);
+    assert_eq!(world.query_shape(&probe, CollisionGroups::new(WALL, PLAYER)), vec![player]);
+
+    let bullet = ColliderShape::from(Sphere {
+        center: glam::Vec3::new(-3.0, 0.0, 0.0),
+        radius: 0.1,
+    });
+    let velocity = glam::Vec3A::new(10.0, 0.0, 0.0);
+    let (handle, time, _) = world.sweep_shape(&bullet, &velocity, bullet_groups).unwrap();
+    assert_eq!(handle, wall);
+    // 벽의 왼쪽 면 x = 2.5에 구의 오른쪽 끝 -2.9가 닿을 때까지 5.4를 이동한다.
+    assert!((time - 0.54).abs() < 1.0e-3, "{time}");
+    let (handle, ..) = world.sweep_shape(&bullet, &velocity, CollisionGroups::ALL).unwrap();
+    assert_eq!(handle, player);
+}
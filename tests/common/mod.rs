@@ -0,0 +1,27 @@
+// 테스트 파일마다 쓰는 도우미가 달라서, 쓰지 않는 파일에서는 dead_code 경고가 난다.
+#![allow(dead_code)]
+
+use collision_test::BoundingBox;
+use rand::{rngs::StdRng, Rng};
+
+/// 중심이 -20 ~ 20, extents가 0.1 ~ 3인 임의의 AABB
+pub fn random_box(rng: &mut StdRng) -> BoundingBox {
+    let center = glam::Vec3::new(rng.random_range(-20.0..20.0), rng.random_range(-20.0..20.0), rng.random_range(-20.0..20.0));
+    let extents = glam::Vec3::new(rng.random_range(0.1..3.0), rng.random_range(0.1..3.0), rng.random_range(0.1..3.0));
+    BoundingBox::new(center, extents)
+}
+
+/// 살아 있는 객체들의 AABB를 모두 비교하여 겹치는 쌍을 구한다. (작은 번호가 앞, 정렬됨)
+pub fn all_pairs(boxes: &[Option<BoundingBox>]) -> Vec<(usize, usize)> {
+    let mut pairs = Vec::new();
+    for i in 0..boxes.len() {
+        for j in i + 1..boxes.len() {
+            if let (Some(a), Some(b)) = (&boxes[i], &boxes[j])
+                && a.aabb_collision(b)
+            {
+                pairs.push((i, j));
+            }
+        }
+    }
+    pairs
+}
@@ -0,0 +1,92 @@
+use collision_test::*;
+
+fn wall(x: f32) -> DepenetrationObject {
+    let wall = BoundingBox::new(glam::Vec3::new(x, 0.0, 0.0), glam::Vec3::new(0.5, 5.0, 5.0));
+    DepenetrationObject::new(wall, BodyType::Static, 0.0)
+}
+
+fn cube(x: f32, half_size: f32) -> DepenetrationObject {
+    let cube = BoundingBox::new(glam::Vec3::new(x, 0.0, 0.0), glam::Vec3::splat(half_size));
+    DepenetrationObject::new(cube, BodyType::Dynamic, 1.0)
+}
+
+/// corrections를 적용한 위치에서 다시 측정한 가장 깊은 침투
+fn measure(objects: &[DepenetrationObject], result: &DepenetrationResult) -> f32 {
+    let shapes = objects.iter().zip(&result.corrections)
+        .map(|(object, correction)| {
+            let mut shape = object.shape;
+            shape.set_center(shape.center() + glam::Vec3::from(*correction));
+            shape
+        })
+        .collect::<Vec<_>>();
+
+    let mut deepest = 0.0_f32;
+    for i in 0..shapes.len() {
+        for j in i + 1..shapes.len() {
+            if objects[i].body_type != BodyType::Dynamic && objects[j].body_type != BodyType::Dynamic {
+                continue;
+            }
+            if let Some(details) = shapes[i].collision_details(&shapes[j]) {
+                deepest = deepest.max(details.penetration);
+            }
+        }
+    }
+    deepest
+}
+
+#[test]
+fn box_overlapping_wall_is_pushed_out() {
+    // 벽의 오른쪽 면은 x = 0.5, 상자의 왼쪽 면은 x = 0.2이므로 0.3만큼 겹친다.
+    let objects = [wall(0.0), cube(0.7, 0.5)];
+    let settings = DepenetrationSettings::default();
+    let result = depenetrate(&objects, &settings);
+
+    assert!(result.converged, "{result:?}");
+    assert!(result.iterations > 0 && result.iterations < settings.max_iterations, "{result:?}");
+    assert_eq!(result.corrections[0], glam::Vec3A::ZERO);
+    assert!(result.corrections[1].x > 0.0 && result.corrections[1].y.abs() < 1.0e-6, "{result:?}");
+
+    // 결과가 돌려준 residual은 밀어낸 뒤의 위치에서 측정한 값과 같아야 한다.
+    let residual = measure(&objects, &result);
+    assert!((result.residual_penetration - residual).abs() < 1.0e-5, "{result:?}, 다시 측정한 값 {residual}");
+    assert!(residual <= settings.tolerance);
+}
+
+#[test]
+fn separated_objects_need_no_iterations() {
+    let objects = [wall(0.0), cube(2.0, 0.5)];
+    let result = depenetrate(&objects, &DepenetrationSettings::default());
+
+    assert!(result.converged);
+    assert_eq!(result.iterations, 0);
+    assert_eq!(result.residual_penetration, 0.0);
+    assert_eq!(result.corrections[1], glam::Vec3A::ZERO);
+}
+
+#[test]
+fn box_wedged_between_walls_settles_in_the_gap() {
+    // 두 벽 사이의 틈은 x = -0.6 ~ 0.6이고 상자의 폭은 1.0이므로, 한가운데로 옮기면 들어간다.
+    let objects = [wall(-1.1), wall(1.1), cube(0.3, 0.5)];
+    let settings = DepenetrationSettings::default();
+    let result = depenetrate(&objects, &settings);
+
+    assert!(result.converged, "{result:?}");
+    let residual = measure(&objects, &result);
+    assert!((result.residual_penetration - residual).abs() < 1.0e-5, "{result:?}, 다시 측정한 값 {residual}");
+    let x = 0.3 + result.corrections[2].x;
+    assert!(x.abs() <= 0.1 + settings.tolerance, "틈 안에 있어야 한다: {x}");
+}
+
+#[test]
+fn box_wider_than_gap_does_not_converge() {
+    // 틈의 폭은 1.0이고 상자의 폭은 1.4이므로 어느 쪽으로 밀어도 0.2 이상 겹친다.
+    let objects = [wall(-1.0), wall(1.0), cube(0.0, 0.7)];
+    let settings = DepenetrationSettings::default();
+    let result = depenetrate(&objects, &settings);
+
+    assert!(!result.converged, "{result:?}");
+    assert_eq!(result.iterations, settings.max_iterations);
+    let residual = measure(&objects, &result);
+    assert!((result.residual_penetration - residual).abs() < 1.0e-5, "{result:?}, 다시 측정한 값 {residual}");
+    assert!(residual >= 0.2 - 1.0e-4, "{residual}");
+}
@@ -0,0 +1,142 @@
+use collision_test::{dynamic_collision::*, static_collision::StaticCollision, *};
+
+fn sphere(center: glam::Vec3, radius: f32) -> Sphere {
+    Sphere { center, radius }
+}
+
+#[test]
+fn fast_sphere_does_not_tunnel_through_thin_wall() {
+    // 두께 0.1의 벽을 한 번에 10만큼 지나가므로, 시작과 끝 위치만 보면 충돌을 놓친다.
+    let wall = BoundingBox::new(glam::Vec3::ZERO, glam::Vec3::new(0.05, 5.0, 5.0));
+    let start = sphere(glam::Vec3::new(-5.0, 0.0, 0.0), 0.1);
+    let end = sphere(glam::Vec3::new(5.0, 0.0, 0.0), 0.1);
+    assert!(!start.check_static_collision(&wall));
+    assert!(!end.check_static_collision(&wall));
+
+    let velocity = glam::Vec3A::new(10.0, 0.0, 0.0);
+    let (time, details) = start.time_of_impact(&velocity, &wall).unwrap();
+    // 구의 오른쪽 끝 -4.9가 벽의 왼쪽 면 -0.05에 닿을 때까지 4.85를 이동한다.
+    assert!((time - 0.485).abs() < 1.0e-4, "{time}");
+    assert!(details.normal.abs_diff_eq(glam::Vec3A::NEG_X, 1.0e-3), "{details:?}");
+    assert!((details.penetration - (1.0 - 0.485) * 10.0).abs() < 1.0e-2, "{details:?}");
+    assert!(start.check_dynamic_collision(&velocity, &wall));
+}
+
+#[test]
+fn box_sweep_matches_hand_computed_time() {
+    let target = BoundingBox::new(glam::Vec3::ZERO, glam::Vec3::splat(0.5));
+    let moving = BoundingBox::new(glam::Vec3::new(-5.0, 0.0, 0.0), glam::Vec3::splat(0.5));
+    let velocity = glam::Vec3A::new(10.0, 0.0, 0.0);
+
+    // 면과 면 사이의 거리 4를 10의 속도로 지나간다.
+    let (time, details) = moving.time_of_impact(&velocity, &target).unwrap();
+    assert!((time - 0.4).abs() < 1.0e-4, "{time}");
+    assert!(details.normal.abs_diff_eq(glam::Vec3A::NEG_X, 1.0e-3), "{details:?}");
+}
+
+#[test]
+fn sphere_sphere_time_of_impact_is_analytic() {
+    let target = sphere(glam::Vec3::ZERO, 1.0);
+    let velocity = glam::Vec3A::new(10.0, 0.0, 0.0);
+
+    // 정면: 중심 거리 5가 2가 될 때까지 3을 이동한다.
+    let moving = sphere(glam::Vec3::new(-5.0, 0.0, 0.0), 1.0);
+    let (time, details) = moving.time_of_impact(&velocity, &target).unwrap();
+    assert!((time - 0.3).abs() < 1.0e-6, "{time}");
+    assert!(details.normal.abs_diff_eq(glam::Vec3A::NEG_X, 1.0e-6), "{details:?}");
+    assert!((details.penetration - 7.0).abs() < 1.0e-5, "{details:?}");
+
+    // 비스듬히: y = 1에서 지나가면 중심의 x 거리가 sqrt(3)일 때 만난다.
+    let moving = sphere(glam::Vec3::new(-5.0, 1.0, 0.0), 1.0);
+    let (time, details) = moving.time_of_impact(&velocity, &target).unwrap();
+    let expected = (5.0 - 3.0_f32.sqrt()) / 10.0;
+    assert!((time - expected).abs() < 1.0e-6, "{time}, 예상 {expected}");
+    let normal = glam::Vec3A::new(-3.0_f32.sqrt(), 1.0, 0.0) / 2.0;
+    assert!(details.normal.abs_diff_eq(normal, 1.0e-5), "{details:?}");
+    // 구와 구는 반복하지 않으므로 설정과 관계없이 같은 결과가 나온다.
+    let config = SweepConfig {
+        max_iterations: 1,
+        tolerance: 1.0,
+    };
+    assert_eq!(moving.time_of_impact_with_config(&velocity, &target, &config).map(|(time, _)| time), Some(time));
+}
+
+#[test]
+fn sweep_reports_miss() {
+    let target = sphere(glam::Vec3::ZERO, 1.0);
+    let wall = BoundingBox::new(glam::Vec3::ZERO, glam::Vec3::splat(1.0));
+    let velocity = glam::Vec3A::new(10.0, 0.0, 0.0);
+
+    // 옆으로 지나감
+    let passing = sphere(glam::Vec3::new(-5.0, 2.5, 0.0), 1.0);
+    assert!(passing.time_of_impact(&velocity, &target).is_none());
+    assert!(passing.time_of_impact(&velocity, &wall).is_none());
+    // 멀어짐
+    let leaving = sphere(glam::Vec3::new(-5.0, 0.0, 0.0), 1.0);
+    assert!(leaving.time_of_impact(&-velocity, &target).is_none());
+    assert!(leaving.time_of_impact(&-velocity, &wall).is_none());
+    // 닿기 전에 멈춤: 3을 이동해야 닿는다.
+    let short = glam::Vec3A::new(2.9, 0.0, 0.0);
+    assert!(leaving.time_of_impact(&short, &target).is_none());
+    assert!(leaving.time_of_impact(&short, &wall).is_none());
+    assert!(!leaving.check_dynamic_collision(&short, &wall));
+}
+
+#[test]
+fn sweep_config_limits_iterations_and_tolerance() {
+    // 상자의 모서리를 스치듯 지나가 conservative advancement가 여러 번 반복해야 하는 경우
+    // 중심이 y = 0.9로 지나가는 구는 상자의 모서리 (-0.5, 0.5)와 x 거리가 0.3일 때 만난다.
+    let target = BoundingBox::new(glam::Vec3::ZERO, glam::Vec3::splat(0.5));
+    let moving = sphere(glam::Vec3::new(-5.0, 0.9, 0.0), 0.5);
+    let velocity = glam::Vec3A::new(10.0, 0.0, 0.0);
+    let expected = 4.2 / 10.0;
+
+    let (time, _) = moving.time_of_impact(&velocity, &target).unwrap();
+    assert!((time - expected).abs() < 1.0e-3, "{time}, 예상 {expected}");
+
+    // 반복이 모자라 수렴하지 않으면 만났다고 보고하지 않는다.
+    let config = SweepConfig {
+        max_iterations: 1,
+        ..SweepConfig::default()
+    };
+    assert!(moving.time_of_impact_with_config(&velocity, &target, &config).is_none());
+
+    // 허용 오차가 크면 일찍 멈추지만 지나치지는 않는다.
+    let config = SweepConfig {
+        tolerance: 0.1,
+        ..SweepConfig::default()
+    };
+    let (coarse, _) = moving.time_of_impact_with_config(&velocity, &target, &config).unwrap();
+    assert!(coarse <= time + 1.0e-6 && coarse > time - 0.05, "{coarse}, {time}");
+
+    // ColliderShape도 설정을 그대로 넘긴다.
+    let shape = ColliderShape::from(moving);
+    let (through_shape, _) = shape.time_of_impact_with_config(&velocity, &target, &config).unwrap();
+    assert_eq!(through_shape, coarse);
+}
+
+#[test]
+fn grazing_miss_is_not_reported_at_any_iteration_limit() {
+    // 상자의 모서리 바로 위를 gap만큼 떨어져 지나간다.
+    // 가장 가까워지는 순간까지 거리가 천천히 줄어들므로, 반복이 끝날 때까지 tolerance 안으로 들어오지 않는다.
+    let target = BoundingBox::new(glam::Vec3::ZERO, glam::Vec3::splat(0.5));
+    let velocity = glam::Vec3A::new(10.0, 0.0, 0.0);
+    for gap in [1.0e-2, 1.0e-3, 5.0e-4] {
+        let moving = sphere(glam::Vec3::new(-5.0, 1.0 + gap, 0.0), 0.5);
+        let cube = BoundingBox::new(glam::Vec3::new(-5.0, 1.0 + gap, 0.0), glam::Vec3::splat(0.5));
+        for max_iterations in 1..=32 {
+            let config = SweepConfig {
+                max_iterations,
+                ..SweepConfig::default()
+            };
+            let hit = moving.time_of_impact_with_config(&velocity, &target, &config);
+            assert!(hit.is_none(), "gap {gap}, {max_iterations}회: {hit:?}");
+            let hit = cube.time_of_impact_with_config(&velocity, &target, &config);
+            assert!(hit.is_none(), "gap {gap}, {max_iterations}회: {hit:?}");
+        }
+    }
+
+    // 같은 경로에서 gap만큼 아래로 지나가면 모서리에 닿는다.
+    let moving = sphere(glam::Vec3::new(-5.0, 1.0 - 1.0e-2, 0.0), 0.5);
+    assert!(moving.time_of_impact(&velocity, &target).is_some());
+}
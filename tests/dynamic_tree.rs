@@ -0,0 +1,169 @@
+mod common;
+
+use collision_test::{ray_cast::RayCast, *};
+use common::{all_pairs, random_box};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+/// 트리가 구한 쌍을 data(객체의 번호) 기준으로 정렬한다.
+fn tree_pairs(tree: &DynamicTree<usize>) -> Vec<(usize, usize)> {
+    let mut pairs = tree.query_pairs()
+        .into_iter()
+        .map(|(a, b)| {
+            let (a, b) = (*tree.get(a).unwrap(), *tree.get(b).unwrap());
+            (a.min(b), a.max(b))
+        })
+        .collect::<Vec<_>>();
+    pairs.sort();
+    let before_dedup = pairs.len();
+    pairs.dedup();
+    assert_eq!(pairs.len(), before_dedup, "중복된 쌍이 있다");
+    pairs
+}
+
+/// AVL 균형이 맞는 트리의 높이 상한 (잎 노드 n개)
+fn max_balanced_height(leaves: usize) -> i32 {
+    (1.45 * ((leaves + 2) as f32).log2()).ceil() as i32
+}
+
+#[test]
+fn pairs_match_all_pairs_through_insert_update_and_remove() {
+    let mut rng = StdRng::seed_from_u64(29);
+    // margin이 0이면 fat AABB가 실제 AABB와 같으므로 쌍이 정확히 같아야 한다.
+    let mut tree = DynamicTree::new(0.0);
+    let mut boxes = (0..300).map(|_| Some(random_box(&mut rng))).collect::<Vec<_>>();
+    let ids = boxes.iter()
+        .enumerate()
+        .map(|(i, bounding_box)| tree.insert(bounding_box.as_ref().unwrap(), i))
+        .collect::<Vec<_>>();
+    assert_eq!(tree_pairs(&tree), all_pairs(&boxes));
+
+    for round in 0..5 {
+        for i in 0..boxes.len() {
+            match rng.random_range(0..10) {
+                0..3 if let Some(old) = boxes[i] => {
+                    // 예전 AABB 안에 들어가면 트리를 갱신하지 않으므로 벗어나는 위치로 옮긴다.
+                    let bounding_box = loop {
+                        let bounding_box = random_box(&mut rng);
+                        if !old.contains(&bounding_box) {
+                            break bounding_box;
+                        }
+                    };
+                    assert!(tree.update(ids[i], &bounding_box));
+                    boxes[i] = Some(bounding_box);
+                }
+                3 if boxes[i].is_some() && round < 4 => {
+                    assert_eq!(tree.remove(ids[i]), Some(i));
+                    boxes[i] = None;
+                }
+                _ => {}
+            }
+        }
+        assert_eq!(tree.len(), boxes.iter().flatten().count());
+        assert_eq!(tree_pairs(&tree), all_pairs(&boxes), "round {round}");
+
+        for _ in 0..20 {
+            let query = random_box(&mut rng);
+            let mut actual = tree.query(&query).into_iter().map(|id| *tree.get(id).unwrap()).collect::<Vec<_>>();
+            actual.sort();
+            let expected = (0..boxes.len())
+                .filter(|&i| boxes[i].is_some_and(|bounding_box| bounding_box.aabb_collision(&query)))
+                .collect::<Vec<_>>();
+            assert_eq!(actual, expected);
+        }
+        assert!(tree.height() <= max_balanced_height(tree.len()), "높이 {}, 객체 {}", tree.height(), tree.len());
+    }
+}
+
+#[test]
+fn fat_aabb_pairs_contain_all_overlapping_pairs() {
+    let mut rng = StdRng::seed_from_u64(30);
+    let margin = 0.5;
+    let mut tree = DynamicTree::new(margin);
+    let mut boxes = (0..200).map(|_| Some(random_box(&mut rng))).collect::<Vec<_>>();
+    let ids = boxes.iter()
+        .enumerate()
+        .map(|(i, bounding_box)| tree.insert(bounding_box.as_ref().unwrap(), i))
+        .collect::<Vec<_>>();
+
+    for _ in 0..10 {
+        // 조금씩 움직이면 대부분 fat AABB 안에 머문다.
+        let mut refits = 0;
+        for i in 0..boxes.len() {
+            let mut bounding_box = boxes[i].unwrap();
+            bounding_box.center += glam::Vec3::new(rng.random_range(-0.3..0.3), rng.random_range(-0.3..0.3), rng.random_range(-0.3..0.3));
+            let fat = tree.fat_aabb(ids[i]).unwrap();
+            let refit = tree.update(ids[i], &bounding_box);
+            assert_eq!(refit, !fat.contains(&bounding_box));
+            refits += refit as usize;
+            assert!(tree.fat_aabb(ids[i]).unwrap().contains(&bounding_box));
+            boxes[i] = Some(bounding_box);
+        }
+        assert!(refits < boxes.len(), "{refits}");
+
+        // 실제로 겹치는 쌍은 모두 있고, 그 밖의 쌍은 fat AABB끼리 겹친다.
+        let pairs = tree_pairs(&tree);
+        for pair in all_pairs(&boxes) {
+            assert!(pairs.binary_search(&pair).is_ok(), "{pair:?}가 없다");
+        }
+        for &(a, b) in &pairs {
+            assert!(tree.fat_aabb(ids[a]).unwrap().aabb_collision(&tree.fat_aabb(ids[b]).unwrap()));
+        }
+    }
+}
+
+#[test]
+fn moved_object_is_found_only_at_new_position() {
+    let mut tree = DynamicTree::new(0.1);
+    let unit = glam::Vec3::splat(0.5);
+    let others = (0..20)
+        .map(|i| tree.insert(&BoundingBox::new(glam::Vec3::new(i as f32 * 2.0, 0.0, 0.0), unit), i))
+        .collect::<Vec<_>>();
+    let mover = tree.insert(&BoundingBox::new(glam::Vec3::new(0.0, 10.0, 0.0), unit), 100);
+
+    // fat AABB 안에서의 이동은 트리를 바꾸지 않는다.
+    assert!(!tree.update(mover, &BoundingBox::new(glam::Vec3::new(0.05, 10.0, 0.0), unit)));
+
+    let new_position = BoundingBox::new(glam::Vec3::new(30.0, 0.0, 0.0), unit);
+    assert!(tree.update(mover, &new_position));
+    assert!(tree.query(&BoundingBox::new(glam::Vec3::new(0.0, 10.0, 0.0), unit)).is_empty());
+    let mut found = tree.query(&new_position);
+    found.sort();
+    assert_eq!(found, vec![others[15], mover]);
+    let pairs = tree_pairs(&tree);
+    assert_eq!(pairs, vec![(15, 100)]);
+
+    let ray = Ray::new(glam::Vec3A::new(100.0, 0.0, 0.0), glam::Vec3A::NEG_X);
+    let hit = tree.ray_cast(&ray, 200.0, |id, _, max_distance| {
+        tree.fat_aabb(id).unwrap().cast_ray(&ray, max_distance).map(|hit| hit.distance)
+    });
+    assert!(matches!(hit, Some((id, _)) if id == others[19]), "{hit:?}");
+}
+
+#[test]
+fn sorted_inserts_stay_balanced() {
+    // 한 줄로 정렬된 순서로 넣는 것은 회전이 없으면 한쪽으로 치우친 트리가 된다.
+    let mut tree = DynamicTree::new(0.0);
+    let mut ids = Vec::new();
+    for i in 0..1024 {
+        ids.push(tree.insert(&BoundingBox::new(glam::Vec3::new(i as f32, 0.0, 0.0), glam::Vec3::splat(0.4)), i));
+        assert!(tree.height() <= max_balanced_height(tree.len()), "높이 {}, 객체 {}", tree.height(), tree.len());
+    }
+
+    // 한쪽 절반을 지워도 균형을 유지한다.
+    for &id in &ids[..768] {
+        tree.remove(id);
+    }
+    assert_eq!(tree.len(), 256);
+    assert!(tree.height() <= max_balanced_height(tree.len()), "높이 {}", tree.height());
+    assert_eq!(tree_pairs(&tree).len(), 0);
+
+    // 모두 지우면 비어 있고, 다시 넣을 수 있다.
+    for &id in &ids[768..] {
+        assert!(tree.remove(id).is_some());
+    }
+    assert!(tree.is_empty());
+    assert_eq!(tree.height(), 0);
+    assert!(tree.remove(ids[0]).is_none());
+    let id = tree.insert(&BoundingBox::new(glam::Vec3::ZERO, glam::Vec3::ONE), 0);
+    assert_eq!(tree.query(&BoundingBox::new(glam::Vec3::ZERO, glam::Vec3::ONE)), vec![id]);
+}
@@ -0,0 +1,28 @@
+use collision_test::{BoundingBox, ConvexHull, GjkConfig, GjkError, Sphere};
+
+fn bad_colliders() -> Vec<BoundingBox> {
+    vec![
+        BoundingBox::new(glam::Vec3::new(f32::NAN, 0.0, 0.0), glam::Vec3::ONE),
+        BoundingBox::new(glam::Vec3::ZERO, glam::Vec3::new(1.0, f32::NAN, 1.0)),
+        BoundingBox::new_rotated(glam::Vec3::ZERO, glam::Vec3::ONE, glam::Mat3::from_cols_array(&[f32::NAN; 9])),
+        BoundingBox::new(glam::Vec3::splat(f32::INFINITY), glam::Vec3::ONE),
+    ]
+}
+
+#[test]
+fn nan_colliders_return_numerical_failure_instead_of_panicking() {
+    let good = BoundingBox::new_rotated(glam::Vec3::ZERO, glam::Vec3::ONE, glam::Mat3::from_rotation_y(0.4));
+    let sphere = Sphere {
+        center: glam::Vec3::ZERO,
+        radius: 1.0,
+    };
+    let config = GjkConfig::default();
+    for bad in bad_colliders() {
+        assert!(matches!(bad.gjk(&good), Err(GjkError::NumericalFailure(_))), "{bad:?}");
+        assert!(matches!(good.gjk(&bad), Err(GjkError::NumericalFailure(_))), "{bad:?}");
+        assert!(matches!(bad.gjk_epa(&good), Err(GjkError::NumericalFailure(_))), "{bad:?}");
+        assert!(matches!(sphere.gjk_epa(&bad), Err(GjkError::NumericalFailure(_))), "{bad:?}");
+        assert!(matches!(bad.gjk_distance(&good, &config), Err(GjkError::NumericalFailure(_))), "{bad:?}");
+        assert!(matches!(sphere.gjk_distance(&bad, &config), Err(GjkError::NumericalFailure(_))), "{bad:?}");
+    }
+}
@@ -0,0 +1,270 @@
+use collision_test::{point_query::PointQuery, static_collision::StaticCollision, *};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+const SCALES: [f32; 7] = [0.001, 0.01, 0.1, 1.0, 10.0, 100.0, 1000.0];
+
+fn cube(center: glam::Vec3, half: f32) -> BoundingBox {
+    BoundingBox::new(center, glam::Vec3::splat(half))
+}
+
+#[test]
+fn overlapping_boxes_report_depth_proportional_to_scale() {
+    for scale in SCALES {
+        let a = cube(glam::Vec3::ZERO, scale);
+        let b = cube(glam::Vec3::new(1.5, 0.2, 0.1) * scale, scale);
+
+        let details = a.gjk_epa(&b)
+            .unwrap_or_else(|error| panic!("scale {scale}: {error}"))
+            .unwrap_or_else(|| panic!("scale {scale}: missed"));
+        assert!((details.penetration - 0.5 * scale).abs() <= 0.001 * scale, "scale {scale}: {details:?}");
+        assert!(details.normal.dot(-glam::Vec3A::X) > 0.999, "scale {scale}: {details:?}");
+    }
+}
+
+#[test]
+fn separated_boxes_miss_at_every_scale() {
+    for scale in SCALES {
+        let a = cube(glam::Vec3::ZERO, scale);
+        let b = cube(glam::Vec3::new(2.1, 0.3, -0.2) * scale, scale);
+
+        assert!(matches!(a.gjk(&b), Ok(None)), "scale {scale}");
+        assert!(matches!(a.gjk_epa(&b), Ok(None)), "scale {scale}");
+    }
+}
+
+#[test]
+fn rotated_boxes_agree_with_sat_at_every_scale() {
+    let rotation = glam::Mat3::from_euler(glam::EulerRot::XYZ, 0.4, 0.9, 0.2);
+    let offsets = [
+        glam::Vec3::new(1.9, 0.4, 0.0),
+        glam::Vec3::new(2.4, 0.4, 0.0),
+        glam::Vec3::new(0.3, 2.3, 0.8),
+        glam::Vec3::new(0.3, 2.9, 0.8),
+    ];
+    for scale in SCALES {
+        let a = BoundingBox::new_rotated(glam::Vec3::ZERO, glam::Vec3::new(1.0, 0.5, 0.8) * scale, rotation);
+        for offset in offsets {
+            let b = cube(offset * scale, scale);
+            let hit = a.gjk(&b).unwrap_or_else(|error| panic!("scale {scale}: {error}"));
+            assert_eq!(hit.is_some(), a.obb_collision(&b), "scale {scale}, offset {offset}");
+        }
+    }
+}
+
+#[test]
+fn overlapping_spheres_report_depth_proportional_to_scale() {
+    for scale in SCALES {
+        let a = Sphere {
+            center: glam::Vec3::ZERO,
+            radius: scale,
+        };
+        let b = Sphere {
+            center: glam::Vec3::new(0.0, 1.5, 0.0) * scale,
+            radius: scale,
+        };
+
+        let details = a.gjk_epa(&b)
+            .unwrap_or_else(|error| panic!("scale {scale}: {error}"))
+            .unwrap_or_else(|| panic!("scale {scale}: missed"));
+        assert!((details.penetration - 0.5 * scale).abs() <= 0.01 * scale, "scale {scale}: {details:?}");
+        assert!(details.normal.dot(-glam::Vec3A::Y) > 0.99, "scale {scale}: {details:?}");
+    }
+}
+
+#[test]
+fn contact_margin_reports_near_contacts() {
+    for scale in SCALES {
+        let a = cube(glam::Vec3::ZERO, scale);
+        let b = cube(glam::Vec3::new(2.05, 0.0, 0.0) * scale, scale);
+        let config = GjkConfig {
+            contact_margin: 0.1 * scale,
+            ..Default::default()
+        };
+
+        assert!(matches!(a.gjk(&b), Ok(None)), "scale {scale}");
+        let details = a.gjk_epa_with_config(&b, &config)
+            .unwrap_or_else(|error| panic!("scale {scale}: {error}"))
+            .unwrap_or_else(|| panic!("scale {scale}: missed"));
+        assert!((details.penetration + 0.05 * scale).abs() <= 0.001 * scale, "scale {scale}: {details:?}");
+        assert!(details.normal.dot(-glam::Vec3A::X) > 0.999, "scale {scale}: {details:?}");
+    }
+}
+
+/// 선분 위에서 point와 가장 가까운 점까지의 거리
+fn segment_distance(point: glam::Vec3A, start: glam::Vec3A, end: glam::Vec3A) -> f32 {
+    let edge = end - start;
+    let t = ((point - start).dot(edge) / edge.length_squared()).clamp(0.0, 1.0);
+    (start + edge * t - point).length()
+}
+
+/// 반지름 0인 구 (점)
+fn point(center: glam::Vec3A) -> Sphere {
+    Sphere {
+        center: center.into(),
+        radius: 0.0,
+    }
+}
+
+#[test]
+fn signed_volume_handles_near_degenerate_triangles() {
+    // 높이가 거의 0인 삼각형은 Minkowski 차의 simplex도 납작한 삼각형이 된다.
+    // 넓이로 나누는 대신 변으로 줄여야 하므로, 세 변까지의 거리(오차는 높이 이내)와 비교한다.
+    let mut rng = StdRng::seed_from_u64(45);
+    for height in [1.0e-2, 1.0e-4, 1.0e-6, 0.0] {
+        let triangle = Triangle::new(
+            glam::Vec3A::ZERO,
+            glam::Vec3A::new(2.0, 0.0, 0.0),
+            glam::Vec3A::new(rng.random_range(0.0..2.0), height, 0.0),
+        );
+        let [a, b, c] = triangle.vertices;
+        for _ in 0..200 {
+            let p = glam::Vec3A::new(rng.random_range(-1.0..3.0), rng.random_range(-1.0..1.0), rng.random_range(-1.0..1.0));
+            let expected = segment_distance(p, a, b).min(segment_distance(p, b, c)).min(segment_distance(p, c, a));
+            if expected < 1.0e-3 {
+                continue;
+            }
+
+            let closest = triangle.gjk_distance(&point(p), &GjkConfig::default())
+                .unwrap_or_else(|error| panic!("height {height}, {p}: {error}"))
+                .unwrap_or_else(|| panic!("height {height}, {p}: reported as overlapping"));
+            assert!((closest.distance - expected).abs() <= height + 1.0e-4, "height {height}, {p}: {closest:?} != {expected}");
+            assert!(closest.point_b.abs_diff_eq(p, 1.0e-6), "height {height}, {p}: {closest:?}");
+            assert!(closest.distance.is_finite() && closest.point_a.is_finite(), "height {height}, {p}: {closest:?}");
+            // point_a는 삼각형 위에 있다.
+            let on_triangle = segment_distance(closest.point_a, a, b).min(segment_distance(closest.point_a, b, c)).min(segment_distance(closest.point_a, c, a));
+            assert!(on_triangle <= height + 1.0e-4 && closest.point_a.z.abs() <= 1.0e-5, "height {height}, {p}: {closest:?}");
+        }
+    }
+}
+
+#[test]
+fn signed_volume_handles_flat_tetrahedra() {
+    // 두께가 거의 0인 박스의 정점들은 납작한 사면체를 만든다. 해석적인 박스 거리와 비교한다.
+    let mut rng = StdRng::seed_from_u64(46);
+    for thickness in [1.0e-3, 1.0e-5, 1.0e-7] {
+        let flat = BoundingBox::new_rotated(
+            glam::Vec3::new(0.5, -0.2, 0.1),
+            glam::Vec3::new(1.0, 0.7, thickness),
+            glam::Mat3::from_euler(glam::EulerRot::XYZ, 0.3, -0.5, 0.8),
+        );
+        let vertex_box = VertexBox::from(&flat);
+        let mut separated = 0;
+        for _ in 0..200 {
+            let p = glam::Vec3A::new(rng.random_range(-2.0..2.0), rng.random_range(-2.0..2.0), rng.random_range(-2.0..2.0));
+            let expected = flat.distance_to_point(&p);
+            if expected < 1.0e-3 {
+                continue;
+            }
+            let closest = vertex_box.gjk_distance(&point(p), &GjkConfig::default())
+                .unwrap_or_else(|error| panic!("thickness {thickness}, {p}: {error}"))
+                .unwrap_or_else(|| panic!("thickness {thickness}, {p}: reported as overlapping"));
+            assert!((closest.distance - expected).abs() <= 1.0e-4, "thickness {thickness}, {p}: {closest:?} != {expected}");
+            assert!(flat.distance_to_point(&closest.point_a) <= 1.0e-4, "thickness {thickness}, {p}: {closest:?}");
+            separated += 1;
+        }
+        assert!(separated > 150, "{separated}");
+
+        // 납작한 박스 안의 점은 겹친 것으로 본다.
+        let inside = glam::Vec3A::from(flat.center);
+        assert!(matches!(vertex_box.gjk_distance(&point(inside), &GjkConfig::default()), Ok(None)), "thickness {thickness}");
+    }
+}
+
+/// GJK가 점 하나의 simplex로 끝나는 접촉(처음 support point가 곧 원점)인지 확인하고, EPA의 결과를 반환한다.
+fn touching_point_simplex(a: &impl ConvexHull, b: &impl ConvexHull) -> CollisionDetails {
+    let mut diagnostics = GjkDiagnostics::default();
+    let simplex = a.gjk_with_diagnostics(b, &GjkConfig::default(), &mut GjkCache::default(), &mut diagnostics)
+        .unwrap()
+        .expect("missed");
+    assert_eq!(diagnostics.termination, Some(Termination::Touching), "{simplex:?}");
+    assert_eq!(simplex.dimension(), 0, "{simplex:?}");
+
+    let details = a.gjk_epa_with_diagnostics(b, &GjkConfig::default(), &mut diagnostics)
+        .unwrap()
+        .expect("missed");
+    assert!(matches!(diagnostics.termination, Some(Termination::Converged | Termination::Stalled)), "{diagnostics:?}");
+    assert!(details.penetration.abs() <= 1.0e-5, "{details:?}");
+    assert!((details.normal.length() - 1.0).abs() <= 1.0e-5, "{details:?}");
+    details
+}
+
+#[test]
+fn touching_simplices_are_blown_up_into_a_tetrahedron() {
+    // EPA는 GJK가 남긴 점 하나에서 주축 방향의 support point를 더해 사면체로 넓힌 뒤 penetration 0을 구한다.
+    // normal은 b에서 a를 향한다.
+    let a = cube(glam::Vec3::ZERO, 1.0);
+    // 면끼리 맞댄 박스 (x 방향의 첫 support point가 양쪽 모두 (1, 1, 1))
+    let details = touching_point_simplex(&a, &cube(glam::Vec3::new(2.0, 0.0, 0.0), 1.0));
+    assert!(details.normal.abs_diff_eq(-glam::Vec3A::X, 1.0e-5), "{details:?}");
+    // 꼭짓점에 닿은 점: 꼭짓점에 모인 세 면 중 하나의 법선벡터
+    let details = touching_point_simplex(&a, &point(glam::Vec3A::ONE));
+    assert!(details.normal.dot(-glam::Vec3A::ONE) > 0.0 && details.normal.abs().max_element() > 1.0 - 1.0e-5, "{details:?}");
+
+    // 곡면끼리 닿은 구
+    let ball = Sphere {
+        center: glam::Vec3::ZERO,
+        radius: 1.0,
+    };
+    let other = Sphere {
+        center: glam::Vec3::new(1.5, 0.0, 0.0),
+        radius: 0.5,
+    };
+    // 곡면은 polytope의 면으로 근사하므로 normal이 조금 기울 수 있다.
+    let details = touching_point_simplex(&ball, &other);
+    assert!(details.normal.dot(-glam::Vec3A::X) > 0.99, "{details:?}");
+    let details = touching_point_simplex(&ball, &point(glam::Vec3A::X));
+    assert!(details.normal.dot(-glam::Vec3A::X) > 0.99, "{details:?}");
+
+    // 같은 평면 위에서 겹친 두 삼각형은 Minkowski 차가 납작하여 사면체로 넓힐 수 없다.
+    let triangle = Triangle::new(glam::Vec3A::ZERO, glam::Vec3A::X, glam::Vec3A::Y);
+    let other = Triangle::new(glam::Vec3A::splat(0.2).with_z(0.0), glam::Vec3A::new(1.2, 0.2, 0.0), glam::Vec3A::new(0.2, 1.2, 0.0));
+    assert!(triangle.gjk(&other).unwrap().is_some());
+    assert!(matches!(triangle.gjk_epa(&other), Err(GjkError::DegenerateSimplex(_))));
+}
+
+#[test]
+fn margin_switches_to_epa_once_the_cores_overlap() {
+    // 구의 core는 중심점이므로, 중심이 박스 밖이면 core의 거리로, 박스 면 위나 안이면 EPA로 구한다.
+    // 어느 쪽이든 penetration은 구와 박스의 해석적인 값과 같고, 경계에서 이어진다.
+    let a = cube(glam::Vec3::ZERO, 1.0);
+    let radius = 0.5;
+    for x in [1.45, 1.2, 1.0 + 1.0e-4, 1.0, 1.0 - 1.0e-4, 0.8, 0.6] {
+        let b = Sphere {
+            center: glam::Vec3::new(x, 0.3, -0.2),
+            radius,
+        };
+        let details = a.gjk_epa_with_margin(&b, &GjkConfig::default())
+            .unwrap_or_else(|error| panic!("{x}: {error}"))
+            .unwrap_or_else(|| panic!("{x}: missed"));
+        let expected = a.check_static_collision_details(&b).unwrap();
+        assert!((details.penetration - expected.penetration).abs() <= 1.0e-4, "{x}: {details:?} != {expected:?}");
+        // core 사이의 거리가 아주 작으면 최근접점의 오차가 그만큼 normal을 기울인다.
+        assert!(details.normal.abs_diff_eq(expected.normal, 1.0e-3), "{x}: {details:?} != {expected:?}");
+        if x <= 1.0 {
+            // 중심이 면 위나 박스 안에 있으면 core끼리 닿거나 겹치므로 EPA의 결과와 같아야 한다.
+            let epa = a.gjk_epa(&b).unwrap().unwrap();
+            assert_eq!(details.penetration, epa.penetration, "{x}");
+            assert_eq!(details.normal, epa.normal, "{x}");
+        }
+    }
+
+    // 박스끼리는 core가 곧 박스이므로 겹치면 EPA와 같다.
+    let c = cube(glam::Vec3::new(1.5, 0.0, 0.0), 1.0);
+    let details = a.gjk_epa_with_margin(&c, &GjkConfig::default()).unwrap().unwrap();
+    assert_eq!(details.penetration, a.gjk_epa(&c).unwrap().unwrap().penetration);
+}
+
+#[test]
+fn rounded_boxes_keep_their_size_and_round_the_corners() {
+    let a = cube(glam::Vec3::ZERO, 1.0).rounded(0.2);
+    let b = cube(glam::Vec3::new(1.9, 0.0, 0.0), 1.0).rounded(0.2);
+    let details = a.gjk_epa_with_margin(&b, &GjkConfig::default()).unwrap().unwrap();
+    assert!((details.penetration - 0.1).abs() <= 0.0001, "{details:?}");
+    assert!(details.normal.dot(-glam::Vec3A::X) > 0.9999, "{details:?}");
+
+    // 모서리끼리는 둥글게 깎인 만큼 떨어진다.
+    let corner = cube(glam::Vec3::new(1.95, 1.95, 1.95), 1.0).rounded(0.2);
+    assert!(matches!(a.gjk_epa_with_margin(&corner, &GjkConfig::default()), Ok(None)));
+    let sharp = cube(glam::Vec3::new(1.95, 1.95, 1.95), 1.0);
+    assert!(cube(glam::Vec3::ZERO, 1.0).gjk_epa(&sharp).unwrap().is_some());
+}
@@ -0,0 +1,31 @@
+use collision_test::BoundingBox;
+
+#[test]
+fn obb_sat_reports_edge_contact_on_cross_axis() {
+    // 두 박스를 x, z축으로 45도 돌려 모서리끼리 엇갈리게 맞댄다.
+    let a = BoundingBox::new_rotated(glam::Vec3::ZERO, glam::Vec3::ONE, glam::Mat3::from_rotation_x(45.0_f32.to_radians()));
+    let b = BoundingBox::new_rotated(
+        glam::Vec3::new(0.0, 2.0 * 2.0_f32.sqrt() - 0.1, 0.0),
+        glam::Vec3::ONE,
+        glam::Mat3::from_rotation_z(45.0_f32.to_radians()),
+    );
+    assert!(a.obb_collision(&b));
+
+    let details = a.obb_collision_details(&b).unwrap();
+    // b가 +y 방향에 있으므로 normal * penetration은 -y 방향이다.
+    let push = details.normal * details.penetration;
+    assert!((push.y + 0.1).abs() <= 1.0e-4, "{details:?}");
+    assert!(push.x.abs() <= 1.0e-4 && push.z.abs() <= 1.0e-4, "{details:?}");
+
+    let apart = BoundingBox::new_rotated(b.center + glam::Vec3::new(0.0, 0.2, 0.0), glam::Vec3::ONE, b.rotation().unwrap());
+    assert!(!apart.obb_collision(&a));
+    assert!(apart.obb_collision_details(&a).is_none());
+}
+
+#[test]
+fn obb_sat_does_not_panic_on_nan() {
+    let a = BoundingBox::new_rotated(glam::Vec3::ZERO, glam::Vec3::ONE, glam::Mat3::from_rotation_y(0.3));
+    let b = BoundingBox::new_rotated(glam::Vec3::splat(f32::NAN), glam::Vec3::ONE, glam::Mat3::from_rotation_x(0.3));
+    let _ = a.obb_collision(&b);
+    let _ = a.obb_collision_details(&b);
+}
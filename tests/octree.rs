@@ -0,0 +1,171 @@
+mod common;
+
+use collision_test::{ray_cast::RayCast, static_collision::StaticCollision, *};
+use common::all_pairs;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+/// 월드 경계 -25 ~ 25
+fn world() -> BoundingBox {
+    BoundingBox::new(glam::Vec3::ZERO, glam::Vec3::splat(25.0))
+}
+
+fn random_point(rng: &mut StdRng, range: f32) -> glam::Vec3 {
+    glam::Vec3::new(rng.random_range(-range..range), rng.random_range(-range..range), rng.random_range(-range..range))
+}
+
+/// 대부분은 작고, 일부는 크거나 월드 경계 밖에 걸친 박스  
+/// 월드 경계를 시험해야 하므로 common::random_box와 분포가 다르다.  
+fn random_box(rng: &mut StdRng) -> BoundingBox {
+    let max_extent = if rng.random_bool(0.1) { 8.0 } else { 1.5 };
+    let extents = glam::Vec3::new(rng.random_range(0.1..max_extent), rng.random_range(0.1..max_extent), rng.random_range(0.1..max_extent));
+    BoundingBox::new(random_point(rng, 30.0), extents)
+}
+
+fn octree_pairs(octree: &mut LooseOctree<BroadphaseProxy<usize>>) -> Vec<(usize, usize)> {
+    let mut pairs = octree.candidate_pairs()
+        .into_iter()
+        .map(|(a, b)| (a.min(b), a.max(b)))
+        .collect::<Vec<_>>();
+    pairs.sort();
+    let before_dedup = pairs.len();
+    pairs.dedup();
+    assert_eq!(pairs.len(), before_dedup, "중복된 쌍이 있다");
+    pairs
+}
+
+/// 영역, 구, ray 질의를 모든 객체와 비교한다.
+fn check_queries(octree: &LooseOctree<BoundingBox>, boxes: &[Option<BoundingBox>], ids: &[ProxyId], rng: &mut StdRng) {
+    let index_of = |id: ProxyId| ids.iter().position(|&other| other == id).unwrap();
+    let alive = || boxes.iter().enumerate().filter_map(|(i, bounding_box)| bounding_box.map(|bounding_box| (i, bounding_box)));
+
+    for _ in 0..30 {
+        let query = random_box(rng);
+        let mut actual = octree.query_aabb(&query).into_iter().map(index_of).collect::<Vec<_>>();
+        actual.sort();
+        let expected = alive().filter(|(_, bounding_box)| bounding_box.aabb_collision(&query)).map(|(i, _)| i).collect::<Vec<_>>();
+        assert_eq!(actual, expected);
+
+        let sphere = Sphere {
+            center: random_point(rng, 30.0),
+            radius: rng.random_range(0.5..5.0),
+        };
+        let mut actual = octree.query_sphere(&sphere).into_iter().map(index_of).collect::<Vec<_>>();
+        actual.sort();
+        let expected = alive().filter(|(_, bounding_box)| sphere.check_static_collision(bounding_box)).map(|(i, _)| i).collect::<Vec<_>>();
+        assert_eq!(actual, expected);
+
+        let mut direction = glam::Vec3A::from(random_point(rng, 1.0));
+        if rng.random_bool(0.3) {
+            direction[rng.random_range(0..3)] = 0.0;
+        }
+        let ray = Ray::new(random_point(rng, 40.0).into(), direction);
+        let expected = alive()
+            .filter_map(|(_, bounding_box)| bounding_box.cast_ray(&ray, 80.0))
+            .map(|hit| hit.distance)
+            .min_by(f32::total_cmp);
+        let actual = octree.ray_cast(&ray, 80.0).map(|(_, hit)| hit.distance);
+        match (expected, actual) {
+            (None, None) => {}
+            (Some(expected), Some(actual)) => assert!((expected - actual).abs() < 1.0e-4, "{ray:?}: {actual} != {expected}"),
+            _ => panic!("{ray:?}: 예상 {expected:?}, 결과 {actual:?}"),
+        }
+    }
+}
+
+#[test]
+fn queries_match_brute_force_through_insert_update_and_remove() {
+    let mut rng = StdRng::seed_from_u64(33);
+    let mut octree = LooseOctree::new(&world(), OctreeSettings::default());
+    let mut boxes = (0..400).map(|_| Some(random_box(&mut rng))).collect::<Vec<_>>();
+    let ids = boxes.iter().map(|bounding_box| octree.insert(bounding_box.unwrap())).collect::<Vec<_>>();
+    assert!(octree.stats().depth_histogram.len() > 2, "{:?}", octree.stats());
+    check_queries(&octree, &boxes, &ids, &mut rng);
+
+    for _ in 0..3 {
+        for i in 0..boxes.len() {
+            match rng.random_range(0..10) {
+                // 조금 움직이기 (대부분 같은 노드에 남는다)
+                0..4 if let Some(mut bounding_box) = boxes[i] => {
+                    bounding_box.center += random_point(&mut rng, 1.0);
+                    octree.update(ids[i], bounding_box);
+                    boxes[i] = Some(bounding_box);
+                }
+                // 멀리 옮기기
+                4 if boxes[i].is_some() => {
+                    let bounding_box = random_box(&mut rng);
+                    octree.update(ids[i], bounding_box);
+                    boxes[i] = Some(bounding_box);
+                }
+                5 if boxes[i].is_some() => {
+                    assert!(octree.remove(ids[i]).is_some());
+                    boxes[i] = None;
+                }
+                _ => {}
+            }
+        }
+        assert_eq!(octree.len(), boxes.iter().flatten().count());
+        check_queries(&octree, &boxes, &ids, &mut rng);
+    }
+}
+
+#[test]
+fn candidate_pairs_match_all_pairs() {
+    let mut rng = StdRng::seed_from_u64(34);
+    let mut octree = LooseOctree::new(&world(), OctreeSettings::default());
+    let mut boxes = (0..400).map(|_| Some(random_box(&mut rng))).collect::<Vec<_>>();
+    let ids = boxes.iter()
+        .enumerate()
+        .map(|(i, bounding_box)| octree.insert_proxy(bounding_box.as_ref().unwrap(), i))
+        .collect::<Vec<_>>();
+    assert_eq!(octree_pairs(&mut octree), all_pairs(&boxes));
+
+    for round in 0..3 {
+        for i in 0..boxes.len() {
+            match rng.random_range(0..10) {
+                0..3 if boxes[i].is_some() => {
+                    let bounding_box = random_box(&mut rng);
+                    octree.update_proxy(ids[i], &bounding_box);
+                    boxes[i] = Some(bounding_box);
+                }
+                3 if boxes[i].is_some() => {
+                    octree.remove_proxy(ids[i]);
+                    boxes[i] = None;
+                }
+                _ => {}
+            }
+        }
+        assert_eq!(octree_pairs(&mut octree), all_pairs(&boxes), "round {round}");
+    }
+}
+
+#[test]
+fn removing_everything_merges_nodes_back() {
+    let mut rng = StdRng::seed_from_u64(35);
+    let mut octree = LooseOctree::new(&world(), OctreeSettings::default());
+    let ids = (0..200).map(|_| octree.insert(random_box(&mut rng))).collect::<Vec<_>>();
+    assert!(octree.stats().node_count > 1);
+
+    for id in ids {
+        assert!(octree.remove(id).is_some());
+        assert!(octree.remove(id).is_none());
+    }
+    assert!(octree.is_empty());
+    let stats = octree.stats();
+    assert_eq!(stats.node_count, 1, "{stats:?}");
+    assert!(octree.query_aabb(&world()).is_empty());
+}
+
+#[test]
+fn objects_outside_world_are_still_found() {
+    let mut octree = LooseOctree::new(&world(), OctreeSettings::default());
+    // 루트 노드의 느슨한 경계(-50 ~ 50)보다 먼 객체
+    let far = BoundingBox::new(glam::Vec3::new(200.0, 0.0, 0.0), glam::Vec3::ONE);
+    let id = octree.insert(far);
+    for i in 0..20 {
+        octree.insert(BoundingBox::new(glam::Vec3::new(i as f32 - 10.0, 0.0, 0.0), glam::Vec3::splat(0.4)));
+    }
+
+    assert_eq!(octree.query_aabb(&far), vec![id]);
+    let ray = Ray::new(glam::Vec3A::new(300.0, 0.0, 0.0), glam::Vec3A::NEG_X);
+    assert!(matches!(octree.ray_cast(&ray, 150.0), Some((hit, _)) if hit == id));
+}
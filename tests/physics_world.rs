@@ -0,0 +1,243 @@
+use collision_test::*;
+
+const DT: f32 = 1.0 / 60.0;
+
+fn ground(world: &mut PhysicsWorld) -> ColliderHandle {
+    let ground = BoundingBox::new(glam::Vec3::new(0.0, -0.5, 0.0), glam::Vec3::new(10.0, 0.5, 10.0));
+    let shape = ColliderShape::from(ground);
+    world.insert(shape, RigidBody::new(BodyType::Static, &shape, 0.0))
+}
+
+fn dynamic(world: &mut PhysicsWorld, shape: impl Into<ColliderShape>) -> ColliderHandle {
+    let shape = shape.into();
+    world.insert(shape, RigidBody::new(BodyType::Dynamic, &shape, 1.0))
+}
+
+#[test]
+fn touching_boxes_have_normal_toward_self() {
+    // 접하기만 하면 penetration이 0이므로 normal만으로 밀어낼 방향을 알 수 있어야 한다.
+    let below = BoundingBox::new(glam::Vec3::new(0.0, -0.5, 0.0), glam::Vec3::new(10.0, 0.5, 10.0));
+    let above = BoundingBox::new(glam::Vec3::new(0.0, 0.5, 0.0), glam::Vec3::splat(0.5));
+
+    for (a, b, expected) in [(above, below, glam::Vec3A::Y), (below, above, glam::Vec3A::NEG_Y)] {
+        let details = a.aabb_collision_details(&b).unwrap();
+        assert_eq!(details.penetration, 0.0);
+        assert_eq!(details.normal, expected, "{details:?}");
+
+        let details = a.obb_collision_details(&b).unwrap().with_positive_penetration();
+        assert!(details.penetration < 1.0e-5, "{details:?}");
+        assert!(details.normal.abs_diff_eq(expected, 1.0e-6), "{details:?}");
+
+        let details = ColliderShape::from(a).collision_details(&ColliderShape::from(b)).unwrap();
+        assert!(details.normal.abs_diff_eq(expected, 1.0e-6), "{details:?}");
+    }
+}
+
+#[test]
+fn box_dropped_on_ground_comes_to_rest() {
+    let mut world = PhysicsWorld::default();
+    ground(&mut world);
+    let cube = BoundingBox::new(glam::Vec3::new(0.0, 2.0, 0.0), glam::Vec3::splat(0.5));
+    let handle = dynamic(&mut world, cube);
+
+    for _ in 0..300 {
+        world.step(DT);
+    }
+
+    let body = world.body(handle).unwrap();
+    assert!((body.position().y - 0.5).abs() < 0.02, "바닥 위에 놓여야 한다: {}", body.position());
+    assert!(body.linear_velocity.length() < 0.05, "멈춰야 한다: {}", body.linear_velocity);
+    assert!(body.position().x.abs() < 0.01 && body.position().z.abs() < 0.01);
+}
+
+#[test]
+fn box_placed_exactly_on_ground_stays_put() {
+    // 처음부터 접하기만 한 상태에서도 접촉 normal이 위를 향해야 바닥을 뚫지 않는다.
+    let mut world = PhysicsWorld::default();
+    ground(&mut world);
+    let cube = BoundingBox::new(glam::Vec3::new(0.0, 0.5, 0.0), glam::Vec3::splat(0.5));
+    let handle = dynamic(&mut world, cube);
+
+    for _ in 0..120 {
+        world.step(DT);
+    }
+
+    let body = world.body(handle).unwrap();
+    assert!((body.position().y - 0.5).abs() < 0.02, "바닥 위에 놓여야 한다: {}", body.position());
+}
+
+#[test]
+fn restitution_sets_bounce_height() {
+    let drop_height = 2.0;
+    let restitution = 0.5;
+    let radius = 0.5;
+
+    let mut world = PhysicsWorld::default();
+    ground(&mut world);
+    let sphere = Sphere {
+        center: glam::Vec3::new(0.0, radius + drop_height, 0.0),
+        radius,
+    };
+    let shape = ColliderShape::from(sphere);
+    let handle = world.insert(shape, RigidBody::new(BodyType::Dynamic, &shape, 1.0).with_restitution(restitution));
+
+    // 첫 번째로 튀어 오른 뒤의 최고 높이
+    let mut bounced = false;
+    let mut peak = 0.0_f32;
+    for _ in 0..240 {
+        world.step(DT);
+        let body = world.body(handle).unwrap();
+        if !bounced {
+            bounced = body.linear_velocity.y > 0.0;
+        } else if body.linear_velocity.y > 0.0 {
+            peak = peak.max(body.position().y - radius);
+        } else {
+            break;
+        }
+    }
+
+    // 떨어진 높이 h에서 튀어 오르는 높이는 e^2 * h
+    let expected = restitution * restitution * drop_height;
+    assert!(bounced, "튀어 올라야 한다");
+    assert!((peak - expected).abs() < 0.15 * expected, "튀어 오른 높이 {peak}, 예상 {expected}");
+}
+
+#[test]
+fn friction_stops_box_on_slope() {
+    // 경사각의 tan보다 마찰 계수가 크면 미끄러지다가 멈춘다.
+    let angle = 20.0_f32.to_radians();
+    let rotation = glam::Mat3::from_rotation_z(angle);
+    let normal = rotation * glam::Vec3::Y;
+
+    let mut world = PhysicsWorld::default();
+    let slope = BoundingBox::new_rotated(-normal * 0.5, glam::Vec3::new(10.0, 0.5, 10.0), rotation);
+    let shape = ColliderShape::from(slope);
+    world.insert(shape, RigidBody::new(BodyType::Static, &shape, 0.0).with_friction(1.0));
+
+    let mut cube = BoundingBox::new_rotated(normal * 0.5, glam::Vec3::splat(0.5), rotation);
+    cube.center += normal * 0.01;
+    let shape = ColliderShape::from(cube);
+    let body = RigidBody::new(BodyType::Dynamic, &shape, 1.0)
+        .with_friction(1.0)
+        .with_linear_velocity(glam::Vec3A::from(rotation * glam::Vec3::NEG_X));
+    let handle = world.insert(shape, body);
+
+    for _ in 0..300 {
+        world.step(DT);
+    }
+
+    let body = world.body(handle).unwrap();
+    let position = glam::Vec3::from(body.position());
+    assert!(body.linear_velocity.length() < 0.05, "멈춰야 한다: {}", body.linear_velocity);
+    assert!(position.dot(normal) > 0.4, "경사면 위에 있어야 한다: {position}");
+    assert!(position.length() < 2.0, "멀리 미끄러지면 안 된다: {position}");
+}
+
+#[test]
+fn box_on_frictionless_slope_keeps_sliding() {
+    let angle = 20.0_f32.to_radians();
+    let rotation = glam::Mat3::from_rotation_z(angle);
+    let normal = rotation * glam::Vec3::Y;
+
+    let mut world = PhysicsWorld::default();
+    let slope = BoundingBox::new_rotated(-normal * 0.5, glam::Vec3::new(10.0, 0.5, 10.0), rotation);
+    let shape = ColliderShape::from(slope);
+    world.insert(shape, RigidBody::new(BodyType::Static, &shape, 0.0).with_friction(0.0));
+
+    let mut cube = BoundingBox::new_rotated(normal * 0.5, glam::Vec3::splat(0.5), rotation);
+    cube.center += normal * 0.01;
+    let shape = ColliderShape::from(cube);
+    let handle = world.insert(shape, RigidBody::new(BodyType::Dynamic, &shape, 1.0).with_friction(0.0));
+
+    for _ in 0..60 {
+        world.step(DT);
+    }
+
+    // 경사면을 따라 아래(-x 쪽)로 내려가야 한다.
+    let body = world.body(handle).unwrap();
+    let downhill = rotation * glam::Vec3::NEG_X;
+    assert!(glam::Vec3::from(body.linear_velocity).dot(downhill) > 1.0, "미끄러져야 한다: {}", body.linear_velocity);
+}
+
+fn stack(world: &mut PhysicsWorld, count: usize) -> Vec<ColliderHandle> {
+    (0..count)
+        .map(|i| dynamic(world, BoundingBox::new(glam::Vec3::new(0.0, 0.5 + i as f32 * 1.01, 0.0), glam::Vec3::splat(0.5))))
+        .collect()
+}
+
+fn island_of(world: &PhysicsWorld, handle: ColliderHandle) -> &Island {
+    world.islands().iter().find(|island| island.bodies.contains(&handle)).unwrap()
+}
+
+#[test]
+fn resting_stack_falls_asleep_together() {
+    let mut world = PhysicsWorld::default();
+    ground(&mut world);
+    let boxes = stack(&mut world, 3);
+
+    let mut slept_at = None;
+    for i in 0..600 {
+        world.step(DT);
+        let sleeping = boxes.iter().filter(|&&handle| world.body(handle).unwrap().is_sleeping()).count();
+        // 섬 단위로 잠들므로 일부만 잠든 상태는 없다.
+        assert!(sleeping == 0 || sleeping == boxes.len(), "step {i}: {sleeping}개만 잠듦");
+        if sleeping == boxes.len() {
+            slept_at = Some(i);
+            break;
+        }
+    }
+    assert!(slept_at.is_some(), "쌓인 상자가 잠들어야 한다");
+
+    let island = island_of(&world, boxes[0]);
+    assert!(island.sleeping);
+    assert!(boxes.iter().all(|handle| island.bodies.contains(handle)), "{island:?}");
+
+    // 잠든 뒤에는 움직이지 않는다.
+    let positions = boxes.iter().map(|&handle| world.body(handle).unwrap().position()).collect::<Vec<_>>();
+    for _ in 0..30 {
+        world.step(DT);
+    }
+    for (&handle, position) in boxes.iter().zip(positions) {
+        assert_eq!(world.body(handle).unwrap().position(), position);
+    }
+}
+
+#[test]
+fn touching_one_body_wakes_its_island() {
+    let mut world = PhysicsWorld::default();
+    ground(&mut world);
+    let boxes = stack(&mut world, 3);
+    // 멀리 떨어져 따로 잠드는 상자
+    let lonely = dynamic(&mut world, BoundingBox::new(glam::Vec3::new(5.0, 0.5, 0.0), glam::Vec3::splat(0.5)));
+
+    for _ in 0..600 {
+        world.step(DT);
+    }
+    assert!(boxes.iter().chain([&lonely]).all(|&handle| world.body(handle).unwrap().is_sleeping()));
+
+    // 맨 위의 상자만 밀어도 같은 섬의 상자가 모두 깨어난다.
+    world.body_mut(boxes[2]).unwrap().linear_velocity = glam::Vec3A::new(0.5, 0.0, 0.0);
+    world.step(DT);
+    for &handle in &boxes {
+        assert!(!world.body(handle).unwrap().is_sleeping(), "{handle:?}가 깨어나야 한다");
+    }
+    assert!(!island_of(&world, boxes[0]).sleeping);
+    assert!(world.body(lonely).unwrap().is_sleeping(), "다른 섬은 계속 잠들어 있어야 한다");
+}
+
+#[test]
+fn moved_body_is_applied_to_its_collider() {
+    let mut world = PhysicsWorld::default();
+    ground(&mut world);
+    let handle = dynamic(&mut world, BoundingBox::new(glam::Vec3::new(0.0, 0.5, 0.0), glam::Vec3::splat(0.5)));
+    world.step(DT);
+
+    // step 밖에서 옮긴 위치는 다음 step의 충돌 검사 전에 충돌체에 반영된다.
+    world.body_mut(handle).unwrap().set_position(glam::Vec3A::new(3.0, 5.0, 0.0));
+    world.step(DT);
+    let body_position = world.body(handle).unwrap().position();
+    let center = glam::Vec3A::from(world.collider(handle).unwrap().shape.center());
+    assert!((body_position.x - 3.0).abs() < 1.0e-6 && body_position.y > 4.9, "{body_position}");
+    assert_eq!(center, body_position);
+    assert_eq!(world.manifolds().len(), 0, "옮긴 뒤에는 바닥과 닿지 않는다");
+}
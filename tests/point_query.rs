@@ -0,0 +1,116 @@
+use collision_test::{point_query::PointQuery, *};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+fn rotated_box() -> BoundingBox {
+    let rotation = glam::Mat3::from_euler(glam::EulerRot::XYZ, 0.4, 0.9, -0.3);
+    BoundingBox::new_rotated(glam::Vec3::new(1.0, -0.5, 2.0), glam::Vec3::new(1.0, 0.5, 1.5), rotation)
+}
+
+fn random_point(rng: &mut StdRng, range: f32) -> glam::Vec3A {
+    glam::Vec3A::new(rng.random_range(-range..range), rng.random_range(-range..range), rng.random_range(-range..range))
+}
+
+/// 투영 결과가 expected와 같은지 확인한다.
+fn assert_projection(shape: &impl PointQuery, point: glam::Vec3A, expected: glam::Vec3A, is_inside: bool) {
+    let projection = shape.project_point(&point);
+    assert_eq!(projection.is_inside, is_inside, "{point}: {projection:?}");
+    assert!(projection.point.abs_diff_eq(expected, 1.0e-3), "{point}: {} != {expected}", projection.point);
+    assert_eq!(shape.contains_point(&point), is_inside, "{point}");
+    let distance = if is_inside { 0.0 } else { (expected - point).length() };
+    assert!((shape.distance_to_point(&point) - distance).abs() < 1.0e-3, "{point}: {} != {distance}", shape.distance_to_point(&point));
+}
+
+#[test]
+fn box_inside_outside_and_on_surface() {
+    let cube = BoundingBox::new(glam::Vec3::ZERO, glam::Vec3::new(1.0, 2.0, 3.0));
+    // 밖: 면, 모서리, 꼭짓점 방향
+    assert_projection(&cube, glam::Vec3A::new(3.0, 0.5, 0.5), glam::Vec3A::new(1.0, 0.5, 0.5), false);
+    assert_projection(&cube, glam::Vec3A::new(2.0, 3.0, 0.0), glam::Vec3A::new(1.0, 2.0, 0.0), false);
+    assert_projection(&cube, glam::Vec3A::new(-2.0, -3.0, -4.0), glam::Vec3A::new(-1.0, -2.0, -3.0), false);
+    // 안: 가장 가까운 면으로 밀어낸다.
+    assert_projection(&cube, glam::Vec3A::new(0.2, 1.5, 0.0), glam::Vec3A::new(0.2, 2.0, 0.0), true);
+    assert_projection(&cube, glam::Vec3A::new(0.0, 0.0, -2.9), glam::Vec3A::new(0.0, 0.0, -3.0), true);
+    // 표면 위: 안으로 보고 그 점 그대로
+    assert_projection(&cube, glam::Vec3A::new(1.0, 0.3, -0.4), glam::Vec3A::new(1.0, 0.3, -0.4), true);
+}
+
+#[test]
+fn sphere_and_capsule_inside_outside_and_on_surface() {
+    let sphere = Sphere {
+        center: glam::Vec3::new(1.0, 0.0, 0.0),
+        radius: 2.0,
+    };
+    assert_projection(&sphere, glam::Vec3A::new(5.0, 0.0, 0.0), glam::Vec3A::new(3.0, 0.0, 0.0), false);
+    assert_projection(&sphere, glam::Vec3A::new(1.0, 0.5, 0.0), glam::Vec3A::new(1.0, 2.0, 0.0), true);
+    assert_projection(&sphere, glam::Vec3A::new(1.0, 0.0, -2.0), glam::Vec3A::new(1.0, 0.0, -2.0), true);
+
+    // 선분 (0, -1, 0) ~ (0, 1, 0), 반지름 0.5
+    let capsule = Capsule::upright(glam::Vec3::ZERO, 3.0, 0.5);
+    // 옆면과 반구
+    assert_projection(&capsule, glam::Vec3A::new(2.0, 0.5, 0.0), glam::Vec3A::new(0.5, 0.5, 0.0), false);
+    assert_projection(&capsule, glam::Vec3A::new(0.0, 4.0, 0.0), glam::Vec3A::new(0.0, 1.5, 0.0), false);
+    assert_projection(&capsule, glam::Vec3A::new(0.0, -0.3, 0.2), glam::Vec3A::new(0.0, -0.3, 0.5), true);
+    assert_projection(&capsule, glam::Vec3A::new(0.0, -1.5, 0.0), glam::Vec3A::new(0.0, -1.5, 0.0), true);
+}
+
+#[test]
+fn gjk_projection_matches_analytic_box() {
+    // VertexBox와 Rounded는 GJK/EPA 기본 구현을 쓰므로, 해석적으로 구한 BoundingBox와 비교한다.
+    let cube = rotated_box();
+    let vertex_box = VertexBox::from(&cube);
+    let prepared = PreparedBox::from(&cube);
+    let rounded = cube.rounded(0.0);
+    let mut rng = StdRng::seed_from_u64(27);
+    let mut inside = 0;
+    for _ in 0..500 {
+        let point = glam::Vec3A::from(cube.center) + random_point(&mut rng, 2.0);
+        let expected = cube.project_point(&point);
+        for projection in [vertex_box.project_point(&point), prepared.project_point(&point), rounded.project_point(&point)] {
+            assert_eq!(projection.is_inside, expected.is_inside, "{point}");
+            assert!(projection.point.abs_diff_eq(expected.point, 1.0e-3), "{point}: {} != {}", projection.point, expected.point);
+        }
+        let distance = PointQuery::distance_to_point(&cube, &point);
+        assert!((vertex_box.distance_to_point(&point) - distance).abs() < 1.0e-3, "{point}");
+        assert!((PointQuery::distance_to_point(&prepared, &point) - distance).abs() < 1.0e-6, "{point}");
+        inside += expected.is_inside as usize;
+    }
+    assert!(inside > 20, "{inside}");
+
+    // 둥근 모서리는 모서리 바깥에서 반지름만큼 떨어진 곡면 위로 투영된다.
+    let cube = BoundingBox::new(glam::Vec3::ZERO, glam::Vec3::ONE);
+    let rounded = cube.rounded(0.5);
+    let corner = glam::Vec3A::splat(0.5);
+    let point = glam::Vec3A::splat(2.0);
+    let expected = corner + (point - corner).normalize() * 0.5;
+    // 곡면에서는 GJK가 천천히 수렴하므로, 거리보다 점의 오차가 크다.
+    let projection = rounded.project_point(&point);
+    assert!(!projection.is_inside);
+    assert!(projection.point.abs_diff_eq(expected, 1.0e-2), "{} != {expected}", projection.point);
+    assert!((rounded.distance_to_point(&point) - (point - expected).length()).abs() < 1.0e-3);
+}
+
+#[test]
+fn projection_uses_gjk_config() {
+    let cube = VertexBox::from(&BoundingBox::new(glam::Vec3::ZERO, glam::Vec3::ONE));
+    let point = glam::Vec3A::new(3.0, 0.2, 0.1);
+    let exact = cube.project_point_with_config(&point, &GjkConfig::default());
+    assert!(exact.point.abs_diff_eq(glam::Vec3A::new(1.0, 0.2, 0.1), 1.0e-4), "{exact:?}");
+    assert_eq!(exact.point, cube.project_point(&point).point);
+
+    // 반복을 허락하지 않으면 처음 support point(꼭짓점)가 추정값으로 나온다.
+    let config = GjkConfig {
+        max_iterations: 0,
+        ..GjkConfig::default()
+    };
+    let estimate = cube.project_point_with_config(&point, &config);
+    assert!(!estimate.is_inside);
+    assert!(estimate.point.abs().abs_diff_eq(glam::Vec3A::ONE, 1.0e-6), "{estimate:?}");
+}
+
+#[test]
+fn triangle_points_on_and_off_the_surface() {
+    let triangle = Triangle::new(glam::Vec3A::ZERO, glam::Vec3A::X * 2.0, glam::Vec3A::Z * 2.0);
+    assert_projection(&triangle, glam::Vec3A::new(0.5, 1.0, 0.5), glam::Vec3A::new(0.5, 0.0, 0.5), false);
+    assert_projection(&triangle, glam::Vec3A::new(0.5, 0.0, 0.5), glam::Vec3A::new(0.5, 0.0, 0.5), true);
+    assert_projection(&triangle, glam::Vec3A::new(3.0, 0.0, 3.0), glam::Vec3A::new(1.0, 0.0, 1.0), false);
+}
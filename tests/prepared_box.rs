@@ -0,0 +1,60 @@
+use collision_test::{static_collision::StaticCollision, BoundingBox, Capsule, ConvexHull, PreparedBox, Sphere};
+
+fn boxes() -> Vec<BoundingBox> {
+    let mut boxes = Vec::new();
+    for i in 0..200 {
+        let t = i as f32;
+        let center = glam::Vec3::new((t * 0.37).sin() * 3.0, (t * 0.53).cos() * 3.0, (t * 0.71).sin() * 3.0);
+        let extents = glam::Vec3::new(0.5 + (t * 0.13).sin().abs(), 0.5 + (t * 0.29).cos().abs(), 0.7);
+        let rotation = glam::Mat3::from_euler(glam::EulerRot::XYZ, t * 0.3, t * 0.7, t * 1.1);
+        boxes.push(BoundingBox::new_rotated(center, extents, rotation));
+    }
+    boxes.push(BoundingBox::new(glam::Vec3::new(1.5, 0.0, 0.0), glam::Vec3::ONE));
+    boxes
+}
+
+#[test]
+fn prepared_box_agrees_with_bounding_box() {
+    let main = BoundingBox::new_rotated(glam::Vec3::ZERO, glam::Vec3::new(1.0, 0.8, 1.2), glam::Mat3::from_rotation_y(0.7));
+    let prepared = PreparedBox::from(&main);
+    for other in boxes() {
+        let prepared_other = PreparedBox::from(&other);
+        assert_eq!(prepared.check_static_collision(&other), main.check_static_collision(&other), "{other:?}");
+        assert_eq!(prepared.check_static_collision(&prepared_other), main.check_static_collision(&other), "{other:?}");
+        assert_eq!(other.check_static_collision(&prepared), other.check_static_collision(&main), "{other:?}");
+
+        let expected = main.check_static_collision_details(&other);
+        let details = prepared.check_static_collision_details(&other);
+        assert_eq!(details.is_some(), expected.is_some(), "{other:?}");
+        if let (Some(details), Some(expected)) = (details, expected) {
+            assert_eq!(details.penetration, expected.penetration, "{other:?}");
+            assert_eq!(details.normal, expected.normal, "{other:?}");
+        }
+
+        let gjk = prepared.gjk_epa(&other).unwrap().map(|details| details.penetration);
+        let expected = main.gjk_epa(&other).unwrap().map(|details| details.penetration);
+        assert_eq!(gjk, expected, "{other:?}");
+    }
+}
+
+#[test]
+fn prepared_box_follows_transform_changes() {
+    let mut prepared = PreparedBox::from(&BoundingBox::new(glam::Vec3::ZERO, glam::Vec3::ONE));
+    let sphere = Sphere {
+        center: glam::Vec3::new(3.0, 0.0, 0.0),
+        radius: 0.5,
+    };
+    let capsule = Capsule::upright(glam::Vec3::new(0.0, 0.0, 3.0), 2.0, 0.5);
+    assert!(!prepared.check_static_collision(&sphere));
+    assert!(!capsule.check_static_collision(&prepared));
+
+    prepared.set_transform(glam::Vec3::new(2.0, 0.0, 0.0), glam::Mat3::from_rotation_y(0.3));
+    assert!(prepared.check_static_collision(&sphere));
+    assert!(!capsule.check_static_collision(&prepared));
+
+    prepared.set_transform(glam::Vec3::new(0.0, 0.0, 2.0), glam::Mat3::from_rotation_y(0.3));
+    assert!(!prepared.check_static_collision(&sphere));
+    assert!(capsule.check_static_collision(&prepared));
+    assert_eq!(prepared.aabb().max(), prepared.bounding_box().aabb().max());
+    assert_eq!(*prepared.get_vertices(), prepared.bounding_box().get_vertices());
+}
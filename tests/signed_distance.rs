@@ -0,0 +1,138 @@
+use collision_test::{signed_distance::SignedDistance, *};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+fn rotated_box() -> BoundingBox {
+    let rotation = glam::Mat3::from_euler(glam::EulerRot::XYZ, 0.4, 0.9, -0.3);
+    BoundingBox::new_rotated(glam::Vec3::new(1.0, -0.5, 2.0), glam::Vec3::new(1.0, 0.5, 1.5), rotation)
+}
+
+fn random_point(rng: &mut StdRng, range: f32) -> glam::Vec3A {
+    glam::Vec3A::new(rng.random_range(-range..range), rng.random_range(-range..range), rng.random_range(-range..range))
+}
+
+/// 밖은 양수, 안은 음수, 표면 위는 0인지 확인하고, 기울기 방향으로 조금 움직이면 그만큼 거리가 늘어나는지 확인한다.
+fn check_sign_convention(shape: &impl SignedDistance, center: glam::Vec3A, tolerance: f32, rng: &mut StdRng) {
+    let (mut inside, mut outside) = (0, 0);
+    for _ in 0..500 {
+        let point = center + random_point(rng, 2.0);
+        let (distance, gradient) = shape.signed_distance(&point);
+        assert!((gradient.length() - 1.0).abs() < 1.0e-4, "{point}: {gradient}");
+
+        let projection = shape.project_point(&point);
+        let to_surface = (projection.point - point).length();
+        if projection.is_inside {
+            assert!(distance <= 0.0, "{point}: {distance}");
+            assert!((distance + to_surface).abs() < tolerance, "{point}: {distance} != -{to_surface}");
+            inside += 1;
+        } else {
+            assert!(distance > 0.0, "{point}: {distance}");
+            assert!((distance - to_surface).abs() < tolerance, "{point}: {distance} != {to_surface}");
+            assert!((distance - shape.distance_to_point(&point)).abs() < tolerance, "{point}");
+            outside += 1;
+        }
+
+        // 표면 위로 투영한 점에서는 0이다.
+        let (on_surface, _) = shape.signed_distance(&projection.point);
+        assert!(on_surface.abs() < tolerance, "{}: {on_surface}", projection.point);
+
+        let step = 0.01;
+        let (moved, _) = shape.signed_distance(&(point + gradient * step));
+        assert!((moved - distance - step).abs() < tolerance, "{point}: {moved} != {distance} + {step}");
+    }
+    assert!(inside > 20 && outside > 20, "안 {inside}, 밖 {outside}");
+}
+
+#[test]
+fn sign_convention_for_every_shape() {
+    let mut rng = StdRng::seed_from_u64(28);
+    let cube = rotated_box();
+    check_sign_convention(&cube, cube.center.into(), 1.0e-4, &mut rng);
+    check_sign_convention(&BoundingBox::new(glam::Vec3::ONE, glam::Vec3::new(2.0, 1.0, 0.5)), glam::Vec3A::ONE, 1.0e-4, &mut rng);
+
+    let sphere = Sphere {
+        center: glam::Vec3::new(1.0, 0.0, -1.0),
+        radius: 1.5,
+    };
+    check_sign_convention(&sphere, sphere.center.into(), 1.0e-4, &mut rng);
+
+    let capsule = Capsule::new(glam::Vec3::new(0.0, 1.0, 0.0), glam::Vec3::new(1.0, 0.5, -0.5), 0.8);
+    check_sign_convention(&capsule, capsule.center.into(), 1.0e-4, &mut rng);
+
+    // VertexBox는 GJK/EPA 기본 구현을 쓰므로 오차를 넉넉히 둔다.
+    check_sign_convention(&VertexBox::from(&cube), cube.center.into(), 1.0e-3, &mut rng);
+}
+
+#[test]
+fn capsule_signed_distance_on_the_segment() {
+    let capsule = Capsule::upright(glam::Vec3::ZERO, 3.0, 0.5);
+    // 선분 위에서는 반지름만큼 안쪽이고, 기울기는 선분에 수직이다.
+    for y in [-1.0, 0.0, 0.3, 1.0] {
+        let (distance, gradient) = capsule.signed_distance(&glam::Vec3A::new(0.0, y, 0.0));
+        assert_eq!(distance, -0.5);
+        assert!(gradient.y.abs() < 1.0e-6 && (gradient.length() - 1.0).abs() < 1.0e-6, "{gradient}");
+    }
+    // 반구 쪽
+    let (distance, gradient) = capsule.signed_distance(&glam::Vec3A::new(0.0, 3.0, 0.0));
+    assert!((distance - 1.5).abs() < 1.0e-6);
+    assert!(gradient.abs_diff_eq(glam::Vec3A::Y, 1.0e-6));
+    // 선분의 길이가 0인 캡슐은 구와 같다.
+    let ball = Capsule::upright(glam::Vec3::ZERO, 1.0, 0.5);
+    assert_eq!(ball.signed_distance(&glam::Vec3A::ZERO), (-0.5, glam::Vec3A::X));
+}
+
+#[test]
+fn sdf_penetration_matches_obb_for_cube_particles() {
+    // 같은 방향으로 놓인 정육면체 입자가 면에 걸쳐 있으면 (한 축으로만 벗어나고, 면에서 radius보다 깊이 들어가지 않은 경우)
+    // SAT의 최소 겹침은 radius에서 SDF를 뺀 값과 같다.
+    let mut rng = StdRng::seed_from_u64(29);
+    let cube = rotated_box();
+    let rotation = cube.rotation().unwrap();
+    let extents = glam::Vec3A::from(cube.extents());
+    let mut hits = 0;
+    for _ in 0..500 {
+        let mut local = random_point(&mut rng, 1.0) * extents;
+        let radius = rng.random_range(0.1..0.5);
+        let axis = rng.random_range(0..3);
+        let sign = if rng.random_bool(0.5) { 1.0 } else { -1.0 };
+        local[axis] = sign * (extents[axis] + rng.random_range(-radius..radius + 0.3));
+        let point = glam::Vec3A::from(cube.center) + glam::Vec3A::from(rotation * glam::Vec3::from(local));
+
+        let particle = BoundingBox::new_rotated(point.into(), glam::Vec3::splat(radius), rotation);
+        let sdf = cube.sdf_penetration(&point, radius);
+        let obb = cube.obb_collision_details(&particle);
+        assert_eq!(sdf.is_some(), obb.is_some(), "{point}, {radius}: {sdf:?} != {obb:?}");
+        if let (Some(sdf), Some(obb)) = (sdf, obb) {
+            // SAT의 penetration은 부호가 있으므로 normal * penetration(밀어내는 벡터)끼리 비교한다.
+            assert!((sdf.penetration - obb.penetration.abs()).abs() < 1.0e-4, "{point}: {sdf:?} != {obb:?}");
+            let push = sdf.normal * sdf.penetration;
+            assert!(push.abs_diff_eq(obb.normal * obb.penetration, 1.0e-4), "{point}: {sdf:?} != {obb:?}");
+            hits += 1;
+        }
+    }
+    assert!(hits > 100, "{hits}");
+}
+
+#[test]
+fn sdf_penetration_matches_sphere_details() {
+    // 구 입자와는 모서리, 꼭짓점 방향을 포함해 check_static_collision_details와 같다.
+    let mut rng = StdRng::seed_from_u64(30);
+    let cube = rotated_box();
+    let mut hits = 0;
+    for _ in 0..500 {
+        let point = glam::Vec3A::from(cube.center) + random_point(&mut rng, 2.0);
+        let radius = rng.random_range(0.1..1.0);
+        let particle = Sphere {
+            center: point.into(),
+            radius,
+        };
+        let sdf = cube.sdf_penetration(&point, radius);
+        let details = static_collision::StaticCollision::check_static_collision_details(&cube, &particle);
+        assert_eq!(sdf.is_some(), details.is_some(), "{point}, {radius}");
+        if let (Some(sdf), Some(details)) = (sdf, details) {
+            assert!((sdf.penetration - details.penetration).abs() < 1.0e-4, "{point}: {sdf:?} != {details:?}");
+            assert!(sdf.normal.abs_diff_eq(details.normal, 1.0e-4), "{point}: {sdf:?} != {details:?}");
+            hits += 1;
+        }
+    }
+    assert!(hits > 100, "{hits}");
+}
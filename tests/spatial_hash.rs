@@ -0,0 +1,175 @@
+mod common;
+
+use collision_test::{ray_cast::RayCast, *};
+use common::{all_pairs, random_box};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+/// 모든 객체에 ray를 쏘아 가장 가까운 것을 구한다.
+fn brute_force_ray_cast(boxes: &[BoundingBox], ray: &Ray, max_distance: f32) -> Option<(usize, f32)> {
+    boxes.iter()
+        .enumerate()
+        .filter_map(|(i, bounding_box)| bounding_box.cast_ray(ray, max_distance).map(|hit| (i, hit.distance)))
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+}
+
+#[test]
+fn axis_aligned_rays_find_closest_object() {
+    // 셀 경계에 딱 맞는 박스들 (셀 크기 1)
+    let boxes = [
+        BoundingBox::new(glam::Vec3::new(5.5, 0.5, 0.5), glam::Vec3::splat(0.5)),
+        BoundingBox::new(glam::Vec3::new(-3.5, 0.5, 0.5), glam::Vec3::splat(0.5)),
+        BoundingBox::new(glam::Vec3::new(0.5, 4.5, 0.5), glam::Vec3::splat(0.5)),
+        BoundingBox::new(glam::Vec3::new(0.5, 0.5, -6.5), glam::Vec3::splat(0.5)),
+        BoundingBox::new(glam::Vec3::new(2.0, 2.0, 2.0), glam::Vec3::new(1.0, 2.0, 0.5)),
+    ];
+    let mut grid = SpatialHashGrid::new(1.0);
+    let mut broadphase = SpatialHashGrid::new(1.0);
+    for (i, bounding_box) in boxes.iter().enumerate() {
+        grid.insert(*bounding_box);
+        broadphase.insert_proxy(bounding_box, i);
+    }
+
+    let directions = [glam::Vec3A::X, glam::Vec3A::NEG_X, glam::Vec3A::Y, glam::Vec3A::NEG_Y, glam::Vec3A::Z, glam::Vec3A::NEG_Z];
+    // 셀 경계(0.0, 1.0) 위와 셀 안쪽(0.5)에서 출발한다.
+    let offsets = [0.0, 0.5, 1.0, 2.5, 4.0];
+    let mut hits = 0;
+    for direction in directions {
+        for &u in &offsets {
+            for &v in &offsets {
+                // direction과 수직인 두 축의 좌표가 u, v이고, direction 반대쪽 멀리서 출발한다.
+                let axis = direction.abs();
+                let (side_u, side_v) = if axis.x > 0.0 {
+                    (glam::Vec3A::Y, glam::Vec3A::Z)
+                } else if axis.y > 0.0 {
+                    (glam::Vec3A::X, glam::Vec3A::Z)
+                } else {
+                    (glam::Vec3A::X, glam::Vec3A::Y)
+                };
+                let origin = -direction * 15.0 + side_u * u + side_v * v;
+                let ray = Ray::new(origin, direction);
+
+                let expected = brute_force_ray_cast(&boxes, &ray, 100.0);
+                let actual = grid.ray_cast(&ray, 100.0);
+                match (expected, actual) {
+                    (None, None) => {}
+                    (Some((_, distance)), Some((_, hit))) => {
+                        assert!((hit.distance - distance).abs() < 1.0e-4, "{ray:?}: {} != {distance}", hit.distance);
+                        hits += 1;
+                    }
+                    _ => panic!("{ray:?}: 예상 {expected:?}, 결과 {actual:?}"),
+                }
+
+                let candidates = broadphase.ray_candidates(&ray, 100.0);
+                if let Some((index, _)) = expected {
+                    assert!(candidates.contains(&index), "{ray:?}: {candidates:?}에 {index}가 없다");
+                }
+            }
+        }
+    }
+    assert!(hits > 10, "{hits}");
+}
+
+#[test]
+fn random_rays_match_brute_force() {
+    let mut rng = StdRng::seed_from_u64(31);
+    let boxes = (0..200).map(|_| random_box(&mut rng)).collect::<Vec<_>>();
+    let mut grid = SpatialHashGrid::new(2.0);
+    for bounding_box in &boxes {
+        grid.insert(*bounding_box);
+    }
+
+    for _ in 0..500 {
+        let origin = glam::Vec3A::new(rng.random_range(-30.0..30.0), rng.random_range(-30.0..30.0), rng.random_range(-30.0..30.0));
+        let mut direction = glam::Vec3A::new(rng.random_range(-1.0..1.0), rng.random_range(-1.0..1.0), rng.random_range(-1.0..1.0));
+        // 절반은 한 축 이상이 0인 ray
+        if rng.random_bool(0.5) {
+            direction[rng.random_range(0..3)] = 0.0;
+        }
+        let ray = Ray::new(origin, direction);
+        let max_distance = rng.random_range(1.0..60.0);
+
+        let expected = brute_force_ray_cast(&boxes, &ray, max_distance).map(|(_, distance)| distance);
+        let actual = grid.ray_cast(&ray, max_distance).map(|(_, hit)| hit.distance);
+        match (expected, actual) {
+            (None, None) => {}
+            (Some(expected), Some(actual)) => assert!((expected - actual).abs() < 1.0e-4, "{ray:?}: {actual} != {expected}"),
+            _ => panic!("{ray:?}: 예상 {expected:?}, 결과 {actual:?}"),
+        }
+    }
+}
+
+#[test]
+fn overlap_queries_match_brute_force() {
+    let mut rng = StdRng::seed_from_u64(7);
+    let mut boxes = (0..300).map(|_| random_box(&mut rng)).collect::<Vec<_>>();
+    let mut grid = SpatialHashGrid::new(2.5);
+    let ids = boxes.iter().map(|bounding_box| grid.insert(*bounding_box)).collect::<Vec<_>>();
+    let index_of = |id: ProxyId| ids.iter().position(|&other| other == id).unwrap();
+
+    let check = |grid: &SpatialHashGrid<BoundingBox>, boxes: &[BoundingBox], alive: &[bool], rng: &mut StdRng| {
+        // 영역 질의
+        for _ in 0..50 {
+            let query = random_box(rng);
+            let mut actual = grid.query_aabb(&query).into_iter().map(index_of).collect::<Vec<_>>();
+            actual.sort();
+            let expected = (0..boxes.len())
+                .filter(|&i| alive[i] && boxes[i].aabb_collision(&query))
+                .collect::<Vec<_>>();
+            assert_eq!(actual, expected);
+        }
+
+        // 모든 쌍
+        let mut actual = grid.query_pairs().into_iter()
+            .map(|(a, b)| {
+                let (a, b) = (index_of(a), index_of(b));
+                (a.min(b), a.max(b))
+            })
+            .collect::<Vec<_>>();
+        actual.sort();
+        let before_dedup = actual.len();
+        actual.dedup();
+        assert_eq!(actual.len(), before_dedup, "중복된 쌍이 있다");
+        let live = boxes.iter().zip(alive).map(|(bounding_box, &alive)| alive.then_some(*bounding_box)).collect::<Vec<_>>();
+        assert_eq!(actual, all_pairs(&live));
+    };
+
+    let mut alive = vec![true; boxes.len()];
+    check(&grid, &boxes, &alive, &mut rng);
+
+    // 옮기고 지운 뒤에도 같아야 한다.
+    for i in 0..boxes.len() {
+        if i % 3 == 0 {
+            boxes[i] = random_box(&mut rng);
+            grid.update(ids[i], boxes[i]);
+        } else if i % 7 == 0 {
+            grid.remove(ids[i]);
+            alive[i] = false;
+        }
+    }
+    check(&grid, &boxes, &alive, &mut rng);
+}
+
+#[test]
+fn huge_query_box_only_visits_occupied_cells() {
+    // 셀 크기 1로 질의 영역의 셀을 모두 훑으면 끝나지 않는 크기
+    let huge = BoundingBox::new(glam::Vec3::ZERO, glam::Vec3::splat(1.0e9));
+    let mut grid = SpatialHashGrid::new(1.0);
+    assert!(grid.query_aabb(&huge).is_empty());
+
+    let mut rng = StdRng::seed_from_u64(11);
+    let boxes = (0..100).map(|_| random_box(&mut rng)).collect::<Vec<_>>();
+    let ids = boxes.iter().map(|bounding_box| grid.insert(*bounding_box)).collect::<Vec<_>>();
+
+    let mut actual = grid.query_aabb(&huge);
+    actual.sort_by_key(|id| ids.iter().position(|other| other == id));
+    assert_eq!(actual, ids);
+
+    // 물체가 있는 범위 밖의 큰 질의
+    let far = BoundingBox::from_min_max(glam::Vec3::splat(1.0e6), glam::Vec3::splat(1.0e9));
+    assert!(grid.query_aabb(&far).is_empty());
+
+    for id in ids {
+        grid.remove(id);
+    }
+    assert!(grid.query_aabb(&huge).is_empty());
+}
@@ -0,0 +1,237 @@
+use collision_test::{point_query::PointQuery, ray_cast::RayCast, static_collision::StaticCollision, *};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+fn random_point(rng: &mut StdRng, range: f32) -> glam::Vec3A {
+    glam::Vec3A::new(rng.random_range(-range..range), rng.random_range(-range..range), rng.random_range(-range..range))
+}
+
+fn random_box(rng: &mut StdRng) -> BoundingBox {
+    let extents = glam::Vec3::new(rng.random_range(0.1..2.0), rng.random_range(0.1..2.0), rng.random_range(0.1..2.0));
+    BoundingBox::new(random_point(rng, 20.0).into(), extents)
+}
+
+fn random_ray(rng: &mut StdRng) -> Ray {
+    let mut direction = random_point(rng, 1.0);
+    // 절반은 한 축 이상이 0인 ray
+    if rng.random_bool(0.5) {
+        direction[rng.random_range(0..3)] = 0.0;
+    }
+    Ray::new(random_point(rng, 30.0), direction)
+}
+
+/// x, z가 -size ~ size인 격자 위에 y = height(x, z)로 놓인 삼각형 메시
+fn height_field(size: i32, height: impl Fn(f32, f32) -> f32) -> Vec<Triangle> {
+    let vertex = |x: i32, z: i32| glam::Vec3A::new(x as f32, height(x as f32, z as f32), z as f32);
+    let mut triangles = Vec::new();
+    for x in -size..size {
+        for z in -size..size {
+            triangles.push(Triangle::new(vertex(x, z), vertex(x + 1, z), vertex(x, z + 1)));
+            triangles.push(Triangle::new(vertex(x + 1, z), vertex(x + 1, z + 1), vertex(x, z + 1)));
+        }
+    }
+    triangles
+}
+
+#[test]
+fn overlap_queries_match_brute_force() {
+    let mut rng = StdRng::seed_from_u64(32);
+    let boxes = (0..500).map(|_| random_box(&mut rng)).collect::<Vec<_>>();
+    let bvh = boxes.iter().copied().collect::<StaticBvh<_>>();
+    assert!(bvh.depth() > 1);
+
+    for _ in 0..200 {
+        let query = random_box(&mut rng);
+        let mut actual = bvh.query_aabb(&query);
+        actual.sort();
+        let expected = (0..boxes.len()).filter(|&i| boxes[i].aabb_collision(&query)).collect::<Vec<_>>();
+        assert_eq!(actual, expected);
+
+        let sphere = Sphere {
+            center: random_point(&mut rng, 20.0).into(),
+            radius: rng.random_range(0.5..4.0),
+        };
+        let mut actual = bvh.query(&sphere);
+        actual.sort();
+        let expected = (0..boxes.len()).filter(|&i| sphere.check_static_collision(&boxes[i])).collect::<Vec<_>>();
+        assert_eq!(actual, expected);
+    }
+}
+
+#[test]
+fn ray_cast_matches_brute_force() {
+    let mut rng = StdRng::seed_from_u64(33);
+    let boxes = (0..500).map(|_| random_box(&mut rng)).collect::<Vec<_>>();
+    let bvh = StaticBvh::new(boxes.clone());
+
+    for _ in 0..1000 {
+        let ray = random_ray(&mut rng);
+        let max_distance = rng.random_range(1.0..60.0);
+        let expected = boxes.iter()
+            .filter_map(|bounding_box| bounding_box.cast_ray(&ray, max_distance))
+            .map(|hit| hit.distance)
+            .min_by(f32::total_cmp);
+        let actual = bvh.ray_cast(&ray, max_distance).map(|(_, hit)| hit.distance);
+        match (expected, actual) {
+            (None, None) => {}
+            (Some(expected), Some(actual)) => assert!((expected - actual).abs() < 1.0e-4, "{ray:?}: {actual} != {expected}"),
+            _ => panic!("{ray:?}: 예상 {expected:?}, 결과 {actual:?}"),
+        }
+    }
+}
+
+#[test]
+fn axis_aligned_ray_from_node_boundary_hits() {
+    // 노드의 경계(y = 0) 위에서 x축으로 쏘는 ray
+    let boxes = (0..32)
+        .map(|i| BoundingBox::new(glam::Vec3::new(i as f32 * 3.0, 0.5, 0.5), glam::Vec3::splat(0.5)))
+        .collect::<Vec<_>>();
+    let bvh = StaticBvh::new(boxes);
+    let ray = Ray::new(glam::Vec3A::new(-10.0, 0.0, 0.0), glam::Vec3A::X);
+    let (index, hit) = bvh.ray_cast(&ray, 100.0).unwrap();
+    assert_eq!(index, 0);
+    assert!((hit.distance - 9.5).abs() < 1.0e-5, "{hit:?}");
+}
+
+#[test]
+fn closest_point_matches_brute_force() {
+    let mut rng = StdRng::seed_from_u64(34);
+    let spheres = (0..300)
+        .map(|_| Sphere {
+            center: random_point(&mut rng, 20.0).into(),
+            radius: rng.random_range(0.1..2.0),
+        })
+        .collect::<Vec<_>>();
+    let bvh = StaticBvh::new(spheres.clone());
+
+    for _ in 0..300 {
+        let point = random_point(&mut rng, 30.0);
+        let expected = spheres.iter().map(|sphere| sphere.distance_to_point(&point)).min_by(f32::total_cmp).unwrap();
+        let (index, closest, distance) = bvh.closest_point(&point).unwrap();
+        assert!((distance - expected).abs() < 1.0e-4, "{point}: {distance} != {expected}");
+        assert!((spheres[index].distance_to_point(&point) - distance).abs() < 1.0e-4);
+        if distance > 0.0 {
+            assert!(((closest - point).length() - distance).abs() < 1.0e-4);
+        }
+    }
+}
+
+#[test]
+fn leaf_size_is_bounded() {
+    // 중심이 모두 같으면 SAH로 나눌 수 없으므로, MAX_LEAF_SIZE(16)까지만 한 잎 노드에 넣는다.
+    let same = |count: usize| StaticBvh::new(vec![BoundingBox::new(glam::Vec3::ZERO, glam::Vec3::ONE); count]);
+    assert_eq!(same(16).node_count(), 1);
+    assert!(same(17).node_count() > 1);
+
+    // LEAF_SIZE(4) 이하는 항상 잎 노드 하나이다.
+    let spread = (0..4).map(|i| BoundingBox::new(glam::Vec3::new(i as f32 * 10.0, 0.0, 0.0), glam::Vec3::ONE)).collect();
+    assert_eq!(StaticBvh::new(spread).node_count(), 1);
+}
+
+#[test]
+fn triangle_mesh_queries() {
+    let height = |x: f32, z: f32| 0.1 * x + 0.05 * z;
+    let triangles = height_field(10, height);
+    let bvh = StaticBvh::new(triangles.clone());
+
+    // 위에서 아래로 쏜 ray는 높이 height(x, z)에서 만난다.
+    let mut rng = StdRng::seed_from_u64(35);
+    for _ in 0..200 {
+        let (x, z) = (rng.random_range(-9.5..9.5), rng.random_range(-9.5..9.5));
+        let ray = Ray::new(glam::Vec3A::new(x, 10.0, z), glam::Vec3A::NEG_Y);
+        let (index, hit) = bvh.ray_cast(&ray, 100.0).unwrap();
+        assert!((hit.point.y - height(x, z)).abs() < 1.0e-4, "{hit:?}");
+        assert!(hit.normal.y > 0.9, "{hit:?}");
+        assert!(triangles[index].distance_to_point(&hit.point) < 1.0e-4, "{hit:?}");
+    }
+    // 메시 밖으로 쏜 ray
+    assert!(bvh.ray_cast(&Ray::new(glam::Vec3A::new(20.0, 10.0, 0.0), glam::Vec3A::NEG_Y), 100.0).is_none());
+
+    // 메시 위의 점까지의 거리
+    let point = glam::Vec3A::new(2.3, height(2.3, -4.1) + 1.0, -4.1);
+    let (_, closest, distance) = bvh.closest_point(&point).unwrap();
+    // 평면 y = 0.1x + 0.05z까지의 거리
+    let normal = glam::Vec3A::new(-0.1, 1.0, -0.05).normalize();
+    assert!((distance - normal.y).abs() < 1.0e-4, "{distance}");
+    assert!((closest.y - height(closest.x, closest.z)).abs() < 1.0e-4, "{closest}");
+
+    // 메시에 걸친 구와 박스, 메시 위로 떨어진 구
+    let sphere = Sphere {
+        center: glam::Vec3::new(0.5, 0.3, 0.5),
+        radius: 0.5,
+    };
+    let touching = bvh.query(&sphere);
+    assert!(!touching.is_empty());
+    for i in touching {
+        let details = sphere.check_static_collision_details(&triangles[i]).unwrap();
+        assert!(details.normal.y > 0.0 && details.penetration >= 0.0, "{details:?}");
+    }
+    let above = Sphere {
+        center: glam::Vec3::new(0.5, 2.0, 0.5),
+        radius: 0.5,
+    };
+    assert!(bvh.query(&above).is_empty());
+    let cube = BoundingBox::new(glam::Vec3::new(-3.5, -0.2, 2.5), glam::Vec3::splat(0.5));
+    assert!(!bvh.query(&cube).is_empty());
+    let cube = BoundingBox::new(glam::Vec3::new(-3.5, 1.5, 2.5), glam::Vec3::splat(0.5));
+    assert!(bvh.query(&cube).is_empty());
+}
+
+#[test]
+fn triangle_closest_point_regions() {
+    let triangle = Triangle::new(glam::Vec3A::ZERO, glam::Vec3A::X, glam::Vec3A::Z);
+    let cases = [
+        // 면
+        (glam::Vec3A::new(0.2, 1.0, 0.2), glam::Vec3A::new(0.2, 0.0, 0.2)),
+        // 정점
+        (glam::Vec3A::new(-1.0, 0.5, -1.0), glam::Vec3A::ZERO),
+        (glam::Vec3A::new(2.0, 0.0, -0.5), glam::Vec3A::X),
+        (glam::Vec3A::new(-0.5, 0.0, 2.0), glam::Vec3A::Z),
+        // 모서리
+        (glam::Vec3A::new(0.5, -1.0, -1.0), glam::Vec3A::new(0.5, 0.0, 0.0)),
+        (glam::Vec3A::new(-1.0, 0.0, 0.5), glam::Vec3A::new(0.0, 0.0, 0.5)),
+        (glam::Vec3A::new(1.0, 0.0, 1.0), glam::Vec3A::new(0.5, 0.0, 0.5)),
+    ];
+    for (point, expected) in cases {
+        let closest = triangle.closest_point(&point);
+        assert!(closest.abs_diff_eq(expected, 1.0e-6), "{point}: {closest} != {expected}");
+    }
+
+    // 넓이가 0인 삼각형은 선분처럼 동작한다.
+    let degenerate = Triangle::new(glam::Vec3A::ZERO, glam::Vec3A::X, glam::Vec3A::X * 2.0);
+    let closest = degenerate.closest_point(&glam::Vec3A::new(1.5, 1.0, 0.0));
+    assert!(closest.abs_diff_eq(glam::Vec3A::new(1.5, 0.0, 0.0), 1.0e-6), "{closest}");
+}
+
+#[test]
+fn box_triangle_collision_matches_gjk() {
+    let mut rng = StdRng::seed_from_u64(36);
+    let config = GjkConfig::default();
+    let mut hits = 0;
+    for _ in 0..2000 {
+        let triangle = Triangle::new(random_point(&mut rng, 2.0), random_point(&mut rng, 2.0), random_point(&mut rng, 2.0));
+        let rotation = glam::Mat3::from_quat(glam::Quat::from_scaled_axis(random_point(&mut rng, 3.0).into()));
+        let extents = glam::Vec3::new(rng.random_range(0.1..1.0), rng.random_range(0.1..1.0), rng.random_range(0.1..1.0));
+        let cube = BoundingBox::new_rotated(random_point(&mut rng, 2.0).into(), extents, rotation);
+
+        let collides = cube.check_static_collision(&triangle);
+        let distance = match cube.gjk_distance(&triangle, &config) {
+            Ok(Some(closest)) => closest.distance,
+            Ok(None) => 0.0,
+            Err(_) => continue,
+        };
+        // 접한 정도의 차이는 허용한다.
+        if distance > 1.0e-3 {
+            assert!(!collides, "{cube:?}, {triangle:?}: 거리 {distance}");
+        } else if distance == 0.0 {
+            assert!(collides, "{cube:?}, {triangle:?}");
+            hits += 1;
+
+            // 박스를 normal 방향으로 penetration만큼 옮기면 떨어진다.
+            let details = cube.check_static_collision_details(&triangle).unwrap();
+            let mut moved = cube;
+            moved.center += glam::Vec3::from(details.normal * (details.penetration + 1.0e-3));
+            assert!(!moved.check_static_collision(&triangle), "{details:?}");
+        }
+    }
+    assert!(hits > 100, "{hits}");
+}
@@ -0,0 +1,84 @@
+use collision_test::{static_collision::StaticCollision, *};
+
+fn rotation() -> glam::Mat3 {
+    glam::Mat3::from_euler(glam::EulerRot::XYZ, 0.4, 0.9, -0.3)
+}
+
+/// 중심이 박스 안에 있는 구는 가장 가까운 면 밖으로 밀려나야 한다.
+fn check_sphere_inside(cube: &BoundingBox, local_center: glam::Vec3, face: glam::Vec3) {
+    let to_world = cube.rotation().unwrap_or(glam::Mat3::IDENTITY);
+    let sphere = Sphere {
+        center: cube.center + to_world * local_center,
+        radius: 0.1,
+    };
+    let face = to_world * face;
+    // 면까지의 거리에 반지름을 더한 만큼 파고든다.
+    let depth = (cube.extents() - local_center.abs()).min_element() + sphere.radius;
+
+    let details = sphere.check_static_collision_details(cube).unwrap();
+    assert!(details.normal.abs_diff_eq(face.into(), 1.0e-5), "{details:?}");
+    assert!((details.penetration - depth).abs() < 1.0e-5, "{details:?}");
+
+    // 박스 쪽에서 본 normal은 반대 방향이다.
+    let details = cube.check_static_collision_details(&sphere).unwrap();
+    assert!(details.normal.abs_diff_eq(-glam::Vec3A::from(face), 1.0e-5), "{details:?}");
+    assert!((details.penetration - depth).abs() < 1.0e-5, "{details:?}");
+
+    // 밀어낸 구는 면에 접하기만 한다.
+    let pushed = Sphere {
+        center: sphere.center + face * depth,
+        ..sphere
+    };
+    let details = pushed.check_static_collision_details(cube);
+    assert!(details.is_none_or(|details| details.penetration < 1.0e-5), "{details:?}");
+}
+
+#[test]
+fn sphere_centered_inside_aabb_is_pushed_out_of_the_nearest_face() {
+    let cube = BoundingBox::new(glam::Vec3::new(1.0, -2.0, 0.5), glam::Vec3::new(2.0, 1.0, 3.0));
+    check_sphere_inside(&cube, glam::Vec3::new(0.5, 0.7, 0.2), glam::Vec3::Y);
+    check_sphere_inside(&cube, glam::Vec3::new(-1.8, 0.1, -0.4), glam::Vec3::NEG_X);
+    check_sphere_inside(&cube, glam::Vec3::new(0.3, -0.2, -2.9), glam::Vec3::NEG_Z);
+    // 중심과 같은 점은 가장 얇은 축의 면으로 밀려난다.
+    check_sphere_inside(&cube, glam::Vec3::ZERO, glam::Vec3::Y);
+}
+
+#[test]
+fn sphere_centered_inside_obb_is_pushed_out_of_the_nearest_face() {
+    let cube = BoundingBox::new_rotated(glam::Vec3::new(1.0, -2.0, 0.5), glam::Vec3::new(2.0, 1.0, 3.0), rotation());
+    check_sphere_inside(&cube, glam::Vec3::new(0.5, -0.7, 0.2), glam::Vec3::NEG_Y);
+    check_sphere_inside(&cube, glam::Vec3::new(1.9, 0.1, -0.4), glam::Vec3::X);
+    check_sphere_inside(&cube, glam::Vec3::new(0.3, -0.2, 2.6), glam::Vec3::Z);
+}
+
+#[test]
+fn obb_details_for_almost_parallel_boxes() {
+    // 거의 평행한 두 박스의 모서리끼리의 외적은 길이가 0에 가깝다.
+    // 그런 축으로 침투 깊이를 재면 축의 길이만큼 작아지므로, 면의 축에서 구한 깊이가 나와야 한다.
+    let rotation = rotation();
+    let a = BoundingBox::new_rotated(glam::Vec3::ZERO, glam::Vec3::ONE, rotation);
+    let tilted = rotation * glam::Mat3::from_rotation_y(1.0e-4);
+    for (offset, depth) in [(glam::Vec3::new(1.7, 0.0, 0.0), 0.3), (glam::Vec3::new(0.2, -1.5, 0.1), 0.5)] {
+        let b = BoundingBox::new_rotated(rotation * offset, glam::Vec3::ONE, tilted);
+        let details = a.obb_collision_details(&b).unwrap().with_positive_penetration();
+        assert!((details.normal.length() - 1.0).abs() < 1.0e-5, "{details:?}");
+        assert!((details.penetration - depth).abs() < 1.0e-3, "{offset}: {details:?}");
+        // normal * penetration은 a를 b에서 밀어내는 방향이다.
+        assert!(details.normal.dot(glam::Vec3A::from(a.center - b.center)) > 0.0, "{offset}: {details:?}");
+    }
+}
+
+#[test]
+fn positive_penetration_keeps_the_push_vector() {
+    let details = CollisionDetails {
+        normal: glam::Vec3A::Y,
+        penetration: -0.25,
+    };
+    let flipped = details.with_positive_penetration();
+    assert_eq!(flipped.normal, glam::Vec3A::NEG_Y);
+    assert_eq!(flipped.penetration, 0.25);
+    assert_eq!(flipped.normal * flipped.penetration, details.normal * details.penetration);
+
+    let positive = flipped.with_positive_penetration();
+    assert_eq!((positive.normal, positive.penetration), (flipped.normal, flipped.penetration));
+}
@@ -0,0 +1,213 @@
+mod common;
+
+use std::collections::BTreeSet;
+use collision_test::*;
+use common::{all_pairs, random_box};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+const MODES: [SweepMode; 4] = [SweepMode::MultiAxis, SweepMode::SingleAxis(0), SweepMode::SingleAxis(1), SweepMode::SingleAxis(2)];
+
+fn unit_box(x: f32) -> BoundingBox {
+    BoundingBox::new(glam::Vec3::new(x, 0.0, 0.0), glam::Vec3::splat(0.5))
+}
+
+/// update_pairs를 호출하고, 쌍과 이벤트를 data(객체의 번호) 기준으로 바꾼다.
+fn update(sap: &mut SweepAndPrune<usize>) -> (BTreeSet<(usize, usize)>, Vec<PairEvent>) {
+    let events = sap.update_pairs();
+    let pairs = sap.pairs()
+        .map(|(a, b)| {
+            assert!(a < b, "작은 id가 앞에 와야 한다");
+            let (a, b) = (*sap.get(a).unwrap(), *sap.get(b).unwrap());
+            (a.min(b), a.max(b))
+        })
+        .collect();
+    (pairs, events)
+}
+
+/// 이벤트가 이전 쌍에서 현재 쌍으로의 변화와 정확히 같은지 확인한다.
+fn check_events(events: &[PairEvent], ids: &[ProxyId], before: &BTreeSet<(usize, usize)>, after: &BTreeSet<(usize, usize)>) {
+    // 지운 객체의 자리는 재사용되므로, 같은 id 중 가장 나중에 넣은 객체를 찾는다.
+    let index_of = |id: ProxyId| ids.iter().rposition(|&other| other == id).unwrap();
+    let pair_of = |a: ProxyId, b: ProxyId| {
+        assert!(a < b, "작은 id가 앞에 와야 한다");
+        let (a, b) = (index_of(a), index_of(b));
+        (a.min(b), a.max(b))
+    };
+    let mut added = BTreeSet::new();
+    let mut removed = BTreeSet::new();
+    for event in events {
+        let inserted = match *event {
+            PairEvent::Added(a, b) => added.insert(pair_of(a, b)),
+            PairEvent::Removed(a, b) => removed.insert(pair_of(a, b)),
+        };
+        assert!(inserted, "중복된 이벤트: {event:?}");
+    }
+    assert_eq!(added, after.difference(before).copied().collect());
+    assert_eq!(removed, before.difference(after).copied().collect());
+}
+
+#[test]
+fn pairs_and_events_match_all_pairs_while_moving() {
+    for mode in MODES {
+        let mut rng = StdRng::seed_from_u64(30);
+        let mut sap = SweepAndPrune::new(mode);
+        let mut boxes = (0..300).map(|_| Some(random_box(&mut rng))).collect::<Vec<_>>();
+        let mut ids = boxes.iter()
+            .enumerate()
+            .map(|(i, bounding_box)| sap.insert(bounding_box.as_ref().unwrap(), i))
+            .collect::<Vec<_>>();
+
+        let (mut previous, events) = update(&mut sap);
+        assert_eq!(previous, BTreeSet::from_iter(all_pairs(&boxes)), "{mode:?}");
+        check_events(&events, &ids, &BTreeSet::new(), &previous);
+
+        for frame in 0..20 {
+            // 조금씩 움직여 삽입 정렬로 갱신되도록 한다.
+            for i in 0..boxes.len() {
+                if let Some(bounding_box) = boxes[i].as_mut() {
+                    bounding_box.center += glam::Vec3::new(rng.random_range(-0.5..0.5), rng.random_range(-0.5..0.5), rng.random_range(-0.5..0.5));
+                    sap.update(ids[i], bounding_box);
+                }
+            }
+            // 몇 프레임마다 일부를 지우고 새로 넣는다. (새 객체가 절반 미만이므로 여전히 삽입 정렬)
+            if frame % 5 == 4 {
+                for _ in 0..10 {
+                    let i = rng.random_range(0..boxes.len());
+                    if boxes[i].take().is_some() {
+                        assert_eq!(sap.remove(ids[i]), Some(i));
+                    }
+                }
+                for _ in 0..10 {
+                    let bounding_box = random_box(&mut rng);
+                    ids.push(sap.insert(&bounding_box, boxes.len()));
+                    boxes.push(Some(bounding_box));
+                }
+            }
+
+            let (pairs, events) = update(&mut sap);
+            assert_eq!(pairs, BTreeSet::from_iter(all_pairs(&boxes)), "{mode:?}, frame {frame}");
+            check_events(&events, &ids, &previous, &pairs);
+            assert_eq!(sap.len(), boxes.iter().flatten().count());
+            previous = pairs;
+        }
+    }
+}
+
+#[test]
+fn pairs_match_all_pairs_after_large_moves_and_rebuild() {
+    for mode in MODES {
+        let mut rng = StdRng::seed_from_u64(31);
+        let mut sap = SweepAndPrune::new(mode);
+        let mut boxes = (0..100).map(|_| Some(random_box(&mut rng))).collect::<Vec<_>>();
+        let mut ids = boxes.iter()
+            .enumerate()
+            .map(|(i, bounding_box)| sap.insert(bounding_box.as_ref().unwrap(), i))
+            .collect::<Vec<_>>();
+        let (mut previous, _) = update(&mut sap);
+
+        // 모두 멀리 옮기면 끝점 목록이 크게 뒤섞인다.
+        for i in 0..boxes.len() {
+            let bounding_box = random_box(&mut rng);
+            sap.update(ids[i], &bounding_box);
+            boxes[i] = Some(bounding_box);
+        }
+        let (pairs, events) = update(&mut sap);
+        assert_eq!(pairs, BTreeSet::from_iter(all_pairs(&boxes)), "{mode:?}");
+        check_events(&events, &ids, &previous, &pairs);
+        previous = pairs;
+
+        // 절반 넘게 새로 넣으면 전체를 다시 정렬한다.
+        for _ in 0..150 {
+            let bounding_box = random_box(&mut rng);
+            ids.push(sap.insert(&bounding_box, boxes.len()));
+            boxes.push(Some(bounding_box));
+        }
+        let (pairs, events) = update(&mut sap);
+        assert_eq!(pairs, BTreeSet::from_iter(all_pairs(&boxes)), "{mode:?}");
+        check_events(&events, &ids, &previous, &pairs);
+    }
+}
+
+#[test]
+fn approaching_boxes_report_added_then_removed() {
+    for mode in MODES {
+        let mut sap = SweepAndPrune::new(mode);
+        let a = sap.insert(&unit_box(0.0), 0);
+        let b = sap.insert(&unit_box(3.0), 1);
+        assert!(sap.update_pairs().is_empty());
+
+        // 접하기만 해도 겹침으로 취급한다.
+        sap.update(b, &unit_box(1.0));
+        assert_eq!(sap.update_pairs(), vec![PairEvent::Added(a, b)], "{mode:?}");
+        sap.update(b, &unit_box(0.5));
+        assert!(sap.update_pairs().is_empty(), "{mode:?}");
+        assert_eq!(sap.pairs().collect::<Vec<_>>(), vec![(a, b)]);
+
+        // 반대편으로 지나가 떨어진다.
+        sap.update(b, &unit_box(-3.0));
+        assert_eq!(sap.update_pairs(), vec![PairEvent::Removed(a, b)], "{mode:?}");
+        assert_eq!(sap.pairs().count(), 0);
+    }
+}
+
+#[test]
+fn pair_created_and_gone_within_one_update_has_no_event() {
+    for mode in MODES {
+        let mut sap = SweepAndPrune::new(mode);
+        let a = sap.insert(&unit_box(0.0), 0);
+        let b = sap.insert(&unit_box(0.5), 1);
+        assert_eq!(sap.update_pairs(), vec![PairEvent::Added(a, b)]);
+
+        // update_pairs 사이에 멀리 갔다가 돌아와 계속 겹쳐 있는 쌍은 이벤트가 없다.
+        sap.update(b, &unit_box(5.0));
+        sap.update(b, &unit_box(0.8));
+        assert!(sap.update_pairs().is_empty(), "{mode:?}");
+
+        // 넣자마자 지운 객체는 쌍을 만들지 않는다.
+        let c = sap.insert(&unit_box(0.2), 2);
+        assert_eq!(sap.remove(c), Some(2));
+        assert!(sap.update_pairs().is_empty(), "{mode:?}");
+
+        // 지운 객체의 쌍은 Removed로 보고되고, 그 뒤에 자리가 재사용된다.
+        assert_eq!(sap.remove(a), Some(0));
+        assert!(sap.remove(a).is_none());
+        assert_eq!(sap.update_pairs(), vec![PairEvent::Removed(a, b)], "{mode:?}");
+        let d = sap.insert(&unit_box(10.0), 3);
+        assert!(d == a || d == c, "{d:?}");
+        assert!(sap.update_pairs().is_empty());
+        assert_eq!(sap.len(), 2);
+    }
+}
+
+#[test]
+fn update_pairs_with_reports_the_same_events_and_pair_count() {
+    for mode in MODES {
+        let mut rng = StdRng::seed_from_u64(300);
+        let mut boxes = (0..300).map(|_| random_box(&mut rng)).collect::<Vec<_>>();
+        let mut sap = SweepAndPrune::new(mode);
+        let ids = boxes.iter()
+            .enumerate()
+            .map(|(i, bounding_box)| sap.insert(bounding_box, i))
+            .collect::<Vec<_>>();
+        let mut counting = sap.clone();
+
+        for frame in 0..10 {
+            if frame > 0 {
+                for (id, bounding_box) in ids.iter().zip(boxes.iter_mut()) {
+                    bounding_box.center += glam::Vec3::new(rng.random_range(-0.5..0.5), rng.random_range(-0.5..0.5), rng.random_range(-0.5..0.5));
+                    sap.update(*id, bounding_box);
+                    counting.update(*id, bounding_box);
+                }
+            }
+
+            let expected = sap.update_pairs();
+            let mut events = Vec::new();
+            counting.update_pairs_with(|event| events.push(event));
+            events.sort_by_key(|event| match *event {
+                PairEvent::Added(a, b) | PairEvent::Removed(a, b) => (a, b),
+            });
+            assert_eq!(events, expected, "{mode:?}");
+            assert_eq!(counting.pair_count(), sap.pairs().count(), "{mode:?}");
+        }
+    }
+}